@@ -6,7 +6,7 @@ use std::time::Duration;
 fn Example(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
     let (width, height) = hooks.use_terminal_size();
     let mut system = hooks.use_context_mut::<SystemContext>();
-    let time = hooks.use_state(|| Local::now());
+    let time = hooks.use_state(Local::now);
     let should_exit = hooks.use_state(|| false);
 
     hooks.use_future(async move {
@@ -18,12 +18,11 @@ fn Example(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
 
     hooks.use_terminal_events({
         move |event| match event {
-            TerminalEvent::Key(KeyEvent { code, kind, .. }) if kind != KeyEventKind::Release => {
-                match code {
-                    KeyCode::Char('q') => should_exit.set(true),
-                    _ => {}
-                }
-            }
+            TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                kind,
+                ..
+            }) if kind != KeyEventKind::Release => should_exit.set(true),
             _ => {}
         }
     });
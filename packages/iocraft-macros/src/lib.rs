@@ -3,23 +3,210 @@
 #![warn(missing_docs)]
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
-    braced, parenthesized,
+    braced, bracketed, parenthesized,
     parse::{Parse, ParseStream, Parser},
     parse_macro_input,
     punctuated::Punctuated,
     spanned::Spanned,
-    token::{Brace, Comma, Paren},
-    DeriveInput, Error, Expr, FieldValue, FnArg, GenericParam, Ident, ItemFn, ItemStruct, Lifetime,
-    Lit, Member, Pat, Result, Token, Type, TypePath,
+    token::{Brace, Bracket, Comma, Paren},
+    AttrStyle, Attribute, DeriveInput, Error, Expr, FieldValue, FnArg, GenericParam, ItemFn,
+    ItemStruct, Lifetime, Lit, Member, Meta, Pat, Result, ReturnType, Token, Type, TypePath,
+    TypeReference, UnOp,
 };
 use uuid::Uuid;
 
+mod kw {
+    syn::custom_keyword!(keyed);
+    syn::custom_keyword!(by);
+}
+
 enum ParsedElementChild {
     Element(ParsedElement),
     Expr(Expr),
+    For(Box<ParsedForChild>),
+    // A child preceded by `#[cfg(...)]` attributes, which only apply to that child.
+    Cfg(Vec<Attribute>, Box<ParsedElementChild>),
+}
+
+/// A loop which declares children once per item, of the form:
+///
+/// #for item in items keyed by item.id {
+///     // children
+/// }
+struct ParsedForChild {
+    pat: Pat,
+    iter: Expr,
+    key: Option<Expr>,
+    children: Vec<ParsedElementChild>,
+}
+
+/// The keys of the loops enclosing a child, which are used to give each iteration's elements
+/// distinct keys.
+#[derive(Clone, Default)]
+struct LoopKeys {
+    idents: Vec<proc_macro2::Ident>,
+    any_keyed: bool,
+}
+
+/// Parses any `#[cfg(...)]` attributes preceding a child. Other attributes are rejected, since
+/// they have no meaning for children.
+fn parse_child_attributes(input: ParseStream) -> Result<Vec<Attribute>> {
+    let mut attrs = Vec::new();
+    while input.peek(Token![#]) && input.peek2(Bracket) {
+        let pound_token = input.parse()?;
+        let meta_input;
+        let bracket_token = bracketed!(meta_input in input);
+        let meta: Meta = meta_input.parse()?;
+        if !meta.path().is_ident("cfg") {
+            return Err(Error::new(
+                meta.span(),
+                "only `#[cfg(...)]` attributes are supported on children",
+            ));
+        }
+        attrs.push(Attribute {
+            pound_token,
+            style: AttrStyle::Outer,
+            bracket_token,
+            meta,
+        });
+    }
+    Ok(attrs)
+}
+
+fn parse_children(input: ParseStream) -> Result<Vec<ParsedElementChild>> {
+    let mut children = Vec::new();
+    while !input.is_empty() {
+        let attrs = parse_child_attributes(input)?;
+        let child = if input.peek(Token![#]) && input.peek2(Token![for]) {
+            input.parse::<Token![#]>()?;
+            input.parse::<Token![for]>()?;
+            let pat = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![in]>()?;
+            let iter = Expr::parse_without_eager_brace(input)?;
+            let key = if input.peek(kw::keyed) {
+                input.parse::<kw::keyed>()?;
+                input.parse::<kw::by>()?;
+                Some(Expr::parse_without_eager_brace(input)?)
+            } else {
+                None
+            };
+            let body_input;
+            braced!(body_input in input);
+            ParsedElementChild::For(Box::new(ParsedForChild {
+                pat,
+                iter,
+                key,
+                children: parse_children(&body_input)?,
+            }))
+        } else if input.peek(Token![#]) {
+            input.parse::<Token![#]>()?;
+            let child_input;
+            parenthesized!(child_input in input);
+            ParsedElementChild::Expr(child_input.parse()?)
+        } else {
+            ParsedElementChild::Element(input.parse()?)
+        };
+        if attrs.is_empty() {
+            children.push(child);
+        } else {
+            children.push(ParsedElementChild::Cfg(attrs, Box::new(child)));
+        }
+    }
+    Ok(children)
+}
+
+/// Generates the code which adds the given children to `_iocraft_element`.
+fn children_to_tokens(
+    children: &[ParsedElementChild],
+    loop_keys: &LoopKeys,
+) -> proc_macro2::TokenStream {
+    children
+        .iter()
+        .map(|child| match child {
+            ParsedElementChild::Element(child) => {
+                let child = child.to_tokens_with_loop_keys(loop_keys);
+                quote!(::iocraft::extend_with_elements(&mut _iocraft_element.props.children, #child);)
+            }
+            ParsedElementChild::Expr(expr) => {
+                quote!(::iocraft::extend_with_elements(&mut _iocraft_element.props.children, #expr);)
+            }
+            ParsedElementChild::For(for_child) => {
+                let ParsedForChild {
+                    pat,
+                    iter,
+                    key,
+                    children,
+                } = &**for_child;
+                let key_ident = quote::format_ident!("_iocraft_loop_key_{}", loop_keys.idents.len());
+                let key_expr = match key {
+                    Some(key) => quote!(#key),
+                    None => quote!(_iocraft_index),
+                };
+                let mut loop_keys = loop_keys.clone();
+                loop_keys.idents.push(key_ident.clone());
+                loop_keys.any_keyed |= key.is_some();
+                let children = children_to_tokens(children, &loop_keys);
+                quote! {
+                    for (_iocraft_index, #pat) in ::std::iter::IntoIterator::into_iter(#iter).enumerate() {
+                        let #key_ident = #key_expr;
+                        #children
+                    }
+                }
+            }
+            ParsedElementChild::Cfg(attrs, child) => {
+                let child = children_to_tokens(std::slice::from_ref(&**child), loop_keys);
+                quote! {
+                    #(#attrs)*
+                    {
+                        #child
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates a struct field initializer for a property or style value, with conversions for
+/// values such as `50pct`. Values marked with a `#[raw]` attribute are assigned as-is, without any
+/// conversion.
+fn field_value_to_tokens(
+    FieldValue {
+        attrs,
+        member,
+        expr,
+        ..
+    }: &FieldValue,
+) -> proc_macro2::TokenStream {
+    if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("raw")) {
+        if let Err(err) = attr.meta.require_path_only() {
+            return err.to_compile_error();
+        }
+        return quote_spanned!(expr.span()=> #member: #expr);
+    }
+    // The conversions are spanned to the values so that type errors point at the offending value
+    // rather than the whole macro invocation. The member keeps its own span, so unknown fields are
+    // reported at their names.
+    if let Some(value) = percent_value(expr) {
+        return quote_spanned!(expr.span()=> #member: ::iocraft::Percent(#value).into());
+    }
+    quote_spanned!(expr.span()=> #member: (#expr).into())
+}
+
+/// Returns the value of a percentage such as `50pct`, `12.5pct`, or `-10pct`.
+fn percent_value(expr: &Expr) -> Option<f32> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(lit) if lit.suffix() == "pct" => Some(lit.base10_parse::<f32>().unwrap()),
+            Lit::Float(lit) if lit.suffix() == "pct" => Some(lit.base10_parse::<f32>().unwrap()),
+            _ => None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            percent_value(&unary.expr).map(|value| -value)
+        }
+        _ => None,
+    }
 }
 
 struct ParsedElement {
@@ -45,21 +232,13 @@ impl Parse for ParsedElement {
             Punctuated::new()
         };
 
-        let mut children = Vec::new();
-        if input.peek(Brace) {
+        let children = if input.peek(Brace) {
             let children_input;
             braced!(children_input in input);
-            while !children_input.is_empty() {
-                if children_input.peek(Token![#]) {
-                    children_input.parse::<Token![#]>()?;
-                    let child_input;
-                    parenthesized!(child_input in children_input);
-                    children.push(ParsedElementChild::Expr(child_input.parse()?));
-                } else {
-                    children.push(ParsedElementChild::Element(children_input.parse()?));
-                }
-            }
-        }
+            parse_children(&children_input)?
+        } else {
+            Vec::new()
+        };
 
         Ok(Self {
             props,
@@ -71,6 +250,12 @@ impl Parse for ParsedElement {
 
 impl ToTokens for ParsedElement {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.to_tokens_with_loop_keys(&LoopKeys::default()));
+    }
+}
+
+impl ParsedElement {
+    fn to_tokens_with_loop_keys(&self, loop_keys: &LoopKeys) -> proc_macro2::TokenStream {
         let ty = &self.ty;
 
         let decl_key = Uuid::new_v4().as_u128();
@@ -79,59 +264,46 @@ impl ToTokens for ParsedElement {
             .props
             .iter()
             .find_map(|FieldValue { member, expr, .. }| match member {
-                Member::Named(ident) if ident == "key" => Some(quote!((#decl_key, #expr))),
+                Member::Named(ident) if ident == "key" => {
+                    Some(quote!(::iocraft::ElementKey::explicit(#decl_key, #expr)))
+                }
                 _ => None,
             })
-            .unwrap_or_else(|| quote!(#decl_key));
+            .unwrap_or_else(|| {
+                let loop_keys_idents = &loop_keys.idents;
+                if loop_keys_idents.is_empty() {
+                    quote!(::iocraft::ElementKey::new(#decl_key))
+                } else if loop_keys.any_keyed {
+                    quote!(::iocraft::ElementKey::explicit(#decl_key, (#(#loop_keys_idents.clone(),)*)))
+                } else {
+                    quote!(::iocraft::ElementKey::new((#decl_key, (#(#loop_keys_idents,)*))))
+                }
+            });
 
         let props = self
             .props
             .iter()
-            .filter_map(|FieldValue { member, expr, .. }| match member {
-                Member::Named(ident) if ident == "key" => None,
-                _ => Some(match expr {
-                    Expr::Lit(lit) => match &lit.lit {
-                        Lit::Int(lit) if lit.suffix() == "pct" => {
-                            let value = lit.base10_parse::<f32>().unwrap();
-                            quote!(#member: ::iocraft::Percent(#value).into())
-                        }
-                        Lit::Float(lit) if lit.suffix() == "pct" => {
-                            let value = lit.base10_parse::<f32>().unwrap();
-                            quote!(#member: ::iocraft::Percent(#value).into())
-                        }
-                        _ => quote!(#member: (#expr).into()),
-                    },
-                    _ => quote!(#member: (#expr).into()),
-                }),
-            })
+            .filter(|FieldValue { member, .. }| !matches!(member, Member::Named(ident) if ident == "key"))
+            .map(field_value_to_tokens)
             .collect::<Vec<_>>();
 
-        let set_children = if !self.children.is_empty() {
-            let children = self.children.iter().map(|child| match child {
-                ParsedElementChild::Element(child) => quote!(#child),
-                ParsedElementChild::Expr(expr) => quote!(#expr),
-            });
-            Some(quote! {
-                #(::iocraft::extend_with_elements(&mut _iocraft_element.props.children, #children);)*
-            })
-        } else {
-            None
-        };
+        let set_children = children_to_tokens(&self.children, &LoopKeys::default());
 
-        tokens.extend(quote! {
+        quote! {
             {
                 type Props<'a> = <#ty as ::iocraft::ElementType>::Props<'a>;
+                #[allow(clippy::needless_update, clippy::useless_conversion)]
                 let mut _iocraft_element = ::iocraft::Element::<#ty>{
-                    key: ::iocraft::ElementKey::new(#key),
+                    key: #key,
                     props: Props{
                         #(#props,)*
-                        ..core::default::Default::default()
+                        ..<Props as ::iocraft::DefaultProps>::default_props()
                     },
                 };
                 #set_children
                 _iocraft_element
             }
-        });
+        }
     }
 }
 
@@ -145,12 +317,45 @@ pub fn element(input: TokenStream) -> TokenStream {
 
 struct ParsedProps {
     def: ItemStruct,
+    field_defaults: Vec<(Member, Option<Expr>)>,
 }
 
 impl Parse for ParsedProps {
     fn parse(input: ParseStream) -> Result<Self> {
         let def: ItemStruct = input.parse()?;
-        Ok(Self { def })
+
+        let mut field_defaults = Vec::new();
+        let mut has_custom_defaults = false;
+        for (i, field) in def.fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            let mut default = None;
+            for attr in &field.attrs {
+                if !attr.path().is_ident("props") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("default") {
+                        default = Some(meta.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported props attribute, expected `default = ...`"))
+                    }
+                })?;
+            }
+            has_custom_defaults |= default.is_some();
+            field_defaults.push((member, default));
+        }
+        if !has_custom_defaults {
+            field_defaults.clear();
+        }
+
+        Ok(Self {
+            def,
+            field_defaults,
+        })
     }
 }
 
@@ -190,59 +395,85 @@ impl ToTokens for ParsedProps {
         };
 
         // If the struct is generic over lifetimes, emit code that will break things at compile
-        // time when the struct is not covariant with respect to its lifetimes.
+        // time when the struct is not covariant with respect to its lifetimes. The check converts
+        // the struct with longer lifetimes into the struct with shorter ones, which only compiles
+        // if the struct is covariant. If it isn't, rustc explains which field makes it invariant,
+        // and the error is spanned to the struct's name.
         if lifetime_generic_count > 0 {
+            let check_generics = |prefix: &str| {
+                let mut lifetime_index = 0;
+                def.generics
+                    .params
+                    .iter()
+                    .map(|param| match param {
+                        GenericParam::Type(ty) => {
+                            let name = &ty.ident;
+                            quote!(#name)
+                        }
+                        GenericParam::Lifetime(_) => {
+                            let lt = Lifetime::new(
+                                format!("'{}{}", prefix, lifetime_index).as_str(),
+                                name.span(),
+                            );
+                            lifetime_index += 1;
+                            quote!(#lt)
+                        }
+                        GenericParam::Const(c) => {
+                            let name = &c.ident;
+                            quote!(#name)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let long_generics = check_generics("long");
+            let short_generics = check_generics("short");
+
             let generic_decls = {
                 let mut lifetime_index = 0;
                 def.generics.params.iter().map(move |param| match param {
                     GenericParam::Lifetime(_) => {
-                        let a = Lifetime::new(
-                            format!("'a{}", lifetime_index).as_str(),
-                            Span::call_site(),
-                        );
-                        let b = Lifetime::new(
-                            format!("'b{}", lifetime_index).as_str(),
-                            Span::call_site(),
+                        let short = Lifetime::new(
+                            format!("'short{}", lifetime_index).as_str(),
+                            name.span(),
                         );
+                        let long =
+                            Lifetime::new(format!("'long{}", lifetime_index).as_str(), name.span());
                         lifetime_index += 1;
-                        quote!(#a, #b: #a)
+                        quote!(#short, #long: #short)
                     }
                     _ => quote!(#param),
                 })
             };
 
-            let test_args = ["a", "b"].iter().map(|arg| {
-                let mut lifetime_index = 0;
-                let generic_params = def.generics.params.iter().map(|param| match param {
-                    GenericParam::Type(ty) => {
-                        let name = &ty.ident;
-                        quote!(#name)
-                    }
-                    GenericParam::Lifetime(_) => {
-                        let lt = Lifetime::new(
-                            format!("'{}{}", arg, lifetime_index).as_str(),
-                            Span::call_site(),
-                        );
-                        lifetime_index += 1;
-                        quote!(#lt)
-                    }
-                    GenericParam::Const(c) => {
-                        let name = &c.ident;
-                        quote!(#name)
+            tokens.extend(quote_spanned! { name.span() =>
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn props_must_be_covariant_over_their_lifetimes<#(#generic_decls),*>(
+                        props: #name<#(#long_generics),*>,
+                    ) -> #name<#(#short_generics),*> {
+                        props
                     }
-                });
-                let arg_ident = Ident::new(arg, Span::call_site());
-                quote!(#arg_ident: &#name<#(#generic_params),*>)
+                };
             });
+        }
 
+        // If any fields have custom defaults, implement `Default` for the struct.
+        if !self.field_defaults.is_empty() {
+            let fields = self
+                .field_defaults
+                .iter()
+                .map(|(member, default)| match default {
+                    Some(default) => quote!(#member: #default),
+                    None => quote!(#member: ::core::default::Default::default()),
+                });
             tokens.extend(quote! {
-                const _: () = {
-                    fn take_two<T>(_a: T, _b: T) {}
-
-                    fn test_type_covariance<#(#generic_decls),*>(#(#test_args),*) {
-                        take_two(a, b)
+                impl #generics ::core::default::Default for #name #bracketed_generic_names #where_clause {
+                    fn default() -> Self {
+                        Self {
+                            #(#fields,)*
+                        }
                     }
-                };
+                }
             });
         }
 
@@ -257,28 +488,115 @@ impl ToTokens for ParsedProps {
 /// Most importantly, this marks a struct as being
 /// [covariant](https://doc.rust-lang.org/nomicon/subtyping.html). If the struct is not actually
 /// covariant, compilation will fail.
-#[proc_macro_derive(Props)]
+///
+/// Props used with the `element!` macro must also implement `Default`. Typically this is done via
+/// `#[derive(Default)]`, but if a field's type doesn't implement `Default` or needs a different
+/// default value, you can instead annotate fields with `#[props(default = expr)]`. If any field
+/// has this attribute, `Default` will be implemented for the struct, using the given expressions
+/// for the annotated fields and `Default::default()` for the rest:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[derive(Props)]
+/// struct LoadingProps {
+///     #[props(default = 80)]
+///     interval_ms: u64,
+///     #[props(default = "Loading...".to_string())]
+///     label: String,
+///     color: Option<Color>,
+/// }
+/// ```
+#[proc_macro_derive(Props, attributes(props))]
 pub fn derive_props(item: TokenStream) -> TokenStream {
     let props = parse_macro_input!(item as ParsedProps);
     quote!(#props).into()
 }
 
+struct ParsedStyle {
+    ty: Option<TypePath>,
+    fields: Punctuated<FieldValue, Comma>,
+}
+
+impl Parse for ParsedStyle {
+    /// Parses a style of either of the forms:
+    ///
+    /// width: 50pct, padding: 1
+    /// MyStyle { width: 50pct, padding: 1 }
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        if fork.parse::<TypePath>().is_ok() && fork.peek(Brace) {
+            let ty = input.parse()?;
+            let fields_input;
+            braced!(fields_input in input);
+            Ok(Self {
+                ty: Some(ty),
+                fields: Punctuated::parse_terminated(&fields_input)?,
+            })
+        } else {
+            Ok(Self {
+                ty: None,
+                fields: Punctuated::parse_terminated(input)?,
+            })
+        }
+    }
+}
+
+impl ToTokens for ParsedStyle {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ty = match &self.ty {
+            Some(ty) => quote!(#ty),
+            None => quote!(::iocraft::LayoutStyle),
+        };
+        let fields = self.fields.iter().map(field_value_to_tokens);
+        tokens.extend(quote! {
+            {
+                #[allow(clippy::needless_update, clippy::useless_conversion)]
+                let _iocraft_style = #ty {
+                    #(#fields,)*
+                    ..::core::default::Default::default()
+                };
+                _iocraft_style
+            }
+        });
+    }
+}
+
+// This is documented in the `iocraft` crate instead so that links to `iocraft` types resolve correctly.
+#[allow(missing_docs)]
+#[proc_macro]
+pub fn style(input: TokenStream) -> TokenStream {
+    let style = parse_macro_input!(input as ParsedStyle);
+    quote!(#style).into()
+}
+
 struct ParsedComponent {
     f: ItemFn,
+    memo: bool,
     props_type: Option<Box<Type>>,
+    context_arg: Option<TypeReference>,
     impl_args: Vec<proc_macro2::TokenStream>,
 }
 
 impl Parse for ParsedComponent {
     fn parse(input: ParseStream) -> Result<Self> {
-        let f: ItemFn = input.parse()?;
+        let mut f: ItemFn = input.parse()?;
 
         let mut props_type = None;
+        let mut context_arg = None;
         let mut impl_args = Vec::new();
 
         for arg in &f.sig.inputs {
             match arg {
                 FnArg::Typed(arg) => {
+                    // Other attributes, such as lints, are carried over to the implementation
+                    // as-is, but the argument list must stay the same.
+                    if let Some(attr) = arg.attrs.iter().find(|attr| attr.path().is_ident("cfg")) {
+                        return Err(Error::new(
+                            attr.span(),
+                            "`cfg` attributes aren't supported on component arguments",
+                        ));
+                    }
+
                     let name = match &*arg.pat {
                         Pat::Ident(arg) => arg.ident.to_string(),
                         _ => return Err(Error::new(arg.pat.span(), "invalid argument")),
@@ -294,10 +612,16 @@ impl Parse for ParsedComponent {
                                     props_type = Some(r.elem.clone());
                                     impl_args.push(quote!(props));
                                 }
+                                // Props taken by value are cloned, since the component's props
+                                // are only ever lent to it.
+                                Type::Path(_) => {
+                                    props_type = Some(arg.ty.clone());
+                                    impl_args.push(quote!(::std::clone::Clone::clone(&*props)));
+                                }
                                 _ => {
                                     return Err(Error::new(
                                         arg.ty.span(),
-                                        "invalid `props` type (must be a reference)",
+                                        "invalid `props` type (must be `&MyProps`, `&mut MyProps`, or `MyProps` by value)",
                                     ))
                                 }
                             }
@@ -316,26 +640,79 @@ impl Parse for ParsedComponent {
                                 ))
                             }
                         },
-                        _ => {
-                            return Err(Error::new(
-                                arg.span(),
-                                "unexpected argument (must be named `props` or `hooks`)",
-                            ))
+                        "context" | "_context" => {
+                            if context_arg.is_some() {
+                                return Err(Error::new(arg.span(), "duplicate `context` argument"));
+                            }
+                            match &*arg.ty {
+                                Type::Reference(r) => {
+                                    context_arg = Some(r.clone());
+                                    impl_args.push(match r.mutability {
+                                        Some(_) => quote!(&mut *context),
+                                        None => quote!(&*context),
+                                    });
+                                }
+                                _ => {
+                                    return Err(Error::new(
+                                        arg.ty.span(),
+                                        "invalid `context` type (must be a reference)",
+                                    ))
+                                }
+                            }
                         }
+                        _ => return Err(Error::new(
+                            arg.span(),
+                            "unexpected argument (must be named `props`, `hooks`, or `context`)",
+                        )),
                     }
                 }
                 _ => return Err(Error::new(arg.span(), "invalid argument")),
             }
         }
 
+        // Doc comments are allowed on the arguments for the sake of documenting them inline, but
+        // the compiler doesn't accept them on function parameters, so they're dropped.
+        for arg in &mut f.sig.inputs {
+            if let FnArg::Typed(arg) = arg {
+                arg.attrs.retain(|attr| !attr.path().is_ident("doc"));
+            }
+        }
+
         Ok(Self {
             f,
+            memo: false,
             props_type,
+            context_arg,
             impl_args,
         })
     }
 }
 
+impl ParsedComponent {
+    fn parse_args(&mut self, args: Punctuated<syn::Ident, Comma>) -> Result<()> {
+        for arg in args {
+            match arg.to_string().as_str() {
+                "memo" => {
+                    if self.f.sig.generics.lifetimes().next().is_some() {
+                        return Err(Error::new(
+                            self.f.sig.generics.span(),
+                            "memoized components can't have lifetime parameters",
+                        ));
+                    }
+                    self.memo = true;
+                }
+                _ => {
+                    return Err(Error::new(
+                        arg.span(),
+                        "unexpected argument (expected `memo`)",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl ToTokens for ParsedComponent {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let vis = &self.f.vis;
@@ -343,35 +720,218 @@ impl ToTokens for ParsedComponent {
         let args = &self.f.sig.inputs;
         let block = &self.f.block;
         let output = &self.f.sig.output;
-        let generics = &self.f.sig.generics;
         let impl_args = &self.impl_args;
 
+        // Lifetime parameters stay on the implementation, with the props' lifetime mapped to that
+        // of `Component::Props`. Type and const parameters are moved to the component type itself.
+        let fn_generics = self
+            .f
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter(|param| matches!(param, GenericParam::Lifetime(_)))
+            .collect::<Vec<_>>();
+        let props_lifetime = fn_generics
+            .iter()
+            .find_map(|param| match param {
+                GenericParam::Lifetime(lt) => Some(lt.lifetime.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| Lifetime::new("'a", proc_macro2::Span::call_site()));
+        let type_generics = self
+            .f
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+            .collect::<Vec<_>>();
+        let type_generics_names = type_generics
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(ty) => ty.ident.clone(),
+                GenericParam::Const(c) => c.ident.clone(),
+                GenericParam::Lifetime(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        let type_params = type_generics
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(ty) => Some(&ty.ident),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let where_clause = &self.f.sig.generics.where_clause;
+        let where_predicates = where_clause
+            .iter()
+            .flat_map(|clause| clause.predicates.iter());
+        let (struct_generics, struct_type_generics) = if type_generics.is_empty() {
+            (quote!(), quote!())
+        } else {
+            (
+                quote!(<#(#type_generics),*>),
+                quote!(<#(#type_generics_names),*>),
+            )
+        };
+        let component_where_clause = quote! {
+            where #(#type_params: 'static,)* #(#where_predicates,)*
+        };
+        // The parameters aren't used by the component's fields, so they need a marker. Function
+        // pointers are always `Send`, `Sync`, and `Unpin`, whatever the parameters are.
+        let (marker_field, marker_value) = if type_generics.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(quote! {
+                    _marker: ::std::marker::PhantomData<fn() -> (#(#type_params,)*)>,
+                }),
+                Some(quote!(_marker: ::std::marker::PhantomData,)),
+            )
+        };
+
+        // Doc comments describe the component, so they go on the generated struct. `cfg`
+        // attributes apply to everything that's generated, and any others, such as lints, stay on
+        // the function itself.
+        let is_doc = |attr: &&Attribute| attr.path().is_ident("doc");
+        let is_cfg = |attr: &&Attribute| attr.path().is_ident("cfg");
+        let struct_attrs = self
+            .f
+            .attrs
+            .iter()
+            .filter(|attr| is_doc(attr) || is_cfg(attr))
+            .collect::<Vec<_>>();
+        let cfg_attrs = self.f.attrs.iter().filter(is_cfg).collect::<Vec<_>>();
+        let fn_attrs = self
+            .f
+            .attrs
+            .iter()
+            .filter(|attr| !is_doc(attr) && !is_cfg(attr))
+            .collect::<Vec<_>>();
+
         let props_type_name = self
             .props_type
             .as_ref()
             .map(|ty| quote!(#ty))
             .unwrap_or_else(|| quote!(::iocraft::NoProps));
 
+        let get_context = self.context_arg.as_ref().map(|r| {
+            let ty = &r.elem;
+            let message = format!(
+                "component `{}` requires a `{}` context, but none was provided",
+                name,
+                ty.to_token_stream().to_string().replace(' ', ""),
+            );
+            match r.mutability {
+                Some(_) => quote! {
+                    let mut context = ::iocraft::hooks::UseContext::try_use_context_mut::<#ty>(&hooks)
+                        .expect(#message);
+                },
+                None => quote! {
+                    let context = ::iocraft::hooks::UseContext::try_use_context::<#ty>(&hooks)
+                        .expect(#message);
+                },
+            }
+        });
+
+        // Components which return `()` render nothing, and those which return an `Option` render
+        // nothing when it's `None`.
+        let render = match &self.f.sig.output {
+            ReturnType::Default => quote! {
+                Self::implementation(#(#impl_args),*);
+                None
+            },
+            ReturnType::Type(_, ty) => match &**ty {
+                Type::Tuple(tuple) if tuple.elems.is_empty() => quote! {
+                    Self::implementation(#(#impl_args),*);
+                    None
+                },
+                Type::Path(TypePath { path, .. })
+                    if path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Option") =>
+                {
+                    quote!(Self::implementation(#(#impl_args),*).map(::std::convert::Into::into))
+                }
+                _ => quote!(Some(Self::implementation(#(#impl_args),*).into())),
+            },
+        };
+
+        if self.memo {
+            tokens.extend(quote! {
+                #(#struct_attrs)*
+                #vis struct #name #struct_generics #where_clause {
+                    props: ::std::option::Option<#props_type_name>,
+                    #marker_field
+                }
+
+                #(#cfg_attrs)*
+                impl #struct_generics #name #struct_type_generics #where_clause {
+                    #(#fn_attrs)*
+                    fn implementation<#(#fn_generics),*>(#args) #output #block
+                }
+
+                #(#cfg_attrs)*
+                impl #struct_generics ::iocraft::Component for #name #struct_type_generics #component_where_clause #props_type_name: ::std::marker::Unpin {
+                    type Props<#props_lifetime> = #props_type_name;
+
+                    fn new(_props: &Self::Props<'_>) -> Self {
+                        Self {
+                            props: None,
+                            #marker_value
+                        }
+                    }
+
+                    fn update(&mut self, props: &mut Self::Props<'_>, mut hooks: ::iocraft::Hooks, updater: &mut ::iocraft::ComponentUpdater) {
+                        // Updating the children consumes parts of their properties, such as handlers, so
+                        // when the render is skipped, the children are left as they are. Any changes of
+                        // their own are reported via `has_changes`, which causes a re-render.
+                        if hooks.has_changes() || self.props.as_ref() != Some(&*props) {
+                            let mut e: ::std::option::Option<::iocraft::AnyElement<'static>> = {
+                                let mut hooks = hooks.with_context_stack(updater.component_context_stack());
+                                #get_context
+                                #render
+                            };
+                            self.props = Some(::std::clone::Clone::clone(&*props));
+                            updater.update_children(e.iter_mut(), None);
+                        }
+                    }
+                }
+            });
+            return;
+        }
+
+        let (struct_body, new_body) = match &marker_field {
+            Some(marker_field) => (quote!({ #marker_field }), quote!({ #marker_value })),
+            None => (quote!(;), quote!()),
+        };
+
         tokens.extend(quote! {
-            #vis struct #name;
+            #(#struct_attrs)*
+            #vis struct #name #struct_generics #where_clause #struct_body
 
-            impl #name {
-                fn implementation #generics (#args) #output #block
+            #(#cfg_attrs)*
+            impl #struct_generics #name #struct_type_generics #where_clause {
+                #(#fn_attrs)*
+                fn implementation<#(#fn_generics),*>(#args) #output #block
             }
 
-            impl ::iocraft::Component for #name {
-                type Props<'a> = #props_type_name;
+            #(#cfg_attrs)*
+            impl #struct_generics ::iocraft::Component for #name #struct_type_generics #component_where_clause {
+                type Props<#props_lifetime> = #props_type_name;
 
                 fn new(_props: &Self::Props<'_>) -> Self {
-                    Self
+                    Self #new_body
                 }
 
                 fn update(&mut self, props: &mut Self::Props<'_>, mut hooks: ::iocraft::Hooks, updater: &mut ::iocraft::ComponentUpdater) {
-                    let mut e = {
+                    let mut e: ::std::option::Option<::iocraft::AnyElement<'_>> = {
                         let mut hooks = hooks.with_context_stack(updater.component_context_stack());
-                        Self::implementation(#(#impl_args),*).into()
+                        #get_context
+                        #render
                     };
-                    updater.update_children([&mut e], None);
+                    updater.update_children(e.iter_mut(), None);
                 }
             }
         });
@@ -386,29 +946,177 @@ impl ToTokens for ParsedComponent {
 #[doc = include_str!("../examples/counter.rs")]
 /// ```
 ///
-/// The function is allowed to take up to two arguments, one named `props`, for the component's
-/// properties and one named `hooks`, for hooks.
+/// Components which only exist for their side effects, such as registering key bindings or
+/// running a background task, can return `()` instead, in which case they render nothing. The
+/// function can also return an `Option` of an element to render nothing only some of the time:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn QuitOnEsc(mut hooks: Hooks) {
+///     let should_quit = hooks.use_state(|| false);
+///     hooks.use_keymap(KeyBindings::new().bind(KeyCode::Esc, move || should_quit.set(true)));
+///     if should_quit.get() {
+///         hooks.use_context_mut::<SystemContext>().exit();
+///     }
+/// }
+///
+/// #[derive(Default, Props)]
+/// struct ErrorMessageProps {
+///     error: Option<String>,
+/// }
+///
+/// #[component]
+/// fn ErrorMessage(props: &ErrorMessageProps) -> Option<impl Into<AnyElement<'static>>> {
+///     let error = props.error.as_ref()?;
+///     Some(element!(Text(content: error, color: Color::Red)))
+/// }
+/// ```
+///
+/// The function is allowed to take up to three arguments: one named `props`, for the component's
+/// properties, one named `hooks`, for hooks, and one named `context`, for a reference to a context
+/// provided by a `ContextProvider` ancestor.
+/// The `props` argument may be a shared reference, a mutable reference, or a value. Props taken by
+/// value are cloned on each render, so they must implement [`Clone`], which makes this best suited
+/// to small props.
+///
+/// Taking `props` by mutable reference allows for two-way binding, where a component writes back
+/// to data that it's given. Keep in mind that the props are created anew by the parent each time
+/// it renders, so changes to the props' own fields are discarded. For changes to reach the parent,
+/// the props need to mutably borrow data that the parent owns, or share it via interior
+/// mutability, such as with a `State`:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[derive(Default, Props)]
+/// struct CounterProps<'a> {
+///     count: Option<&'a mut i32>,
+/// }
+///
+/// #[component]
+/// fn Counter<'a>(props: &mut CounterProps<'a>) -> impl Into<AnyElement<'static>> {
+///     if let Some(count) = props.count.as_mut() {
+///         **count += 1;
+///     }
+///     element!(Box)
+/// }
+///
+/// let mut count = 0;
+/// element!(Counter(count: &mut count)).to_string();
+/// assert_eq!(count, 1);
+/// ```
+/// The `context` argument is looked up by type and may be a mutable reference if the context was
+/// provided mutably. If no matching context is available, the component will panic.
+///
+/// Doc comments on the function document the generated component type, and other attributes,
+/// such as `#[allow(...)]`, are applied to the function itself. Arguments may have lint attributes
+/// and doc comments too, but not `#[cfg(...)]`.
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[component]
+/// fn Greeting(context: &User) -> impl Into<AnyElement<'static>> {
+///     element! {
+///         Text(content: format!("Hello, {}!", context.name))
+///     }
+/// }
+/// ```
 ///
 /// Here is an example of a component that takes a reference to a `Vec` of `User` structs via properties:
 ///
 /// ```
 #[doc = include_str!("../examples/table.rs")]
 /// ```
+///
+/// # Generics
+///
+/// Components may be generic over types, in which case the generated component type takes the
+/// same type parameters, with the same bounds. They're given explicitly when the component is
+/// used. A lifetime parameter is treated as the lifetime of the component's properties.
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::fmt::Display;
+/// #[derive(Default, Props)]
+/// struct ListProps<T> {
+///     items: Vec<T>,
+/// }
+///
+/// #[component]
+/// fn List<T: Display + Send + Sync>(props: &ListProps<T>) -> impl Into<AnyElement<'static>> {
+///     element! {
+///         Box(flex_direction: FlexDirection::Column) {
+///             #(props.items.iter().map(|item| element!(Text(content: item.to_string()))))
+///         }
+///     }
+/// }
+///
+/// let numbers = element!(List<i32>(items: vec![1, 2, 3]));
+/// let names = element!(List<String>(items: vec!["alice".to_string(), "bob".to_string()]));
+/// ```
+///
+/// # Memoization
+///
+/// By default, a component is re-rendered whenever its parent is. With the `memo` argument, the
+/// component is only re-rendered when its properties differ from those of its previous render, or
+/// when one of its own hooks or one of its descendants signals a change, such as a `State` being
+/// set. Otherwise, it and its descendants are left as they are. This requires the properties type
+/// to implement [`Clone`] and [`PartialEq`], and to not borrow any data.
+///
+/// Because elements can be neither cloned nor compared, memoized components can't take children
+/// or other elements via their properties. Contexts aren't compared either, so a memoized
+/// component, or one of its descendants, which reads a context won't see changes to it until it
+/// re-renders for another reason, unless it reads the context via `use_context_selector`, or the
+/// theme via `use_theme`.
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[derive(Clone, Default, PartialEq, Props)]
+/// struct ChartProps {
+///     values: Vec<f64>,
+/// }
+///
+/// #[component(memo)]
+/// fn Chart(props: &ChartProps) -> impl Into<AnyElement<'static>> {
+///     // Only runs when the values change.
+///     let max = props.values.iter().cloned().fold(0.0, f64::max);
+///     element! {
+///         Text(content: format!("max: {}", max))
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn component(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let component = parse_macro_input!(item as ParsedComponent);
+pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr with Punctuated::<syn::Ident, Comma>::parse_terminated);
+    let mut component = parse_macro_input!(item as ParsedComponent);
+    if let Err(err) = component.parse_args(args) {
+        return err.into_compile_error().into();
+    }
     quote!(#component).into()
 }
 
 #[doc(hidden)]
 #[proc_macro_attribute]
-pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn with_layout_style_props(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // With the `builder` argument, builder methods are also generated for each field.
+    let builder = parse_macro_input!(attr with Punctuated::<syn::Ident, Comma>::parse_terminated)
+        .iter()
+        .any(|arg| arg == "builder");
+
     let layout_style_fields = [
         quote! {
             /// Sets the display mode for the element. Defaults to [`Display::Flex`].
             ///
+            /// With [`Display::None`], the element and its children take up no space and aren't
+            /// drawn, but they remain mounted, so their state is preserved until they're shown
+            /// again.
+            ///
             /// See [the MDN documentation for display](https://developer.mozilla.org/en-US/docs/Web/CSS/display).
-            pub display: ::iocraft::Display
+            pub display: Option<::iocraft::Display>
         },
         quote! {
             /// Sets the width of the element.
@@ -434,6 +1142,13 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
             /// Sets the maximum height of the element.
             pub max_height: ::iocraft::Size
         },
+        quote! {
+            /// Determines whether the width and height of the element include its padding and
+            /// border. Defaults to [`BoxSizing::BorderBox`].
+            ///
+            /// See [the MDN documentation for box-sizing](https://developer.mozilla.org/en-US/docs/Web/CSS/box-sizing).
+            pub box_sizing: Option<::iocraft::BoxSizing>
+        },
         quote! {
             /// Defines the area to reserve around the element's content, but inside the border.
             ///
@@ -498,13 +1213,13 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
             /// Defines how items are placed along the main axis of a flex container.
             ///
             /// See [the MDN documentation for flex-direction](https://developer.mozilla.org/en-US/docs/Web/CSS/flex-direction).
-            pub flex_direction: ::iocraft::FlexDirection
+            pub flex_direction: Option<::iocraft::FlexDirection>
         },
         quote! {
             /// Defines whether items are forced onto one line or can wrap into multiple lines.
             ///
             /// See [the MDN documentation for flex-wrap](https://developer.mozilla.org/en-US/docs/Web/CSS/flex-wrap).
-            pub flex_wrap: ::iocraft::FlexWrap
+            pub flex_wrap: Option<::iocraft::FlexWrap>
         },
         quote! {
             /// Sets the initial main size of a flex item.
@@ -517,7 +1232,7 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
             /// to the item's main size.
             ///
             /// See [the MDN documentation for flex-grow](https://developer.mozilla.org/en-US/docs/Web/CSS/flex-grow).
-            pub flex_grow: f32
+            pub flex_grow: Option<f32>
         },
         quote! {
             /// Sets the flex shrink factor, which specifies how the item should shrink when the
@@ -538,12 +1253,40 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
             /// See [the MDN documentation for align-content](https://developer.mozilla.org/en-US/docs/Web/CSS/align-content).
             pub align_content: Option<::iocraft::AlignContent>
         },
+        quote! {
+            /// Overrides the container's `align_items` value for this item, controlling its
+            /// alignment along the cross axis of its flex container.
+            ///
+            /// See [the MDN documentation for align-self](https://developer.mozilla.org/en-US/docs/Web/CSS/align-self).
+            pub align_self: Option<::iocraft::AlignSelf>
+        },
+        quote! {
+            /// Controls the alignment of items within their grid areas along the inline axis, which
+            /// for terminals is the horizontal one. Flexbox layouts ignore this property.
+            ///
+            /// See [the MDN documentation for justify-items](https://developer.mozilla.org/en-US/docs/Web/CSS/justify-items).
+            pub justify_items: Option<::iocraft::JustifyItems>
+        },
         quote! {
             /// Controls the distribution of space between and around items along a flex container's main axis.
             ///
             /// See [the MDN documentation for justify-content](https://developer.mozilla.org/en-US/docs/Web/CSS/justify-content).
             pub justify_content: Option<::iocraft::JustifyContent>
         },
+        quote! {
+            /// Places the item within the columns of its grid, when the parent's `display` is
+            /// [`Display::Grid`].
+            ///
+            /// See [the MDN documentation for grid-column](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-column).
+            pub grid_column: ::iocraft::GridPlacement
+        },
+        quote! {
+            /// Places the item within the rows of its grid, when the parent's `display` is
+            /// [`Display::Grid`].
+            ///
+            /// See [the MDN documentation for grid-row](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-row).
+            pub grid_row: ::iocraft::GridPlacement
+        },
     ]
     .map(|tokens| syn::Field::parse_named.parse2(tokens).unwrap());
 
@@ -554,11 +1297,57 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
                 fields.named.extend(layout_style_fields.iter().cloned());
             }
 
+            // Props also get a base style, which the individual fields are layered on top of.
+            if !builder {
+                if let syn::Fields::Named(fields) = &mut struct_data.fields {
+                    fields.named.push(
+                        syn::Field::parse_named
+                            .parse2(quote! {
+                                /// A base layout style, such as one built with the `style!` macro.
+                                /// This allows a style to be computed once and shared by multiple
+                                /// elements. Layout properties which are given individually take
+                                /// precedence over the base style, unless they're left at their
+                                /// default values.
+                                pub style: Option<::iocraft::LayoutStyle>
+                            })
+                            .unwrap(),
+                    );
+                }
+            }
+
             let struct_name = &ast.ident;
             let field_assignments = layout_style_fields.iter().map(|field| {
                 let field_name = &field.ident;
-                quote! { #field_name: self.#field_name }
+                if builder {
+                    quote! { #field_name: self.#field_name }
+                } else {
+                    quote! {
+                        #field_name: if self.#field_name != ::core::default::Default::default() {
+                            self.#field_name
+                        } else {
+                            base.#field_name
+                        }
+                    }
+                }
             });
+            let base = (!builder).then(|| quote!(let base = self.style.unwrap_or_default();));
+
+            let builder_methods =
+                builder.then(|| {
+                    layout_style_fields.iter().map(|field| {
+                    let field_name = &field.ident;
+                    let field_type = &field.ty;
+                    let docs = field.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+                    quote! {
+                        #(#docs)*
+                        pub fn #field_name(mut self, #field_name: impl Into<#field_type>) -> Self {
+                            self.#field_name = #field_name.into();
+                            self
+                        }
+                    }
+                })
+                });
+            let builder_methods = builder_methods.into_iter().flatten();
 
             let where_clause = &ast.generics.where_clause;
 
@@ -590,10 +1379,13 @@ pub fn with_layout_style_props(_attr: TokenStream, item: TokenStream) -> TokenSt
                 impl #generics #struct_name #bracketed_generic_names #where_clause {
                     /// Returns the layout style based on the layout-related fields of this struct.
                     pub fn layout_style(&self) -> ::iocraft::LayoutStyle {
+                        #base
                         ::iocraft::LayoutStyle{
                             #(#field_assignments,)*
                         }
                     }
+
+                    #(#builder_methods)*
                 }
             }
             .into()
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
-use iocraft::{components::Box, AnyElement, Hooks};
+use iocraft::{
+    components::{Box, ContextProvider, Text},
+    AnyElement, Context, ElementExt, Hooks,
+};
 use iocraft_macros::{component, element, Props};
 
 #[component]
@@ -18,6 +21,115 @@ fn MyComponentWithProps(_props: &mut MyProps) -> impl Into<AnyElement<'static>>
     element!(Box)
 }
 
+#[derive(Clone, Copy, Default, Props)]
+struct MyCopyProps {
+    value: i32,
+}
+
+#[component]
+fn MyComponentWithOwnedProps(mut props: MyCopyProps) -> impl Into<AnyElement<'static>> {
+    props.value *= 2;
+    element!(Text(content: props.value.to_string()))
+}
+
+#[test]
+fn owned_props() {
+    assert_eq!(
+        element!(MyComponentWithOwnedProps(value: 21)).to_string(),
+        "42\n"
+    );
+}
+
+#[derive(Default, Props)]
+struct MyMutProps<'a> {
+    buffer: Option<&'a mut String>,
+    local: i32,
+}
+
+#[component]
+fn MyComponentWithMutProps<'a>(props: &mut MyMutProps<'a>) -> impl Into<AnyElement<'static>> {
+    props.local += 1;
+    if let Some(buffer) = props.buffer.as_mut() {
+        buffer.push_str(&props.local.to_string());
+    }
+    element!(Text(content: props.local.to_string()))
+}
+
+#[test]
+fn mutable_props() {
+    let mut buffer = String::new();
+    // The mutation of `local` only lasts for the render, but the borrowed buffer is written back
+    // to.
+    assert_eq!(
+        element!(MyComponentWithMutProps(buffer: &mut buffer, local: 1)).to_string(),
+        "2\n"
+    );
+    assert_eq!(buffer, "2");
+}
+
+#[derive(Default, Props)]
+struct MyCounterProps<'a> {
+    count: Option<&'a mut i32>,
+}
+
+#[component]
+fn MyComponentWithoutOutput<'a>(props: &mut MyCounterProps<'a>) {
+    if let Some(count) = props.count.as_mut() {
+        **count += 1;
+    }
+}
+
+#[component]
+fn MyComponentWithUnitOutput() -> () {}
+
+#[derive(Clone, Default, PartialEq, Props)]
+struct MyOptionalProps {
+    content: Option<String>,
+}
+
+#[component]
+fn MyComponentWithOptionalOutput(
+    props: &MyOptionalProps,
+) -> Option<impl Into<AnyElement<'static>>> {
+    let content = props.content.clone()?;
+    Some(element!(Text(content)))
+}
+
+#[component(memo)]
+fn MyMemoComponentWithOptionalOutput(
+    props: &MyOptionalProps,
+) -> Option<impl Into<AnyElement<'static>>> {
+    let content = props.content.clone()?;
+    Some(element!(Text(content)))
+}
+
+#[test]
+fn empty_output() {
+    let mut count = 0;
+    assert_eq!(
+        element! {
+            Box {
+                MyComponentWithoutOutput(count: &mut count)
+                MyComponentWithUnitOutput
+            }
+        }
+        .to_string(),
+        ""
+    );
+    assert_eq!(count, 1);
+
+    assert_eq!(element!(MyComponentWithOptionalOutput).to_string(), "");
+    assert_eq!(
+        element!(MyComponentWithOptionalOutput(content: "x".to_string())).to_string(),
+        "x\n"
+    );
+    assert_eq!(element!(MyMemoComponentWithOptionalOutput).to_string(), "");
+    assert_eq!(
+        element!(MyMemoComponentWithOptionalOutput(content: "x".to_string())).to_string(),
+        "x\n"
+    );
+}
+
 #[component]
 fn MyComponentWithHooks(_hooks: Hooks) -> impl Into<AnyElement<'static>> {
     element!(Box)
@@ -27,3 +139,244 @@ fn MyComponentWithHooks(_hooks: Hooks) -> impl Into<AnyElement<'static>> {
 fn MyComponentWithHooksRef(_hooks: &mut Hooks) -> impl Into<AnyElement<'static>> {
     element!(Box)
 }
+
+struct MyContext {
+    value: i32,
+}
+
+#[component]
+fn MyComponentWithContext(context: &MyContext) -> impl Into<AnyElement<'static>> {
+    element!(Text(content: context.value.to_string()))
+}
+
+#[component]
+fn MyComponentWithMutContext(
+    _hooks: Hooks,
+    _props: &MyProps,
+    context: &mut MyContext,
+) -> impl Into<AnyElement<'static>> {
+    context.value += 1;
+    element!(Text(content: context.value.to_string()))
+}
+
+#[test]
+fn context_arg() {
+    let mut context = MyContext { value: 1 };
+    assert_eq!(
+        element! {
+            ContextProvider(value: Context::from_mut(&mut context)) {
+                MyComponentWithContext
+                MyComponentWithMutContext
+            }
+        }
+        .to_string(),
+        "12\n"
+    );
+    assert_eq!(context.value, 2);
+}
+
+#[test]
+#[should_panic(expected = "component `MyComponentWithContext` requires a `MyContext` context")]
+fn missing_context_arg() {
+    element!(MyComponentWithContext).to_string();
+}
+
+mod memo {
+    use iocraft::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static LABEL_RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Default, PartialEq, Props)]
+    struct LabelProps {
+        label: String,
+    }
+
+    #[component(memo)]
+    fn Label(mut hooks: Hooks, props: &LabelProps) -> impl Into<AnyElement<'static>> {
+        LABEL_RENDERS.fetch_add(1, Ordering::SeqCst);
+        let exclaim = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('x'),
+                ..
+            }) = event
+            {
+                exclaim.set(true);
+            }
+        });
+        element! {
+            Text(content: format!("{}{}", props.label, if exclaim.get() { "!" } else { "" }))
+        }
+    }
+
+    #[component]
+    fn Parent(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let presses = hooks.use_state(|| 0);
+        let label = hooks.use_state(|| "a");
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent { code, .. }) = event {
+                presses.set(presses + 1);
+                if code == KeyCode::Char('l') {
+                    label.set("b");
+                }
+            }
+        });
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: presses.to_string())
+                Label(label: label.get())
+            }
+        }
+    }
+
+    #[test]
+    fn memo() {
+        let mut e = element!(Parent);
+        let mut renderer = TestRenderer::new(&mut e);
+        assert_eq!(renderer.frame(), "0\na\n");
+        assert_eq!(LABEL_RENDERS.load(Ordering::SeqCst), 1);
+
+        // The parent re-renders, but the label's props are unchanged.
+        renderer.send_key(KeyCode::Char('k'));
+        assert_eq!(renderer.frame(), "1\na\n");
+        assert_eq!(LABEL_RENDERS.load(Ordering::SeqCst), 1);
+
+        // The label's props change.
+        renderer.send_key(KeyCode::Char('l'));
+        assert_eq!(renderer.frame(), "2\nb\n");
+        assert_eq!(LABEL_RENDERS.load(Ordering::SeqCst), 2);
+
+        // The label's own state changes.
+        renderer.send_key(KeyCode::Char('x'));
+        assert_eq!(renderer.frame(), "3\nb!\n");
+        assert_eq!(LABEL_RENDERS.load(Ordering::SeqCst), 3);
+    }
+
+    #[derive(Clone, Default, PartialEq, Props)]
+    struct CounterButtonProps {
+        label: String,
+    }
+
+    #[component(memo)]
+    fn CounterButton(
+        mut hooks: Hooks,
+        props: &CounterButtonProps,
+    ) -> impl Into<AnyElement<'static>> {
+        let presses = hooks.use_state(|| 0);
+        element! {
+            Button(
+                label: format!("{}: {}", props.label, presses),
+                is_focused: true,
+                on_press: move |_| presses.set(presses + 1),
+            )
+        }
+    }
+
+    #[component]
+    fn ButtonParent(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let keys = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |_| keys.set(keys + 1));
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: keys.to_string())
+                CounterButton(label: "presses")
+            }
+        }
+    }
+
+    #[test]
+    fn memo_with_handlers() {
+        let mut e = element!(ButtonParent);
+        let mut renderer = TestRenderer::new(&mut e);
+        assert_eq!(renderer.frame(), "0\n presses: 0\n");
+
+        // The parent re-renders while the button's render is skipped, which must leave the
+        // button's handler in place.
+        renderer.send_key(KeyCode::Char('k'));
+        assert_eq!(renderer.frame(), "1\n presses: 0\n");
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "2\n presses: 1\n");
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "3\n presses: 2\n");
+    }
+}
+
+#[derive(Default, Props)]
+struct MyPropsWithAttributes {
+    label: String,
+    #[cfg(any())]
+    disabled: DoesNotExist,
+}
+
+/// A component with attributes on itself, its arguments, its props, and its body.
+#[component]
+#[allow(unused_variables, clippy::needless_pass_by_ref_mut)]
+fn MyComponentWithAttributes(
+    #[allow(unused_mut)] mut hooks: Hooks,
+    /// The component's properties.
+    props: &mut MyPropsWithAttributes,
+) -> impl Into<AnyElement<'static>> {
+    let unused = 1;
+    #[cfg(any())]
+    let label: DoesNotExist = props.disabled;
+    element!(Text(content: props.label.clone()))
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(
+        element!(MyComponentWithAttributes(label: "attributes")).to_string(),
+        "attributes\n"
+    );
+}
+
+mod generic {
+    use iocraft::prelude::*;
+    use std::fmt::Display;
+
+    #[derive(Default, Props)]
+    struct ListProps<T> {
+        items: Vec<T>,
+    }
+
+    /// Lists the given items.
+    #[component]
+    fn List<T: Display + Send + Sync>(props: &ListProps<T>) -> impl Into<AnyElement<'static>> {
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                #(props.items.iter().map(|item| element!(Text(content: item.to_string()))))
+            }
+        }
+    }
+
+    #[derive(Clone, Default, PartialEq, Props)]
+    struct SumProps<T> {
+        values: Vec<T>,
+    }
+
+    #[component(memo)]
+    fn Sum<T>(props: &SumProps<T>) -> impl Into<AnyElement<'static>>
+    where
+        T: Copy + Default + Display + PartialEq + Send + Sync + std::iter::Sum<T>,
+    {
+        let sum: T = props.values.iter().copied().sum();
+        element!(Text(content: sum.to_string()))
+    }
+
+    #[test]
+    fn generic() {
+        assert_eq!(
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    List<i32>(items: vec![1, 2])
+                    List<&'static str>(items: vec!["a", "b"])
+                    Sum<i32>(values: vec![1, 2, 3])
+                    Sum<f64>(values: vec![0.5, 0.25])
+                }
+            }
+            .to_string(),
+            "1\n2\na\nb\n6\n0.75\n"
+        );
+    }
+}
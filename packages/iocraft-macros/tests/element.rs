@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
-use iocraft::{element, AnyElement, Component, Element, Percent, Props};
+use iocraft::{
+    element, AnyElement, Auto, Component, Element, Handler, Margin, Percent, Props, RefHandler,
+};
 
 #[derive(Default)]
 struct MyComponent;
@@ -9,6 +11,8 @@ struct MyComponent;
 struct MyComponentProps {
     foo: String,
     percent: Percent,
+    margin: Margin,
+    count: u64,
     children: Vec<Element<'static, MyComponent>>,
 }
 
@@ -114,6 +118,153 @@ fn code_interpolation_single_child() {
     assert_eq!(e.props.children.len(), 2);
 }
 
+#[test]
+fn code_interpolation_iterator() {
+    let e = element! {
+        MyContainer {
+            #((0..3).map(|_| element!(MyComponent)))
+            #(Some(element!(MyContainer)))
+            #(vec![element!(MyComponent), element!(MyComponent)])
+        }
+    };
+    assert_eq!(e.props.children.len(), 6);
+}
+
+#[test]
+fn code_interpolation_mixed_children() {
+    let children: Vec<AnyElement<'static>> = vec![
+        element!(MyContainer).into_any(),
+        element!(MyComponent(foo: "bar")).into(),
+    ];
+    let e = element! {
+        MyContainer {
+            MyContainer
+            #(children)
+        }
+    };
+    assert_eq!(e.props.children.len(), 3);
+}
+
+struct MyHandlerComponent;
+
+#[derive(Clone, Default, PartialEq, Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Default, Props)]
+struct MyHandlerComponentProps {
+    on_pair: Handler<'static, (i32, i32)>,
+    on_label: RefHandler<'static, str>,
+    label: String,
+    point: Point,
+    count: i32,
+}
+
+impl Component for MyHandlerComponent {
+    type Props<'a> = MyHandlerComponentProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+}
+
+#[test]
+fn complex_prop_values() {
+    let sum = std::sync::Arc::new(std::sync::Mutex::new(0));
+    let n = 2;
+    let mut e = element! {
+        MyHandlerComponent(
+            on_pair: {
+                let sum = sum.clone();
+                move |(a, b): (i32, i32)| {
+                    let product = a * b;
+                    *sum.lock().unwrap() += product;
+                }
+            },
+            label: match n {
+                1 => "one",
+                2 => "two",
+                _ => "many",
+            },
+            point: Point { x: 1, y: n },
+            count: [(n, 3)].iter().map(|(a, b)| a + b).sum::<i32>(),
+        )
+    };
+    assert_eq!(e.props.label, "two");
+    assert_eq!(e.props.point, Point { x: 1, y: 2 });
+    assert_eq!(e.props.count, 5);
+    e.props.on_pair.invoke((3, 4));
+    assert_eq!(*sum.lock().unwrap(), 12);
+
+    // The handler's argument types are inferred from the prop.
+    let pairs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut e = element! {
+        MyHandlerComponent(
+            on_pair: {
+                let pairs = pairs.clone();
+                move |(a, b)| pairs.lock().unwrap().push((a, b))
+            },
+            count: 1,
+        )
+    };
+    assert_eq!(e.props.count, 1);
+    e.props.on_pair.invoke((5, 6));
+    assert_eq!(*pairs.lock().unwrap(), vec![(5, 6)]);
+}
+
+#[test]
+fn ref_handler_props() {
+    let labels = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut e = element! {
+        MyHandlerComponent(
+            label: "foo",
+            on_label: {
+                let labels = labels.clone();
+                move |label: &str| labels.lock().unwrap().push(label.to_string())
+            },
+        )
+    };
+    let label = e.props.label.clone();
+    e.props.on_label.invoke(&label);
+    e.props.on_label.invoke("bar");
+    assert_eq!(*labels.lock().unwrap(), vec!["foo", "bar"]);
+}
+
+struct MySlotComponent;
+
+#[derive(Default, Props)]
+struct MySlotComponentProps {
+    title: Option<AnyElement<'static>>,
+    header: Option<AnyElement<'static>>,
+    footer: Option<Element<'static, MyComponent>>,
+}
+
+impl Component for MySlotComponent {
+    type Props<'a> = MySlotComponentProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+}
+
+#[test]
+fn element_props() {
+    let e = element! {
+        MySlotComponent(
+            title: element!(MyComponent(foo: "title")),
+            header: element!(MyContainer).into_any(),
+            footer: element! {
+                MyComponent(foo: "footer")
+            },
+        )
+    };
+    assert!(e.props.title.is_some());
+    assert!(e.props.header.is_some());
+    assert_eq!(e.props.footer.unwrap().props.foo, "footer");
+}
+
 #[test]
 fn percent() {
     let e = element! {
@@ -125,6 +276,59 @@ fn percent() {
     assert_eq!(e.props.children.len(), 2);
 }
 
+#[test]
+fn auto() {
+    let e = element! {
+        MyComponent(margin: Auto)
+    };
+    assert_eq!(e.props.margin, Margin::Auto);
+
+    // A local named `auto` is used like any other value.
+    let auto = Margin::Length(2);
+    let e = element! {
+        MyComponent(margin: auto)
+    };
+    assert_eq!(e.props.margin, Margin::Length(2));
+}
+
+#[test]
+fn raw() {
+    let e = element! {
+        MyComponent(#[raw] count: 42, #[raw] foo: String::from("bar"))
+    };
+    assert_eq!(e.props.count, 42);
+    assert_eq!(e.props.foo, "bar");
+
+    let count = 7u64;
+    let e = element! {
+        MyComponent(#[raw] count: count * 2)
+    };
+    assert_eq!(e.props.count, 14);
+
+    // A function named `raw` is called like any other.
+    fn raw(count: u64) -> u64 {
+        count + 1
+    }
+    let e = element! {
+        MyComponent(count: raw(count))
+    };
+    assert_eq!(e.props.count, 8);
+}
+
+#[test]
+fn negative() {
+    let e = element! {
+        MyComponent(margin: -1)
+    };
+    assert_eq!(e.props.margin, Margin::Length(-1));
+
+    let e = element! {
+        MyComponent(margin: -12.5pct, percent: -50pct)
+    };
+    assert_eq!(e.props.margin, Margin::Percent(-12.5));
+    assert_eq!(e.props.percent, Percent(-50.0));
+}
+
 #[test]
 fn comment() {
     let e = element! {
@@ -136,6 +340,33 @@ fn comment() {
     assert_eq!(e.props.children.len(), 1);
 }
 
+#[test]
+fn cfg_children() {
+    let e = element! {
+        MyContainer {
+            #[cfg(all())]
+            MyContainer
+            #[cfg(any())]
+            MyComponent(foo: "excluded")
+            #[cfg(any())]
+            #(Some(element!(MyContainer)))
+            #[cfg(any())]
+            #for _ in 0..3 {
+                MyContainer
+            }
+            // Gated out children aren't compiled at all.
+            #[cfg(any())]
+            ComponentWhichDoesNotExist
+            #[cfg(all())]
+            #[cfg(all())]
+            #for _ in 0..2 {
+                MyContainer
+            }
+        }
+    };
+    assert_eq!(e.props.children.len(), 3);
+}
+
 #[test]
 fn key() {
     let e = element! {
@@ -145,3 +376,119 @@ fn key() {
     };
     assert_eq!(e.props.children.len(), 1);
 }
+
+#[test]
+fn for_loop() {
+    let e = element! {
+        MyComponent {
+            #for i in 0..3 {
+                MyComponent(foo: i.to_string())
+            }
+            MyComponent(foo: "last")
+        }
+    };
+    let foos = e
+        .props
+        .children
+        .iter()
+        .map(|c| c.props.foo.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(foos, vec!["0", "1", "2", "last"]);
+    assert_ne!(e.props.children[0].key, e.props.children[1].key);
+}
+
+#[test]
+fn nested_for_loop() {
+    let names = ["a".to_string(), "b".to_string()];
+    let e = element! {
+        MyComponent {
+            #for (i, name) in names.iter().enumerate() keyed by name.clone() {
+                #for j in 0..2 {
+                    MyComponent(foo: format!("{}{}{}", name, i, j))
+                }
+            }
+        }
+    };
+    let foos = e
+        .props
+        .children
+        .iter()
+        .map(|c| c.props.foo.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(foos, vec!["a00", "a01", "b10", "b11"]);
+    for (i, a) in e.props.children.iter().enumerate() {
+        for b in &e.props.children[i + 1..] {
+            assert_ne!(a.key, b.key);
+        }
+    }
+}
+
+mod for_loop_keys {
+    use iocraft::prelude::*;
+
+    #[derive(Default, Props)]
+    struct ItemProps {
+        label: String,
+    }
+
+    #[component]
+    fn Item(mut hooks: Hooks, props: &ItemProps) -> impl Into<AnyElement<'static>> {
+        let label = props.label.clone();
+        let initial_label = hooks.use_state(move || label);
+        element!(Text(content: format!("{}:{}", initial_label, props.label)))
+    }
+
+    #[derive(Default, Props)]
+    struct ListProps {
+        keyed: bool,
+    }
+
+    #[component]
+    fn List(mut hooks: Hooks, props: &ListProps) -> impl Into<AnyElement<'static>> {
+        let reversed = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(_) = event {
+                reversed.set(true);
+            }
+        });
+        let mut items = vec!["a", "b"];
+        if reversed.get() {
+            items.reverse();
+        }
+        if props.keyed {
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    #for item in items keyed by item {
+                        Item(label: item)
+                    }
+                }
+            }
+        } else {
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    #for item in items {
+                        Item(label: item)
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keyed_by() {
+        let mut e = element!(List(keyed: true));
+        let mut renderer = TestRenderer::new(&mut e);
+        assert_eq!(renderer.frame(), "a:a\nb:b\n");
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "b:b\na:a\n");
+    }
+
+    #[test]
+    fn positional() {
+        let mut e = element!(List);
+        let mut renderer = TestRenderer::new(&mut e);
+        assert_eq!(renderer.frame(), "a:a\nb:b\n");
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "a:b\nb:a\n");
+    }
+}
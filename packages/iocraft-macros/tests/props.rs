@@ -33,3 +33,22 @@ struct StructWithTypeGeneric<T> {
 struct StructWithLifetimeAndTypeGeneric<'lt, T> {
     foo: &'lt T,
 }
+
+struct NotDefault(i32);
+
+#[derive(Props)]
+struct StructWithCustomDefaults<'lt> {
+    #[props(default = NotDefault(42))]
+    foo: NotDefault,
+    #[props(default = "bar".to_string())]
+    bar: String,
+    baz: Option<&'lt str>,
+}
+
+#[test]
+fn custom_defaults() {
+    let props = StructWithCustomDefaults::default();
+    assert_eq!(props.foo.0, 42);
+    assert_eq!(props.bar, "bar");
+    assert_eq!(props.baz, None);
+}
@@ -0,0 +1,74 @@
+use iocraft::{
+    components::{Box, Text},
+    AlignItems, Auto, CanvasTextStyle, Color, ElementExt, FitContent, FlexBasis, FlexDirection,
+    LayoutStyle, MaxContent, MinContent, Padding, Percent, Size,
+};
+use iocraft_macros::{element, style};
+
+#[test]
+fn layout_style() {
+    assert_eq!(style!(), LayoutStyle::default());
+
+    let compact = true;
+    let style = style!(
+        width: 50pct,
+        height: Auto,
+        padding: if compact { 0 } else { 1 },
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+    );
+    assert_eq!(style.width, Size::Percent(50.0));
+    assert_eq!(style.height, Size::Auto);
+    assert_eq!(style.padding, Padding::Length(0));
+    assert_eq!(style.flex_direction, Some(FlexDirection::Column));
+    assert_eq!(style.align_items, Some(AlignItems::Center));
+    assert_eq!(style!(min_width: 12.5pct).min_width, Size::Percent(12.5));
+    assert_eq!(
+        style!(max_width: Percent(10.0)).max_width,
+        Size::Percent(10.0)
+    );
+
+    let style = style!(width: MaxContent, min_height: MinContent, flex_basis: FitContent);
+    assert_eq!(style.width, Size::MaxContent);
+    assert_eq!(style.min_height, Size::MinContent);
+    assert_eq!(style.flex_basis, FlexBasis::FitContent);
+
+    // Locals are never mistaken for keywords.
+    let max_content = 7;
+    assert_eq!(style!(width: max_content).width, Size::Length(7));
+}
+
+#[test]
+fn typed_style() {
+    let style = style!(CanvasTextStyle {
+        color: Color::Red,
+        underline: true,
+    });
+    assert_eq!(
+        style,
+        CanvasTextStyle {
+            color: Some(Color::Red),
+            underline: true,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn shared_style() {
+    let bordered = style!(width: 6, padding_left: 2);
+    assert_eq!(
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Box(style: bordered) {
+                    Text(content: "a")
+                }
+                Box(style: bordered, padding_left: 4) {
+                    Text(content: "b")
+                }
+            }
+        }
+        .to_string(),
+        "  a\n    b\n"
+    );
+}
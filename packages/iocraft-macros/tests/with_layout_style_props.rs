@@ -1,4 +1,4 @@
-use iocraft::Display;
+use iocraft::{AlignItems, FlexBasis, FlexDirection, Margin, Padding, Percent, Size};
 use iocraft_macros::{with_layout_style_props, Props};
 
 #[with_layout_style_props]
@@ -25,21 +25,83 @@ struct MyPropsWithConstParam<const N: usize> {
     foo: Option<[u8; N]>,
 }
 
+#[with_layout_style_props(builder)]
+#[derive(Default)]
+struct MyStyle {
+    foo: String,
+}
+
 #[test]
 fn layout_style_props() {
     let props: MyProps = Default::default();
     assert_eq!(props.foo, "");
-    assert_eq!(props.display, Display::DEFAULT);
+    assert_eq!(props.display, None);
 
     let props: MyPropsWithLifetime<'static> = Default::default();
     assert_eq!(props.foo, None);
-    assert_eq!(props.display, Display::DEFAULT);
+    assert_eq!(props.display, None);
 
     let props: MyPropsWithTypeGeneric<String> = Default::default();
     assert_eq!(props.foo, None);
-    assert_eq!(props.display, Display::DEFAULT);
+    assert_eq!(props.display, None);
 
     let props: MyPropsWithConstParam<1> = Default::default();
     assert_eq!(props.foo, None);
-    assert_eq!(props.display, Display::DEFAULT);
+    assert_eq!(props.display, None);
+}
+
+#[test]
+fn layout_style_builder() {
+    let style = MyStyle::default()
+        .width(10)
+        .height(Percent(50.0))
+        .padding_left(1)
+        .margin(Margin::Auto)
+        .flex_direction(FlexDirection::Column)
+        .flex_grow(1.0)
+        .flex_shrink(0.0)
+        .align_items(AlignItems::Center);
+    assert_eq!(style.foo, "");
+    assert_eq!(style.width, Size::Length(10));
+    assert_eq!(style.height, Size::Percent(50.0));
+    assert_eq!(style.padding_left, Padding::Length(1));
+    assert_eq!(style.margin, Margin::Auto);
+    assert_eq!(style.flex_direction, Some(FlexDirection::Column));
+    assert_eq!(style.flex_grow, Some(1.0));
+    assert_eq!(style.flex_shrink, Some(0.0));
+    assert_eq!(style.align_items, Some(AlignItems::Center));
+    assert_eq!(style.layout_style().width, Size::Length(10));
+}
+
+#[test]
+fn layout_style_base() {
+    let props = MyProps {
+        style: Some(MyStyle::default().width(10).padding(2).layout_style()),
+        padding: Padding::Length(1),
+        flex_direction: Some(FlexDirection::Column),
+        ..Default::default()
+    };
+    let style = props.layout_style();
+    assert_eq!(style.width, Size::Length(10));
+    assert_eq!(style.padding, Padding::Length(1));
+    assert_eq!(style.flex_direction, Some(FlexDirection::Column));
+
+    // Fields can be reset to their default values over a base style which sets them otherwise.
+    let props = MyProps {
+        style: Some(
+            MyStyle::default()
+                .flex_direction(FlexDirection::Column)
+                .flex_grow(1.0)
+                .flex_basis(FlexBasis::Length(10))
+                .layout_style(),
+        ),
+        flex_direction: Some(FlexDirection::Row),
+        flex_grow: Some(0.0),
+        flex_basis: FlexBasis::Auto,
+        ..Default::default()
+    };
+    let style = props.layout_style();
+    assert_eq!(style.flex_direction, Some(FlexDirection::Row));
+    assert_eq!(style.flex_grow, Some(0.0));
+    assert_eq!(style.flex_basis, FlexBasis::Auto);
 }
@@ -0,0 +1,200 @@
+//! This module contains easing and interpolation helpers for animations.
+//!
+//! The helpers are pure functions, so they can be called from component bodies with the elapsed
+//! time from [`use_animation_frame`](crate::hooks::UseAnimationFrame::use_animation_frame) to
+//! animate any property over a duration:
+//!
+//! ```
+//! # use iocraft::prelude::*;
+//! # use std::time::Duration;
+//! use iocraft::animation;
+//!
+//! #[component]
+//! fn Reveal(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+//!     let elapsed = hooks.use_animation_frame();
+//!     let t = animation::ease_out(animation::progress(elapsed, Duration::from_secs(1)));
+//!     element! {
+//!         Box(
+//!             width: animation::lerp(0.0, 40.0, t).round() as u16,
+//!             height: 1,
+//!             background_color: animation::lerp_color(Color::DarkBlue, Color::Cyan, t),
+//!         )
+//!     }
+//! }
+//! ```
+//!
+//! The easing functions take a position `t` from 0.0 to 1.0, clamping values outside of that
+//! range, and return the eased position, which is 0.0 at the start and 1.0 at the end.
+
+use crate::{color, Color};
+use std::time::Duration;
+
+/// Returns how far along an animation of the given duration is after `elapsed` time, from 0.0 to
+/// 1.0. If the duration is zero, the animation is complete immediately.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// use iocraft::animation;
+///
+/// let duration = Duration::from_secs(2);
+/// assert_eq!(animation::progress(Duration::from_millis(500), duration), 0.25);
+/// assert_eq!(animation::progress(Duration::from_secs(3), duration), 1.0);
+/// ```
+pub fn progress(elapsed: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        1.0
+    } else {
+        (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+}
+
+fn clamp(t: f32) -> f32 {
+    if t.is_nan() {
+        0.0
+    } else {
+        t.clamp(0.0, 1.0)
+    }
+}
+
+/// Moves at a constant speed.
+pub fn linear(t: f32) -> f32 {
+    clamp(t)
+}
+
+/// Starts slowly and speeds up, following a quadratic curve.
+pub fn ease_in(t: f32) -> f32 {
+    let t = clamp(t);
+    t * t
+}
+
+/// Starts quickly and slows down, following a quadratic curve.
+pub fn ease_out(t: f32) -> f32 {
+    let t = clamp(t);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Starts slowly, speeds up, and slows down again, following a quadratic curve.
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = clamp(t);
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+    }
+}
+
+/// Like [`ease_in`], but follows a cubic curve, so the change in speed is more pronounced.
+pub fn ease_in_cubic(t: f32) -> f32 {
+    let t = clamp(t);
+    t * t * t
+}
+
+/// Like [`ease_out`], but follows a cubic curve, so the change in speed is more pronounced.
+pub fn ease_out_cubic(t: f32) -> f32 {
+    let t = clamp(t);
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Like [`ease_in_out`], but follows a cubic curve, so the change in speed is more pronounced.
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    let t = clamp(t);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - 4.0 * (1.0 - t).powi(3)
+    }
+}
+
+/// Returns the value at position `t` between `from` and `to`, where 0.0 is `from` and 1.0 is
+/// `to`. Values of `t` outside of that range extrapolate beyond the endpoints.
+pub fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Returns the color at position `t` between `from` and `to`, interpolating in RGB space.
+///
+/// Colors from the terminal's palette are converted to RGB, so the result is a 24-bit color, with
+/// the exception that if either color is [`Color::Reset`], the nearer of the two is returned
+/// as-is. `t` is clamped to the range from 0.0 to 1.0. This is equivalent to a
+/// [`gradient`](crate::color::gradient) with two stops.
+///
+/// # Example
+///
+/// ```
+/// use iocraft::{animation, color};
+///
+/// let color = animation::lerp_color(color::rgb(0, 0, 0), color::rgb(255, 100, 0), 0.5);
+/// assert_eq!(color, color::rgb(128, 50, 0));
+/// ```
+pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    color::gradient(&[from, to], t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let duration = Duration::from_millis(400);
+        assert_eq!(progress(Duration::ZERO, duration), 0.0);
+        assert_eq!(progress(Duration::from_millis(100), duration), 0.25);
+        assert_eq!(progress(Duration::from_secs(1), duration), 1.0);
+        assert_eq!(progress(Duration::ZERO, Duration::ZERO), 1.0);
+    }
+
+    #[test]
+    fn test_easing() {
+        let curves: [fn(f32) -> f32; 7] = [
+            linear,
+            ease_in,
+            ease_out,
+            ease_in_out,
+            ease_in_cubic,
+            ease_out_cubic,
+            ease_in_out_cubic,
+        ];
+        for curve in curves {
+            assert_eq!(curve(0.0), 0.0);
+            assert_eq!(curve(1.0), 1.0);
+            assert_eq!(curve(-1.0), 0.0);
+            assert_eq!(curve(2.0), 1.0);
+            assert_eq!(curve(f32::NAN), 0.0);
+
+            // Every curve is monotonic.
+            let samples = (0..=100)
+                .map(|i| curve(i as f32 / 100.0))
+                .collect::<Vec<_>>();
+            assert!(samples.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        assert_eq!(linear(0.25), 0.25);
+        assert_eq!(ease_in(0.5), 0.25);
+        assert_eq!(ease_out(0.5), 0.75);
+        assert_eq!(ease_in_out(0.25), 0.125);
+        assert_eq!(ease_in_out(0.5), 0.5);
+        assert_eq!(ease_in_cubic(0.5), 0.125);
+        assert_eq!(ease_out_cubic(0.5), 0.875);
+        assert_eq!(ease_in_out_cubic(0.25), 0.0625);
+    }
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(10.0, 20.0, 0.0), 10.0);
+        assert_eq!(lerp(10.0, 20.0, 0.25), 12.5);
+        assert_eq!(lerp(10.0, 20.0, 1.0), 20.0);
+        assert_eq!(lerp(10.0, 20.0, 2.0), 30.0);
+
+        assert_eq!(
+            lerp_color(Color::Black, Color::White, 0.5),
+            color::rgb(128, 128, 128)
+        );
+        assert_eq!(
+            lerp_color(color::rgb(0, 0, 0), color::rgb(200, 100, 0), 2.0),
+            color::rgb(200, 100, 0)
+        );
+        assert_eq!(lerp_color(Color::Reset, Color::Red, 0.25), Color::Reset);
+    }
+}
@@ -1,20 +1,79 @@
 use crate::style::{Color, Weight};
 use crossterm::{
-    csi,
-    style::{Attribute, Colored},
+    csi, cursor, queue,
+    style::{Attribute, Colored, ContentStyle},
+    terminal,
 };
 use std::{
     fmt::{self, Display},
     io::{self, Write},
 };
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The zero width joiner, which combines the emoji on either side of it into a single glyph.
+const ZWJ: char = '\u{200d}';
+
+/// Determines how the width of emoji sequences is measured.
+///
+/// Terminals disagree on how to draw emoji which are joined into a single glyph by zero width
+/// joiners, such as 👨‍👩‍👧. Some draw the glyph, while others draw each of its emoji side by
+/// side. If content may contain such sequences, for example because it comes from users, this
+/// should match the terminal so that the text fits where it's laid out.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EmojiWidth {
+    /// A joined sequence is as wide as its first emoji, which is how most modern terminals draw
+    /// it. This is the default.
+    #[default]
+    Joined,
+    /// Each emoji in a joined sequence takes up its own width, which is how terminals without
+    /// support for the sequences draw them.
+    Separate,
+}
+
+impl EmojiWidth {
+    /// Returns the number of columns the grapheme cluster occupies.
+    pub(crate) fn grapheme_width(self, grapheme: &str) -> usize {
+        match self {
+            Self::Joined => grapheme.width(),
+            Self::Separate => grapheme.split(ZWJ).map(|part| part.width()).sum(),
+        }
+    }
+
+    /// Returns the number of columns the text occupies.
+    pub(crate) fn str_width(self, text: &str) -> usize {
+        graphemes(text).map(|g| self.grapheme_width(g)).sum()
+    }
+}
+
+/// Splits the string into extended grapheme clusters: the sequences of characters which are drawn
+/// as a single unit, such as a letter followed by combining accents, or a sequence of emoji joined
+/// by zero width joiners.
+pub(crate) fn graphemes(s: &str) -> unicode_segmentation::Graphemes<'_> {
+    s.graphemes(true)
+}
 
 #[derive(Clone, Debug, PartialEq)]
 struct Character {
     value: char,
+    // Any characters following the first in its grapheme cluster.
+    rest: Option<Box<str>>,
+    // The number of columns the character occupies. This is never zero so that writers always
+    // make progress.
+    width: usize,
     style: CanvasTextStyle,
 }
 
+impl Character {
+    fn write_value<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "{}", self.value)?;
+        if let Some(rest) = &self.rest {
+            w.write_all(rest.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
 /// Describes the style of text to be rendered via a [`Canvas`].
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct CanvasTextStyle {
@@ -24,8 +83,79 @@ pub struct CanvasTextStyle {
     /// The weight of the text.
     pub weight: Weight,
 
+    /// Whether the text is dimmed, also known as faint. Unlike [`Weight::Light`], this is
+    /// independent of the weight, so text can be both bold and dimmed.
+    pub dim: bool,
+
+    /// Whether the text is italic.
+    pub italic: bool,
+
     /// Whether the text is underlined.
     pub underline: bool,
+
+    /// Whether the text is struck through.
+    pub strikethrough: bool,
+
+    /// Whether the foreground and background colors of the text are swapped.
+    pub reverse: bool,
+
+    /// Whether the text blinks.
+    pub blink: bool,
+
+    /// The background color of the cells which the text is written to. If `None`, the cells keep
+    /// whatever background they already have.
+    pub background_color: Option<Color>,
+}
+
+impl From<ContentStyle> for CanvasTextStyle {
+    /// Converts a crossterm style. Its foreground and background colors and the attributes which
+    /// have an equivalent here are kept. Its underline color, and attributes such as hidden or
+    /// framed, are dropped.
+    fn from(style: ContentStyle) -> Self {
+        let attributes = style.attributes;
+        Self {
+            color: style.foreground_color,
+            weight: if attributes.has(Attribute::Bold) {
+                Weight::Bold
+            } else {
+                Weight::Normal
+            },
+            dim: attributes.has(Attribute::Dim),
+            italic: attributes.has(Attribute::Italic),
+            underline: [
+                Attribute::Underlined,
+                Attribute::DoubleUnderlined,
+                Attribute::Undercurled,
+                Attribute::Underdotted,
+                Attribute::Underdashed,
+            ]
+            .into_iter()
+            .any(|attribute| attributes.has(attribute)),
+            strikethrough: attributes.has(Attribute::CrossedOut),
+            reverse: attributes.has(Attribute::Reverse),
+            blink: attributes.has(Attribute::SlowBlink) || attributes.has(Attribute::RapidBlink),
+            background_color: style.background_color,
+        }
+    }
+}
+
+impl CanvasTextStyle {
+    /// Returns `true` if the text is dimmed, either explicitly or via its weight.
+    fn is_dim(&self) -> bool {
+        self.dim || self.weight == Weight::Light
+    }
+
+    /// Returns `true` if any of the attributes which are set in `self` are unset in `other`. Such
+    /// attributes can only be turned off by resetting all attributes.
+    fn has_attributes_missing_from(&self, other: &Self) -> bool {
+        (self.weight == Weight::Bold && other.weight != Weight::Bold)
+            || (self.is_dim() && !other.is_dim())
+            || (self.italic && !other.italic)
+            || (self.underline && !other.underline)
+            || (self.strikethrough && !other.strikethrough)
+            || (self.reverse && !other.reverse)
+            || (self.blink && !other.blink)
+    }
 }
 
 #[derive(Clone, Default, PartialEq)]
@@ -35,11 +165,143 @@ struct Cell {
 }
 
 impl Cell {
+    const EMPTY: Cell = Cell {
+        background_color: None,
+        character: None,
+    };
+
     fn is_empty(&self) -> bool {
         self.background_color.is_none() && self.character.is_none()
     }
 }
 
+/// Returns the cell at the given column, treating columns past the end of the row as empty.
+fn cell_at(row: &[Cell], x: usize) -> &Cell {
+    row.get(x).unwrap_or(&Cell::EMPTY)
+}
+
+/// Returns the column at which the character covering column `x` starts.
+fn char_start(row: &[Cell], x: usize) -> usize {
+    let mut col = 0;
+    while col < row.len() {
+        let width = row[col].character.as_ref().map_or(1, |c| c.width);
+        if col + width > x {
+            return col;
+        }
+        col += width;
+    }
+    x
+}
+
+/// Returns the column at which the character covering column `x - 1` ends.
+fn char_end(row: &[Cell], x: usize) -> usize {
+    if x == 0 || x > row.len() {
+        return x;
+    }
+    let start = char_start(row, x - 1);
+    start + row[start].character.as_ref().map_or(1, |c| c.width)
+}
+
+fn write_cells<W: Write>(
+    w: &mut W,
+    row: &[Cell],
+    ansi: bool,
+    background_color: &mut Option<Color>,
+    text_style: &mut CanvasTextStyle,
+) -> io::Result<()> {
+    let mut col = 0;
+    while col < row.len() {
+        let cell = &row[col];
+
+        if ansi {
+            // For certain changes, we need to reset all attributes.
+            let mut needs_reset = false;
+            if let Some(c) = &cell.character {
+                if text_style.has_attributes_missing_from(&c.style) {
+                    needs_reset = true;
+                }
+            } else if text_style.has_attributes_missing_from(&CanvasTextStyle::default()) {
+                needs_reset = true;
+            }
+            if needs_reset {
+                write!(w, csi!("0m"))?;
+                *background_color = None;
+                *text_style = CanvasTextStyle::default();
+            }
+
+            if cell.background_color != *background_color {
+                write!(
+                    w,
+                    csi!("{}m"),
+                    Colored::BackgroundColor(cell.background_color.unwrap_or(Color::Reset))
+                )?;
+                *background_color = cell.background_color;
+            }
+
+            if let Some(c) = &cell.character {
+                if c.style.color != text_style.color {
+                    write!(
+                        w,
+                        csi!("{}m"),
+                        Colored::ForegroundColor(c.style.color.unwrap_or(Color::Reset))
+                    )?;
+                }
+
+                if c.style.weight == Weight::Bold && text_style.weight != Weight::Bold {
+                    write!(w, csi!("{}m"), Attribute::Bold.sgr())?;
+                }
+
+                if c.style.is_dim() && !text_style.is_dim() {
+                    write!(w, csi!("{}m"), Attribute::Dim.sgr())?;
+                }
+
+                if c.style.italic && !text_style.italic {
+                    write!(w, csi!("{}m"), Attribute::Italic.sgr())?;
+                }
+
+                if c.style.underline && !text_style.underline {
+                    write!(w, csi!("{}m"), Attribute::Underlined.sgr())?;
+                }
+
+                if c.style.strikethrough && !text_style.strikethrough {
+                    write!(w, csi!("{}m"), Attribute::CrossedOut.sgr())?;
+                }
+
+                if c.style.reverse && !text_style.reverse {
+                    write!(w, csi!("{}m"), Attribute::Reverse.sgr())?;
+                }
+
+                if c.style.blink && !text_style.blink {
+                    write!(w, csi!("{}m"), Attribute::SlowBlink.sgr())?;
+                }
+
+                *text_style = c.style;
+            }
+        }
+
+        if let Some(c) = &cell.character {
+            c.write_value(w)?;
+            col += c.width;
+        } else {
+            w.write_all(b" ")?;
+            col += 1;
+        }
+    }
+    Ok(())
+}
+
+/// The format that a [`Canvas`] is written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Unstyled text.
+    Plain,
+    /// Text styled with ANSI escape codes, and no other control sequences. Lines end with `\n`.
+    Ansi,
+    /// Text styled with ANSI escape codes, which also clears the remainder of each line and ends
+    /// lines with `\r\n` so that it can be written over previous output in raw mode.
+    Terminal,
+}
+
 /// Canvas is a low-level abstraction for rendering output. Most users of the library will not need
 /// to use it directly. However, it is used by low level component implementations and can be used
 /// to store and copy their output.
@@ -79,17 +341,52 @@ impl Canvas {
         }
     }
 
-    fn set_text_row_chars<I>(&mut self, mut x: usize, y: usize, chars: I, style: CanvasTextStyle)
-    where
-        I: IntoIterator<Item = char>,
+    fn set_text_row_graphemes<'a, I>(
+        &mut self,
+        mut x: usize,
+        y: usize,
+        graphemes: I,
+        style: CanvasTextStyle,
+    ) where
+        I: IntoIterator<Item = (&'a str, usize)>,
     {
         let row = &mut self.cells[y];
-        for c in chars.into_iter() {
+        for (grapheme, width) in graphemes.into_iter() {
             if x >= row.len() {
                 break;
             }
-            row[x].character = Some(Character { value: c, style });
-            x += c.width().unwrap_or(0);
+            // Anything without a width, such as a stray combining mark, can't be drawn on its own.
+            if width == 0 {
+                continue;
+            }
+            let mut chars = grapheme.chars();
+            let Some(value) = chars.next() else {
+                continue;
+            };
+            let rest = chars.as_str();
+            if let Some(color) = style.background_color {
+                for cell in row.iter_mut().skip(x).take(width) {
+                    cell.background_color = Some(color);
+                }
+            }
+            row[x].character = Some(Character {
+                value,
+                rest: (!rest.is_empty()).then(|| rest.into()),
+                width,
+                style,
+            });
+            x += width;
+        }
+    }
+
+    /// Removes the blink attribute from all of the canvas's text.
+    pub(crate) fn disable_blink(&mut self) {
+        for row in &mut self.cells {
+            for cell in row {
+                if let Some(c) = &mut cell.character {
+                    c.style.blink = false;
+                }
+            }
         }
     }
 
@@ -101,7 +398,20 @@ impl Canvas {
         width: usize,
         height: usize,
         clip: bool,
-    ) -> CanvasSubviewMut {
+    ) -> CanvasSubviewMut<'_> {
+        self.subview_mut_at(x as _, y as _, width, height, clip)
+    }
+
+    /// Gets a subview of the canvas for writing, which may start above or to the left of the
+    /// canvas. Anything written outside of the canvas is discarded.
+    pub(crate) fn subview_mut_at(
+        &mut self,
+        x: isize,
+        y: isize,
+        width: usize,
+        height: usize,
+        clip: bool,
+    ) -> CanvasSubviewMut<'_> {
         CanvasSubviewMut {
             y,
             x,
@@ -112,12 +422,66 @@ impl Canvas {
         }
     }
 
+    /// Copies the given region of another canvas into this one at the given position. Empty cells
+    /// are skipped so that anything already drawn beneath them remains visible, and anything
+    /// outside of either canvas is ignored.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn copy_from(
+        &mut self,
+        src: &Canvas,
+        src_x: usize,
+        src_y: usize,
+        width: usize,
+        height: usize,
+        dest_x: isize,
+        dest_y: isize,
+    ) {
+        for y in 0..height {
+            let Ok(dest_row) = usize::try_from(dest_y + y as isize) else {
+                continue;
+            };
+            let (Some(src_row), Some(dest_row)) =
+                (src.cells.get(src_y + y), self.cells.get_mut(dest_row))
+            else {
+                continue;
+            };
+            for x in 0..width {
+                let Ok(dest_col) = usize::try_from(dest_x + x as isize) else {
+                    continue;
+                };
+                let (Some(src_cell), Some(dest_cell)) =
+                    (src_row.get(src_x + x), dest_row.get_mut(dest_col))
+                else {
+                    continue;
+                };
+                if src_cell.background_color.is_some() {
+                    dest_cell.background_color = src_cell.background_color;
+                }
+                if src_cell.character.is_some() {
+                    dest_cell.character = src_cell.character.clone();
+                }
+            }
+        }
+    }
+
+    /// Replaces every color in the canvas using the given function.
+    pub(crate) fn map_colors<F: Fn(Color) -> Color>(&mut self, f: F) {
+        for cell in self.cells.iter_mut().flatten() {
+            cell.background_color = cell.background_color.map(&f);
+            if let Some(c) = &mut cell.character {
+                c.style.color = c.style.color.map(&f);
+                c.style.background_color = c.style.background_color.map(&f);
+            }
+        }
+    }
+
     fn write_impl<W: Write>(
         &self,
         mut w: W,
-        ansi: bool,
+        format: OutputFormat,
         omit_final_newline: bool,
     ) -> io::Result<()> {
+        let ansi = format != OutputFormat::Plain;
         if ansi {
             write!(w, csi!("0m"))?;
         }
@@ -127,84 +491,26 @@ impl Canvas {
 
         for y in 0..self.cells.len() {
             let row = &self.cells[y];
-            let last_non_empty = row.iter().rposition(|cell| !cell.is_empty());
-            let row = &row[..last_non_empty.map_or(0, |i| i + 1)];
-            let mut col = 0;
-            while col < row.len() {
-                let cell = &row[col];
-
-                if ansi {
-                    // For certain changes, we need to reset all attributes.
-                    let mut needs_reset = false;
-                    if let Some(c) = &cell.character {
-                        if c.style.weight != text_style.weight && c.style.weight == Weight::Normal {
-                            needs_reset = true;
-                        }
-                        if !c.style.underline && text_style.underline {
-                            needs_reset = true;
-                        }
-                    } else if text_style.underline {
-                        needs_reset = true;
-                    }
-                    if needs_reset {
-                        write!(w, csi!("0m"))?;
-                        background_color = None;
-                        text_style = CanvasTextStyle::default();
-                    }
-
-                    if cell.background_color != background_color {
-                        write!(
-                            w,
-                            csi!("{}m"),
-                            Colored::BackgroundColor(cell.background_color.unwrap_or(Color::Reset))
-                        )?;
-                        background_color = cell.background_color;
-                    }
-
-                    if let Some(c) = &cell.character {
-                        if c.style.color != text_style.color {
-                            write!(
-                                w,
-                                csi!("{}m"),
-                                Colored::ForegroundColor(c.style.color.unwrap_or(Color::Reset))
-                            )?;
-                        }
-
-                        if c.style.weight != text_style.weight {
-                            match c.style.weight {
-                                Weight::Bold => write!(w, csi!("{}m"), Attribute::Bold.sgr())?,
-                                Weight::Normal => {}
-                                Weight::Light => write!(w, csi!("{}m"), Attribute::Dim.sgr())?,
-                            }
-                        }
-
-                        if c.style.underline && !text_style.underline {
-                            write!(w, csi!("{}m"), Attribute::Underlined.sgr())?;
-                        }
-
-                        text_style = c.style;
-                    }
-                }
-
-                if let Some(c) = &cell.character {
-                    write!(w, "{}", c.value)?;
-                    col += c.value.width().unwrap_or(0);
-                } else {
-                    w.write_all(b" ")?;
-                    col += 1;
-                }
-            }
+            let row = &row[..self.row_width(y).min(row.len())];
+            write_cells(&mut w, row, ansi, &mut background_color, &mut text_style)?;
             if ansi {
                 // if the background color is set, we need to reset it
                 if background_color.is_some() {
                     write!(w, csi!("{}m"), Colored::BackgroundColor(Color::Reset))?;
                     background_color = None;
                 }
+            }
+            if format == OutputFormat::Ansi && text_style != CanvasTextStyle::default() {
+                // each line should stand on its own, e.g. for tools which process output by line
+                write!(w, csi!("0m"))?;
+                text_style = CanvasTextStyle::default();
+            }
+            if format == OutputFormat::Terminal {
                 // clear until end of line
                 write!(w, csi!("K"))?;
             }
             if !omit_final_newline || y < self.cells.len() - 1 {
-                if ansi {
+                if format == OutputFormat::Terminal {
                     // add a carriage return in case we're in raw mode
                     w.write_all(b"\r\n")?;
                 } else {
@@ -219,21 +525,503 @@ impl Canvas {
         Ok(())
     }
 
+    /// Returns the number of columns occupied by the row once trailing empty cells are removed.
+    pub(crate) fn row_width(&self, y: usize) -> usize {
+        let row = &self.cells[y];
+        match row.iter().rposition(|cell| !cell.is_empty()) {
+            Some(i) => i + row[i].character.as_ref().map_or(1, |c| c.width),
+            None => 0,
+        }
+    }
+
+    /// Writes the changes needed to turn `prev` into this canvas, with ANSI escape codes.
+    ///
+    /// The cursor must be where writing `prev` via [`write_ansi`](Self::write_ansi) (or without
+    /// the final newline, if `omit_final_newline` is true) left it, and no row of either canvas may
+    /// be wider than the terminal. Afterwards, the cursor is left as if this canvas had been
+    /// written in full. Only the cells which differ are written, which avoids the flicker of
+    /// clearing and redrawing the whole canvas.
+    pub(crate) fn write_ansi_diff<W: Write>(
+        &self,
+        mut w: W,
+        prev: &Canvas,
+        omit_final_newline: bool,
+    ) -> io::Result<()> {
+        // Rows are addressed relative to the canvas's top row.
+        let last_row = |height: usize| {
+            if omit_final_newline {
+                height.saturating_sub(1)
+            } else {
+                height
+            }
+        };
+        let mut cursor_row = last_row(prev.height());
+        let mut move_to = |w: &mut W, row: usize, col: usize| -> io::Result<()> {
+            if row < cursor_row {
+                queue!(w, cursor::MoveUp((cursor_row - row) as _))?;
+            } else if row > cursor_row {
+                queue!(w, cursor::MoveDown((row - cursor_row) as _))?;
+            }
+            cursor_row = row;
+            queue!(w, cursor::MoveToColumn(col as _))
+        };
+
+        for y in 0..self.height().min(prev.height()) {
+            let row = &self.cells[y];
+            let prev_row = &prev.cells[y];
+            let width = row.len().max(prev_row.len());
+            let Some(first) = (0..width).find(|&x| cell_at(row, x) != cell_at(prev_row, x)) else {
+                continue;
+            };
+            let last = (first..width)
+                .rfind(|&x| cell_at(row, x) != cell_at(prev_row, x))
+                .unwrap_or(first);
+
+            // Never start or stop writing in the middle of a wide character on either canvas.
+            let mut start = first;
+            loop {
+                let s = char_start(row, start).min(char_start(prev_row, start));
+                if s == start {
+                    break;
+                }
+                start = s;
+            }
+            let mut end = last + 1;
+            loop {
+                let e = char_end(row, end).max(char_end(prev_row, end));
+                if e == end {
+                    break;
+                }
+                end = e;
+            }
+
+            let row_width = self.row_width(y);
+            let clear_to_end = end >= row_width;
+            let end = if clear_to_end { row_width } else { end };
+
+            move_to(&mut w, y, start)?;
+            write!(w, csi!("0m"))?;
+            let mut background_color = None;
+            let mut text_style = CanvasTextStyle::default();
+            if start < end {
+                write_cells(
+                    &mut w,
+                    &row[start..end],
+                    true,
+                    &mut background_color,
+                    &mut text_style,
+                )?;
+            }
+            if clear_to_end {
+                if background_color.is_some() {
+                    write!(w, csi!("{}m"), Colored::BackgroundColor(Color::Reset))?;
+                }
+                write!(w, csi!("K"))?;
+            }
+            write!(w, csi!("0m"))?;
+        }
+
+        if self.height() > prev.height() {
+            // The new rows are written just like they would be for a full write.
+            let rest = Canvas {
+                width: self.width,
+                cells: self.cells[prev.height()..].to_vec(),
+            };
+            if omit_final_newline {
+                if prev.height() > 0 {
+                    move_to(&mut w, prev.height() - 1, 0)?;
+                    w.write_all(b"\r\n")?;
+                }
+            } else {
+                move_to(&mut w, prev.height(), 0)?;
+            }
+            rest.write_impl(&mut w, OutputFormat::Terminal, omit_final_newline)?;
+        } else {
+            if self.height() < prev.height() {
+                move_to(&mut w, self.height(), 0)?;
+                queue!(w, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+            }
+            move_to(&mut w, last_row(self.height()), 0)?;
+        }
+
+        w.flush()
+    }
+
     /// Writes the canvas to the given writer with ANSI escape codes.
     pub fn write_ansi<W: Write>(&self, w: W) -> io::Result<()> {
-        self.write_impl(w, true, false)
+        self.write_impl(w, OutputFormat::Terminal, false)
     }
 
     pub(crate) fn write_ansi_without_final_newline<W: Write>(&self, w: W) -> io::Result<()> {
-        self.write_impl(w, true, true)
+        self.write_impl(w, OutputFormat::Terminal, true)
+    }
+
+    /// Writes the canvas to the given writer with ANSI escape codes for its styles, but no other
+    /// control sequences.
+    ///
+    /// Unlike [`write_ansi`](Self::write_ansi), the output doesn't clear anything that was
+    /// previously on the terminal, and lines end with `\n` rather than `\r\n`. This makes it
+    /// suitable for writing to files, or for piping to programs such as `less -R`.
+    pub fn write_ansi_styles<W: Write>(&self, w: W) -> io::Result<()> {
+        self.write_impl(w, OutputFormat::Ansi, false)
     }
 
     /// Writes the canvas to the given writer as unstyled text, without ANSI escape codes.
     pub fn write<W: Write>(&self, w: W) -> io::Result<()> {
-        self.write_impl(w, false, false)
+        self.write_impl(w, OutputFormat::Plain, false)
+    }
+
+    /// Writes the canvas to the given writer as a grid for debugging and snapshot tests.
+    ///
+    /// The canvas is framed by a border so that trailing spaces and empty rows are visible. If
+    /// `axis_labels` is true, the rows are numbered and rulers above the grid number the columns.
+    /// Styles are annotated inline with tags rather than written as escape codes, so the output
+    /// stays readable in diffs. For example, bold red text on a blue background is written as
+    /// `[red bold bg:blue]text[/red bold bg:blue]`. Colors are named as in
+    /// [`color::NAMED_COLORS`](crate::color::NAMED_COLORS), or written in hex.
+    pub fn write_debug_grid<W: Write>(&self, mut w: W, axis_labels: bool) -> io::Result<()> {
+        let label_width = if axis_labels {
+            self.height().saturating_sub(1).to_string().len() + 1
+        } else {
+            0
+        };
+        let margin = " ".repeat(label_width);
+
+        if axis_labels {
+            if self.width > 10 {
+                let tens = (0..self.width)
+                    .map(|x| match x % 10 {
+                        0 => char::from_digit((x / 10 % 10) as _, 10).unwrap_or(' '),
+                        _ => ' ',
+                    })
+                    .collect::<String>();
+                writeln!(w, "{} {}", margin, tens.trim_end())?;
+            }
+            let ones = (0..self.width)
+                .map(|x| char::from_digit((x % 10) as _, 10).unwrap_or(' '))
+                .collect::<String>();
+            writeln!(w, "{} {}", margin, ones)?;
+        }
+
+        let border = "─".repeat(self.width);
+        writeln!(w, "{}┌{}┐", margin, border)?;
+        for (y, row) in self.cells.iter().enumerate() {
+            if axis_labels {
+                write!(w, "{:>1$} ", y, label_width - 1)?;
+            }
+            w.write_all("│".as_bytes())?;
+            let mut open_tag = String::new();
+            let mut col = 0;
+            while col < self.width {
+                let cell = cell_at(row, col);
+                let tag = debug_tag(cell);
+                if tag != open_tag {
+                    if !open_tag.is_empty() {
+                        write!(w, "[/{}]", open_tag)?;
+                    }
+                    if !tag.is_empty() {
+                        write!(w, "[{}]", tag)?;
+                    }
+                    open_tag = tag;
+                }
+                match &cell.character {
+                    Some(c) => {
+                        c.write_value(&mut w)?;
+                        col += c.width;
+                    }
+                    None => {
+                        w.write_all(b" ")?;
+                        col += 1;
+                    }
+                }
+            }
+            if !open_tag.is_empty() {
+                write!(w, "[/{}]", open_tag)?;
+            }
+            writeln!(w, "│")?;
+        }
+        writeln!(w, "{}└{}┘", margin, border)?;
+        w.flush()
+    }
+
+    /// Writes the canvas to the given writer as a static HTML `<pre>` element with inline CSS.
+    ///
+    /// The output approximates how the canvas looks in a terminal with a dark background, which
+    /// makes it useful for documentation and web previews. Colors from the terminal's palette are
+    /// written using xterm's defaults, and wide characters are given two columns so that the grid
+    /// stays aligned.
+    #[cfg(feature = "html")]
+    pub fn write_html<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write!(
+            w,
+            "<pre style=\"font-family:monospace;line-height:1.2;padding:0.5em;color:{};background-color:{}\">",
+            html_color(HTML_FOREGROUND),
+            html_color(HTML_BACKGROUND),
+        )?;
+        for y in 0..self.cells.len() {
+            let row = &self.cells[y];
+            let row = &row[..self.row_width(y).min(row.len())];
+            let mut open_style = String::new();
+            let mut col = 0;
+            while col < row.len() {
+                let cell = &row[col];
+                let style = html_style(cell);
+                if style != open_style {
+                    if !open_style.is_empty() {
+                        w.write_all(b"</span>")?;
+                    }
+                    if !style.is_empty() {
+                        write!(w, "<span style=\"{}\">", style)?;
+                    }
+                    open_style = style;
+                }
+                match &cell.character {
+                    Some(c) => {
+                        let mut value = String::new();
+                        value.push(c.value);
+                        if let Some(rest) = &c.rest {
+                            value.push_str(rest);
+                        }
+                        let value = html_escape(&value);
+                        if c.width > 1 {
+                            write!(
+                                w,
+                                "<span style=\"display:inline-block;width:{}ch\">{}</span>",
+                                c.width, value
+                            )?;
+                        } else {
+                            w.write_all(value.as_bytes())?;
+                        }
+                        col += c.width;
+                    }
+                    None => {
+                        w.write_all(b" ")?;
+                        col += 1;
+                    }
+                }
+            }
+            if !open_style.is_empty() {
+                w.write_all(b"</span>")?;
+            }
+            w.write_all(b"\n")?;
+        }
+        w.write_all(b"</pre>\n")?;
+        w.flush()
+    }
+
+    /// Writes the canvas into the given area of a [ratatui](https://ratatui.rs) buffer, translating
+    /// its characters and styles into ratatui cells. Anything which doesn't fit within the area
+    /// or the buffer is clipped.
+    ///
+    /// Cells which the canvas leaves empty are left as they are, so the canvas can be drawn over
+    /// whatever the buffer already contains.
+    #[cfg(feature = "ratatui")]
+    pub fn write_ratatui_buffer(
+        &self,
+        area: ratatui::layout::Rect,
+        buf: &mut ratatui::buffer::Buffer,
+    ) {
+        let area = area.intersection(buf.area);
+        for (y, row) in self.cells.iter().take(area.height as usize).enumerate() {
+            let y = area.y + y as u16;
+            let width = row.len().min(area.width as usize);
+            let mut col = 0;
+            while col < width {
+                let cell = &row[col];
+                let char_width = cell.character.as_ref().map_or(1, |c| c.width);
+                if !cell.is_empty() && col + char_width <= width {
+                    let x = area.x + col as u16;
+                    let style = ratatui_style(cell);
+                    let target = &mut buf[(x, y)];
+                    target.reset();
+                    target.set_style(style);
+                    if let Some(c) = &cell.character {
+                        let mut symbol = c.value.to_string();
+                        if let Some(rest) = &c.rest {
+                            symbol.push_str(rest);
+                        }
+                        target.set_symbol(&symbol);
+                    }
+                    // Like ratatui itself, reset the cells hidden by wide characters.
+                    for i in 1..char_width {
+                        buf[(x + i as u16, y)].reset();
+                    }
+                }
+                col += char_width;
+            }
+        }
+    }
+}
+
+/// Returns the ratatui equivalent of a color, following ratatui's own crossterm backend.
+#[cfg(feature = "ratatui")]
+fn ratatui_color(color: Color) -> ratatui::style::Color {
+    use ratatui::style::Color as R;
+    match color {
+        Color::Reset => R::Reset,
+        Color::Black => R::Black,
+        Color::DarkGrey => R::DarkGray,
+        Color::Red => R::LightRed,
+        Color::DarkRed => R::Red,
+        Color::Green => R::LightGreen,
+        Color::DarkGreen => R::Green,
+        Color::Yellow => R::LightYellow,
+        Color::DarkYellow => R::Yellow,
+        Color::Blue => R::LightBlue,
+        Color::DarkBlue => R::Blue,
+        Color::Magenta => R::LightMagenta,
+        Color::DarkMagenta => R::Magenta,
+        Color::Cyan => R::LightCyan,
+        Color::DarkCyan => R::Cyan,
+        Color::White => R::White,
+        Color::Grey => R::Gray,
+        Color::Rgb { r, g, b } => R::Rgb(r, g, b),
+        Color::AnsiValue(n) => R::Indexed(n),
     }
 }
 
+/// Returns the ratatui style of a cell.
+#[cfg(feature = "ratatui")]
+fn ratatui_style(cell: &Cell) -> ratatui::style::Style {
+    use ratatui::style::{Modifier, Style};
+    let mut style = Style::default();
+    if let Some(color) = cell.background_color {
+        style = style.bg(ratatui_color(color));
+    }
+    if let Some(c) = &cell.character {
+        let text_style = &c.style;
+        if let Some(color) = text_style.color {
+            style = style.fg(ratatui_color(color));
+        }
+        for (is_set, modifier) in [
+            (text_style.weight == Weight::Bold, Modifier::BOLD),
+            (text_style.is_dim(), Modifier::DIM),
+            (text_style.italic, Modifier::ITALIC),
+            (text_style.underline, Modifier::UNDERLINED),
+            (text_style.strikethrough, Modifier::CROSSED_OUT),
+            (text_style.reverse, Modifier::REVERSED),
+            (text_style.blink, Modifier::SLOW_BLINK),
+        ] {
+            if is_set {
+                style = style.add_modifier(modifier);
+            }
+        }
+    }
+    style
+}
+
+/// The color which unstyled text is given in HTML output.
+#[cfg(feature = "html")]
+const HTML_FOREGROUND: Color = Color::Grey;
+
+/// The color which unstyled cells are given in HTML output.
+#[cfg(feature = "html")]
+const HTML_BACKGROUND: Color = Color::Black;
+
+/// Returns the CSS value for a color. [`Color::Reset`] must be resolved by the caller.
+#[cfg(feature = "html")]
+fn html_color(color: Color) -> String {
+    let (r, g, b) = crate::color::to_rgb(color).unwrap_or((0, 0, 0));
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Returns the inline CSS for a cell in HTML output, or an empty string if the cell is unstyled.
+#[cfg(feature = "html")]
+fn html_style(cell: &Cell) -> String {
+    let style = cell.character.as_ref().map(|c| c.style).unwrap_or_default();
+    let mut color = style.color.filter(|&c| c != Color::Reset);
+    let mut background_color = cell.background_color.filter(|&c| c != Color::Reset);
+    if style.reverse {
+        (color, background_color) = (
+            Some(background_color.unwrap_or(HTML_BACKGROUND)),
+            Some(color.unwrap_or(HTML_FOREGROUND)),
+        );
+    }
+
+    let mut parts = Vec::new();
+    if let Some(color) = color {
+        parts.push(format!("color:{}", html_color(color)));
+    }
+    if let Some(color) = background_color {
+        parts.push(format!("background-color:{}", html_color(color)));
+    }
+    if style.weight == Weight::Bold {
+        parts.push("font-weight:bold".to_string());
+    }
+    if style.is_dim() {
+        parts.push("opacity:0.6".to_string());
+    }
+    if style.italic {
+        parts.push("font-style:italic".to_string());
+    }
+    let decorations = [
+        (style.underline, "underline"),
+        (style.strikethrough, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(is_set, name)| is_set.then_some(name))
+    .collect::<Vec<_>>();
+    if !decorations.is_empty() {
+        parts.push(format!("text-decoration:{}", decorations.join(" ")));
+    }
+    parts.join(";")
+}
+
+/// Escapes the characters which have special meaning in HTML text.
+#[cfg(feature = "html")]
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Returns the name of a color for debug output.
+fn debug_color_name(color: Color) -> String {
+    match color {
+        Color::Rgb { r, g, b } => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Color::AnsiValue(n) => format!("ansi{}", n),
+        color => {
+            let mut name = String::new();
+            for (i, c) in format!("{:?}", color).chars().enumerate() {
+                if c.is_uppercase() && i > 0 {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_lowercase());
+            }
+            name
+        }
+    }
+}
+
+/// Returns the tag which describes the style of a cell in debug output, or an empty string if the
+/// cell is unstyled.
+fn debug_tag(cell: &Cell) -> String {
+    let mut parts = Vec::new();
+    if let Some(c) = &cell.character {
+        let style = &c.style;
+        if let Some(color) = style.color {
+            parts.push(debug_color_name(color));
+        }
+        for (is_set, name) in [
+            (style.weight == Weight::Bold, "bold"),
+            (style.is_dim(), "dim"),
+            (style.italic, "italic"),
+            (style.underline, "underline"),
+            (style.strikethrough, "strikethrough"),
+            (style.reverse, "reverse"),
+            (style.blink, "blink"),
+        ] {
+            if is_set {
+                parts.push(name.to_string());
+            }
+        }
+    }
+    if let Some(color) = cell.background_color {
+        parts.push(format!("bg:{}", debug_color_name(color)));
+    }
+    parts.join(" ")
+}
+
 impl Display for Canvas {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut buf = Vec::with_capacity(self.width * self.cells.len());
@@ -246,8 +1034,8 @@ impl Display for Canvas {
 /// Represents a writeable region of a [`Canvas`]. All coordinates provided to functions of this
 /// type are relative to the region's top-left corner.
 pub struct CanvasSubviewMut<'a> {
-    x: usize,
-    y: usize,
+    x: isize,
+    y: isize,
     width: usize,
     height: usize,
     clip: bool,
@@ -257,15 +1045,21 @@ pub struct CanvasSubviewMut<'a> {
 impl<'a> CanvasSubviewMut<'a> {
     /// Fills the region with the given color.
     pub fn set_background_color(&mut self, x: isize, y: isize, w: usize, h: usize, color: Color) {
-        let mut left = self.x as isize + x;
-        let mut top = self.y as isize + y;
+        let mut left = self.x + x;
+        let mut top = self.y + y;
         let mut right = left + w as isize;
         let mut bottom = top + h as isize;
         if self.clip {
-            left = left.max(self.x as isize);
-            top = top.max(self.y as isize);
-            right = right.min((self.x + self.width) as isize);
-            bottom = bottom.min((self.y + self.height) as isize);
+            left = left.max(self.x);
+            top = top.max(self.y);
+            right = right.min(self.x + self.width as isize);
+            bottom = bottom.min(self.y + self.height as isize);
+        }
+        left = left.max(0);
+        top = top.max(0);
+        bottom = bottom.min(self.canvas.height() as isize);
+        if right <= left || bottom <= top {
+            return;
         }
         self.canvas.set_background_color(
             left as _,
@@ -276,42 +1070,87 @@ impl<'a> CanvasSubviewMut<'a> {
         );
     }
 
+    /// Modifies the style of any text already written to the given area of the region.
+    pub(crate) fn map_text_style<F: Fn(&mut CanvasTextStyle)>(
+        &mut self,
+        x: usize,
+        y: usize,
+        w: usize,
+        h: usize,
+        f: F,
+    ) {
+        let (w, h) = if self.clip {
+            (
+                w.min(self.width.saturating_sub(x)),
+                h.min(self.height.saturating_sub(y)),
+            )
+        } else {
+            (w, h)
+        };
+        // Skip whatever lies above or to the left of the canvas.
+        let (top, left) = (self.y + y as isize, self.x + x as isize);
+        let (h, w) = (
+            h.saturating_sub((-top).max(0) as usize),
+            w.saturating_sub((-left).max(0) as usize),
+        );
+        for row in self.canvas.cells.iter_mut().skip(top.max(0) as _).take(h) {
+            for cell in row.iter_mut().skip(left.max(0) as _).take(w) {
+                if let Some(c) = &mut cell.character {
+                    f(&mut c.style);
+                }
+            }
+        }
+    }
+
     /// Writes text to the region.
-    pub fn set_text(&mut self, x: isize, mut y: isize, text: &str, style: CanvasTextStyle) {
-        let mut x = self.x as isize + x;
-        let min_x = if self.clip { self.x as isize } else { 0 };
+    pub fn set_text(&mut self, x: isize, y: isize, text: &str, style: CanvasTextStyle) {
+        self.set_text_with_emoji_width(x, y, text, style, EmojiWidth::default());
+    }
+
+    /// Writes text to the region, measuring emoji sequences according to the given policy.
+    pub(crate) fn set_text_with_emoji_width(
+        &mut self,
+        x: isize,
+        mut y: isize,
+        text: &str,
+        style: CanvasTextStyle,
+        emoji_width: EmojiWidth,
+    ) {
+        let mut x = self.x + x;
+        let min_x = if self.clip { self.x.max(0) } else { 0 };
         let mut to_skip = 0;
         if x < min_x {
             to_skip = min_x - x;
             x = min_x;
         }
         let max_x = if self.clip {
-            (self.x + self.width) as isize - 1
+            self.x + self.width as isize - 1
         } else {
             self.canvas.width as isize - 1
         };
         let horizontal_space = max_x - x + 1;
         for line in text.lines() {
             if !self.clip || (y >= 0 && y < self.height as isize) {
-                let y = self.y as isize + y;
+                let y = self.y + y;
                 if y >= 0 && y < self.canvas.height() as _ {
                     let mut skipped_width = 0;
                     let mut taken_width = 0;
-                    self.canvas.set_text_row_chars(
+                    self.canvas.set_text_row_graphemes(
                         x as usize,
                         y as usize,
-                        line.chars()
-                            .skip_while(|c| {
+                        graphemes(line)
+                            .map(|g| (g, emoji_width.grapheme_width(g)))
+                            .skip_while(|(_, width)| {
                                 if skipped_width < to_skip {
-                                    skipped_width += c.width().unwrap_or(0) as isize;
+                                    skipped_width += *width as isize;
                                     true
                                 } else {
                                     false
                                 }
                             })
-                            .take_while(|c| {
+                            .take_while(|(_, width)| {
                                 if taken_width < horizontal_space {
-                                    taken_width += c.width().unwrap_or(0) as isize;
+                                    taken_width += *width as isize;
                                     true
                                 } else {
                                     false
@@ -451,6 +1290,9 @@ mod tests {
         write!(expected, csi!("{}m"), Attribute::Bold.sgr()).unwrap();
         write!(expected, ".").unwrap();
 
+        // Going from bold to light requires a reset, or the text would be both bold and dimmed.
+        write!(expected, csi!("0m")).unwrap();
+        write!(expected, csi!("{}m"), Colored::ForegroundColor(Color::Red)).unwrap();
         write!(expected, csi!("{}m"), Attribute::Dim.sgr()).unwrap();
         write!(expected, ".").unwrap();
 
@@ -490,6 +1332,207 @@ mod tests {
         assert_eq!(actual, "\n\n  ne 2\n  ne 3\n\n");
     }
 
+    #[test]
+    fn test_graphemes() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let text = format!(
+            "e\u{301}{}\u{1f1fa}\u{1f1f8}\u{1f1eb}\u{1f1f7}\u{1f44d}\u{1f3fd}\u{2764}\u{fe0f}a",
+            family
+        );
+        assert_eq!(
+            graphemes(&text).collect::<Vec<_>>(),
+            vec![
+                "e\u{301}",
+                family,
+                "\u{1f1fa}\u{1f1f8}",
+                "\u{1f1eb}\u{1f1f7}",
+                "\u{1f44d}\u{1f3fd}",
+                "\u{2764}\u{fe0f}",
+                "a",
+            ]
+        );
+        // Control characters are never combined.
+        assert_eq!(
+            graphemes("a\u{301}\n\u{301}").collect::<Vec<_>>(),
+            vec!["a\u{301}", "\n", "\u{301}"]
+        );
+        // A zero width joiner only joins emoji, so it stays with the letter before it.
+        assert_eq!(
+            graphemes("a\u{200d}b").collect::<Vec<_>>(),
+            vec!["a\u{200d}", "b"]
+        );
+        // Hangul jamo and Indic spacing marks combine into single syllables.
+        assert_eq!(
+            graphemes("\u{1100}\u{1161}\u{11a8}\u{915}\u{93f}").collect::<Vec<_>>(),
+            vec!["\u{1100}\u{1161}\u{11a8}", "\u{915}\u{93f}"]
+        );
+
+        assert_eq!(EmojiWidth::Joined.str_width(&text), 12);
+        assert_eq!(EmojiWidth::Joined.grapheme_width(family), 2);
+        assert_eq!(EmojiWidth::Separate.grapheme_width(family), 6);
+
+        // Each grapheme is drawn into a single cell, so combining marks don't replace the
+        // character before them.
+        let mut canvas = Canvas::new(10, 1);
+        canvas.subview_mut(0, 0, 10, 1, true).set_text(
+            0,
+            0,
+            &format!("e\u{301}{}!", family),
+            CanvasTextStyle::default(),
+        );
+        assert_eq!(canvas.row_width(0), 4);
+        assert_eq!(canvas.to_string(), format!("e\u{301}{}!\n", family));
+    }
+
+    #[test]
+    fn test_write_debug_grid() {
+        let mut canvas = Canvas::new(12, 2);
+        canvas.subview_mut(0, 0, 12, 2, false).set_text(
+            0,
+            0,
+            "hello",
+            CanvasTextStyle {
+                color: Some(Color::DarkRed),
+                weight: Weight::Bold,
+                ..Default::default()
+            },
+        );
+        canvas
+            .subview_mut(0, 0, 12, 2, false)
+            .set_text(6, 0, "世界", CanvasTextStyle::default());
+        canvas.subview_mut(0, 0, 12, 2, false).set_background_color(
+            10,
+            1,
+            2,
+            1,
+            Color::Rgb { r: 255, g: 0, b: 0 },
+        );
+
+        let grid = |axis_labels| {
+            let mut buf = Vec::new();
+            canvas.write_debug_grid(&mut buf, axis_labels).unwrap();
+            String::from_utf8(buf).unwrap()
+        };
+        assert_eq!(
+            grid(true),
+            [
+                "   0         1",
+                "   012345678901",
+                "  ┌────────────┐",
+                "0 │[dark_red bold]hello[/dark_red bold] 世界  │",
+                "1 │          [bg:#ff0000]  [/bg:#ff0000]│",
+                "  └────────────┘",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            grid(false),
+            [
+                "┌────────────┐",
+                "│[dark_red bold]hello[/dark_red bold] 世界  │",
+                "│          [bg:#ff0000]  [/bg:#ff0000]│",
+                "└────────────┘",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_write_html() {
+        let mut canvas = Canvas::new(8, 3);
+        canvas.subview_mut(0, 0, 8, 3, false).set_text(
+            0,
+            0,
+            "<hi>",
+            CanvasTextStyle {
+                color: Some(Color::Red),
+                weight: Weight::Bold,
+                ..Default::default()
+            },
+        );
+        canvas.subview_mut(0, 0, 8, 3, false).set_text(
+            0,
+            1,
+            "世 x",
+            CanvasTextStyle {
+                reverse: true,
+                ..Default::default()
+            },
+        );
+        canvas.subview_mut(0, 0, 8, 3, false).set_background_color(
+            2,
+            2,
+            2,
+            1,
+            Color::Rgb { r: 0, g: 0, b: 255 },
+        );
+
+        let mut buf = Vec::new();
+        canvas.write_html(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            [
+                "<pre style=\"font-family:monospace;line-height:1.2;padding:0.5em;color:#e5e5e5;background-color:#000000\"><span style=\"color:#ff0000;font-weight:bold\">&lt;hi&gt;</span>",
+                "<span style=\"color:#000000;background-color:#e5e5e5\"><span style=\"display:inline-block;width:2ch\">世</span> x</span>",
+                "  <span style=\"background-color:#0000ff\">  </span>",
+                "</pre>",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[cfg(feature = "ratatui")]
+    #[test]
+    fn test_write_ratatui_buffer() {
+        use ratatui::{
+            buffer::Buffer,
+            layout::Rect,
+            style::{Color as RatatuiColor, Modifier, Style},
+        };
+
+        let mut canvas = Canvas::new(8, 3);
+        canvas.subview_mut(0, 0, 8, 3, false).set_text(
+            0,
+            0,
+            "hi",
+            CanvasTextStyle {
+                color: Some(Color::DarkRed),
+                weight: Weight::Bold,
+                ..Default::default()
+            },
+        );
+        canvas
+            .subview_mut(0, 0, 8, 3, false)
+            .set_text(0, 1, "a世b世", CanvasTextStyle::default());
+        canvas.subview_mut(0, 0, 8, 3, false).set_background_color(
+            3,
+            0,
+            1,
+            1,
+            Color::Rgb { r: 0, g: 0, b: 255 },
+        );
+
+        // The area cuts the last wide character, and the last row, off.
+        let mut buf = Buffer::with_lines(["xxxxxxxx", "xxxxxxxx", "xxxxxxxx"]);
+        canvas.write_ratatui_buffer(Rect::new(1, 0, 5, 2), &mut buf);
+
+        let mut expected = Buffer::with_lines(["xhixxxxx", "xa世bxxx", "xxxxxxxx"]);
+        expected.set_style(
+            Rect::new(1, 0, 2, 1),
+            Style::new()
+                .fg(RatatuiColor::Red)
+                .add_modifier(Modifier::BOLD),
+        );
+        expected[(4, 0)]
+            .set_symbol(" ")
+            .set_bg(RatatuiColor::Rgb(0, 0, 255));
+        assert_eq!(buf, expected);
+    }
+
     #[test]
     fn test_write_ansi_without_final_newline() {
         let mut canvas = Canvas::new(10, 3);
@@ -515,4 +1558,104 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    // Applies output to a screen, supporting only the escape codes used by `write_ansi_diff`.
+    fn emulate(screen: &mut Vec<Vec<char>>, cursor: &mut (usize, usize), output: &[u8]) {
+        let output = String::from_utf8(output.to_vec()).unwrap();
+        let mut chars = output.chars().peekable();
+        while let Some(c) = chars.next() {
+            if screen.len() <= cursor.1 {
+                screen.resize(cursor.1 + 1, Vec::new());
+            }
+            match c {
+                '\x1b' => {
+                    assert_eq!(chars.next(), Some('['));
+                    let mut arg = String::new();
+                    while let Some(c) = chars.next_if(|c| !c.is_ascii_alphabetic()) {
+                        arg.push(c);
+                    }
+                    let n = arg.parse::<usize>().unwrap_or(1);
+                    let line = &mut screen[cursor.1];
+                    match chars.next().unwrap() {
+                        'A' => cursor.1 -= n,
+                        'B' => cursor.1 += n,
+                        'G' => cursor.0 = n - 1,
+                        'K' => line.truncate(cursor.0),
+                        'J' => {
+                            line.truncate(cursor.0);
+                            screen.truncate(cursor.1 + 1);
+                        }
+                        'm' => {}
+                        c => panic!("unexpected escape code: {}", c),
+                    }
+                }
+                '\r' => cursor.0 = 0,
+                '\n' => cursor.1 += 1,
+                c => {
+                    let line = &mut screen[cursor.1];
+                    if line.len() <= cursor.0 {
+                        line.resize(cursor.0 + 1, ' ');
+                    }
+                    line[cursor.0] = c;
+                    cursor.0 += 1;
+                }
+            }
+        }
+    }
+
+    fn text_canvas(text: &str) -> Canvas {
+        let mut canvas = Canvas::new(10, text.lines().count());
+        canvas
+            .subview_mut(0, 0, 10, canvas.height(), true)
+            .set_text(0, 0, text, CanvasTextStyle::default());
+        canvas
+    }
+
+    #[test]
+    fn test_write_ansi_diff() {
+        for omit_final_newline in [false, true] {
+            let mut screen = Vec::new();
+            let mut cursor = (0, 0);
+            let mut prev = text_canvas("foo\nbar\nbaz");
+            let mut output = Vec::new();
+            prev.write_impl(&mut output, OutputFormat::Terminal, omit_final_newline)
+                .unwrap();
+            emulate(&mut screen, &mut cursor, &output);
+
+            for text in [
+                "foo\nbar\nbaz",
+                "foo\nbaaaar\nbaz",
+                "foo\nba\nbaz",
+                "foo\nba\nbaz\nqux\nquux",
+                "fo",
+                "",
+                "hello\nworld",
+            ] {
+                let canvas = text_canvas(text);
+                let mut output = Vec::new();
+                canvas
+                    .write_ansi_diff(&mut output, &prev, omit_final_newline)
+                    .unwrap();
+                emulate(&mut screen, &mut cursor, &output);
+
+                let mut expected = Vec::new();
+                canvas
+                    .write_impl(&mut expected, OutputFormat::Terminal, omit_final_newline)
+                    .unwrap();
+                let mut expected_screen = Vec::new();
+                let mut expected_cursor = (0, 0);
+                emulate(&mut expected_screen, &mut expected_cursor, &expected);
+                expected_screen.resize(screen.len().max(expected_screen.len()), Vec::new());
+                screen.resize(expected_screen.len(), Vec::new());
+                assert_eq!(screen, expected_screen, "{:?}", text);
+                assert_eq!(cursor.1, expected_cursor.1, "{:?}", text);
+
+                // Unchanged lines aren't written again.
+                if text.starts_with("foo\n") && prev.to_string().starts_with("foo\n") {
+                    assert!(!String::from_utf8(output).unwrap().contains("foo"));
+                }
+                prev = canvas;
+            }
+        }
+    }
 }
@@ -0,0 +1,143 @@
+#[cfg(any(test, feature = "testing"))]
+use crate::testing::MockClock;
+use async_io::Timer;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// The source of time for the timers used by hooks such as
+/// [`use_interval`](crate::hooks::UseInterval::use_interval).
+///
+/// Each tree has exactly one, owned by its [`SystemState`](crate::context::SystemState). It
+/// follows real time, unless the tree is rendered by a `TestRenderer`, in which case it follows
+/// the renderer's virtual clock. Times are measured from when the clock was created.
+#[derive(Clone)]
+pub(crate) enum Clock {
+    Real(Instant),
+    #[cfg(any(test, feature = "testing"))]
+    Mock(MockClock),
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::Real(Instant::now())
+    }
+}
+
+impl Clock {
+    /// Returns the amount of time that has passed since the clock was created.
+    pub fn now(&self) -> Duration {
+        match self {
+            Self::Real(start) => start.elapsed(),
+            #[cfg(any(test, feature = "testing"))]
+            Self::Mock(clock) => clock.now(),
+        }
+    }
+
+    /// Returns a timer which fires once the clock reaches `deadline`.
+    pub fn timer_at(&self, deadline: Duration) -> ClockTimer {
+        ClockTimer {
+            clock: self.clone(),
+            deadline,
+            timer: None,
+        }
+    }
+
+    /// Returns a timer which fires every `period`, starting one `period` from now. Periods shorter
+    /// than [`MIN_INTERVAL_PERIOD`] are rounded up to it.
+    pub fn interval(&self, period: Duration) -> Interval {
+        let period = period.max(MIN_INTERVAL_PERIOD);
+        Interval {
+            timer: self.timer_at(self.now() + period),
+            period,
+        }
+    }
+}
+
+/// A timer which fires once its [`Clock`] reaches a deadline.
+pub(crate) struct ClockTimer {
+    clock: Clock,
+    deadline: Duration,
+    timer: Option<Timer>,
+}
+
+impl ClockTimer {
+    /// Returns the time at which the timer fires.
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    /// Moves the deadline of the timer.
+    pub fn set_deadline(&mut self, deadline: Duration) {
+        if deadline != self.deadline {
+            self.deadline = deadline;
+            self.timer = None;
+        }
+    }
+
+    /// Returns `Poll::Ready` once the clock has reached the deadline, and keeps doing so until
+    /// the deadline is moved.
+    ///
+    /// For virtual clocks, no waker is registered, since the test renderer polls the tree again
+    /// whenever it advances the clock.
+    pub fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        match &self.clock {
+            Clock::Real(start) => {
+                let deadline = *start + self.deadline;
+                let timer = self.timer.get_or_insert_with(|| Timer::at(deadline));
+                Pin::new(timer).poll(cx).map(|_| ())
+            }
+            #[cfg(any(test, feature = "testing"))]
+            Clock::Mock(clock) => {
+                if clock.now() >= self.deadline {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+/// The shortest period an [`Interval`] fires at, so that a zero period doesn't spin the render loop.
+pub(crate) const MIN_INTERVAL_PERIOD: Duration = Duration::from_millis(1);
+
+/// A timer which fires periodically, created by [`Clock::interval`].
+pub(crate) struct Interval {
+    timer: ClockTimer,
+    period: Duration,
+}
+
+impl Interval {
+    /// Restarts the interval with a new period, so that it next fires one `period` from now.
+    /// Periods shorter than [`MIN_INTERVAL_PERIOD`] are rounded up to it.
+    pub fn restart(&mut self, period: Duration) {
+        let period = period.max(MIN_INTERVAL_PERIOD);
+        self.period = period;
+        let now = self.timer.clock.now();
+        self.timer.set_deadline(now + period);
+    }
+
+    /// Returns `true` if the interval has ticked since the last poll.
+    ///
+    /// If several ticks came due since then, e.g. because the render loop was busy, they're
+    /// coalesced into one, and the interval carries on with its original schedule.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> bool {
+        if self.timer.poll(cx).is_pending() {
+            return false;
+        }
+        let now = self.timer.clock.now();
+        let behind = now.saturating_sub(self.timer.deadline()).as_nanos() % self.period.as_nanos();
+        self.timer
+            .set_deadline(now + self.period - Duration::from_nanos(behind as u64));
+        // Make sure that a waker is registered for the next tick, or that we're polled again if
+        // it's already due.
+        if self.timer.poll(cx).is_ready() {
+            cx.waker().wake_by_ref();
+        }
+        true
+    }
+}
@@ -0,0 +1,434 @@
+//! This module contains helpers for constructing [`Color`] values.
+//!
+//! All of the helpers produce plain [`Color`] values, so they can be used anywhere a color is
+//! accepted, such as the `color` property of [`Text`](crate::components::Text):
+//!
+//! ```
+//! # use iocraft::prelude::*;
+//! use iocraft::color;
+//!
+//! # fn my_element() -> impl Into<AnyElement<'static>> {
+//! element! {
+//!     Box(border_style: BorderStyle::Round, border_color: color::rgb(255, 165, 0)) {
+//!         Text(content: "Hello!", color: color::hex("#c0ffee").unwrap())
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::Color;
+use std::{
+    env,
+    fmt::{self, Display},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Creates a 24-bit color from its red, green, and blue components.
+pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+/// An error which can be returned when parsing a color.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string did not begin with a `#`.
+    MissingHash,
+    /// The string contained the wrong number of hex digits. The value is the number of digits
+    /// which were found.
+    InvalidLength(usize),
+    /// The string contained a character which is not a hex digit.
+    InvalidDigit(char),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHash => write!(f, "hex colors must begin with `#`"),
+            Self::InvalidLength(n) => write!(
+                f,
+                "hex colors must have 3 or 6 digits, but {} {} found",
+                n,
+                if *n == 1 { "was" } else { "were" }
+            ),
+            Self::InvalidDigit(c) => write!(f, "invalid hex digit: {:?}", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parses a hex color string of the form `#rrggbb` or the `#rgb` shorthand.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::{color, Color};
+/// assert_eq!(color::hex("#ff8000"), Ok(Color::Rgb { r: 255, g: 128, b: 0 }));
+/// assert_eq!(color::hex("#f80"), Ok(Color::Rgb { r: 255, g: 136, b: 0 }));
+/// assert!(color::hex("ff8000").is_err());
+/// ```
+pub fn hex(s: &str) -> Result<Color, ParseColorError> {
+    let digits = s.strip_prefix('#').ok_or(ParseColorError::MissingHash)?;
+    let values = digits
+        .chars()
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(ParseColorError::InvalidDigit(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match values[..] {
+        [r, g, b] => Ok(rgb(r * 17, g * 17, b * 17)),
+        [r1, r0, g1, g0, b1, b0] => Ok(rgb(r1 << 4 | r0, g1 << 4 | g0, b1 << 4 | b0)),
+        _ => Err(ParseColorError::InvalidLength(values.len())),
+    }
+}
+
+/// The table of colors which can be looked up by name via [`named`].
+///
+/// The first entries correspond to the standard terminal colors, which will be rendered using the
+/// terminal's own palette. The remaining entries are common web colors, which are rendered as
+/// 24-bit colors.
+pub const NAMED_COLORS: &[(&str, Color)] = &[
+    ("black", Color::Black),
+    ("dark_grey", Color::DarkGrey),
+    ("red", Color::Red),
+    ("dark_red", Color::DarkRed),
+    ("green", Color::Green),
+    ("dark_green", Color::DarkGreen),
+    ("yellow", Color::Yellow),
+    ("dark_yellow", Color::DarkYellow),
+    ("blue", Color::Blue),
+    ("dark_blue", Color::DarkBlue),
+    ("magenta", Color::Magenta),
+    ("dark_magenta", Color::DarkMagenta),
+    ("cyan", Color::Cyan),
+    ("dark_cyan", Color::DarkCyan),
+    ("white", Color::White),
+    ("grey", Color::Grey),
+    ("orange", rgb(255, 165, 0)),
+    ("purple", rgb(128, 0, 128)),
+    ("pink", rgb(255, 192, 203)),
+    ("brown", rgb(165, 42, 42)),
+    ("gold", rgb(255, 215, 0)),
+    ("navy", rgb(0, 0, 128)),
+    ("teal", rgb(0, 128, 128)),
+    ("olive", rgb(128, 128, 0)),
+    ("maroon", rgb(128, 0, 0)),
+    ("silver", rgb(192, 192, 192)),
+];
+
+/// Looks up a color in [`NAMED_COLORS`] by name.
+///
+/// The lookup is case-insensitive, treats `-` and spaces the same as `_`, and accepts "gray" as
+/// an alternative spelling of "grey".
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::{color, Color};
+/// assert_eq!(color::named("Dark-Gray"), Some(Color::DarkGrey));
+/// assert_eq!(color::named("not a color"), None);
+/// ```
+pub fn named(name: &str) -> Option<Color> {
+    let name = name
+        .to_ascii_lowercase()
+        .replace(['-', ' '], "_")
+        .replace("gray", "grey");
+    NAMED_COLORS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, color)| *color)
+}
+
+/// The approximate values of the 16 standard terminal colors, as used by xterm.
+const STANDARD_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The channel values of the 6x6x6 color cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the red, green, and blue components of a color. Colors from the terminal's palette are
+/// approximated using xterm's defaults. Returns `None` for [`Color::Reset`].
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb { r, g, b } => return Some((r, g, b)),
+        Color::AnsiValue(v) => v,
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15,
+    };
+    Some(match index {
+        0..=15 => STANDARD_COLORS[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let v = 8 + (index - 232) * 10;
+            (v, v, v)
+        }
+    })
+}
+
+/// Returns the color at position `t` along a gradient which passes through the given colors at
+/// evenly spaced intervals. `t` ranges from 0.0 to 1.0, and values outside of that range are
+/// clamped.
+///
+/// The colors are interpolated in RGB space, so the result is always a 24-bit color, with the
+/// exception that if either of the two colors being interpolated between is [`Color::Reset`], the
+/// nearer of the two is returned as-is. If no colors are given, [`Color::Reset`] is returned.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// use iocraft::color;
+///
+/// let red_to_green = [color::rgb(255, 0, 0), color::rgb(0, 255, 0)];
+/// assert_eq!(color::gradient(&red_to_green, 0.0), color::rgb(255, 0, 0));
+/// assert_eq!(color::gradient(&red_to_green, 0.5), color::rgb(128, 128, 0));
+///
+/// # fn my_element(progress: f32) -> impl Into<AnyElement<'static>> {
+/// # let red_to_green = [color::rgb(255, 0, 0), color::rgb(0, 255, 0)];
+/// element! {
+///     ProgressBar(value: progress, color: color::gradient(&red_to_green, progress))
+/// }
+/// # }
+/// ```
+pub fn gradient(stops: &[Color], t: f32) -> Color {
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+    let (first, last) = match stops {
+        [] => return Color::Reset,
+        [color] => return *color,
+        [first, .., last] => (*first, *last),
+    };
+    if t >= 1.0 {
+        return last;
+    } else if t <= 0.0 {
+        return first;
+    }
+    let position = t * (stops.len() - 1) as f32;
+    let index = position.floor() as usize;
+    let t = position - index as f32;
+    let (from, to) = (stops[index], stops[index + 1]);
+    match (to_rgb(from), to_rgb(to)) {
+        (Some(from), Some(to)) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// Converts a 24-bit color to the nearest color in the 256-color palette, for terminals that don't
+/// support 24-bit color. Other colors are returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::{color, Color};
+/// assert_eq!(color::to_ansi256(color::rgb(255, 0, 0)), Color::AnsiValue(196));
+/// assert_eq!(color::to_ansi256(color::rgb(128, 128, 128)), Color::AnsiValue(244));
+/// assert_eq!(color::to_ansi256(Color::Red), Color::Red);
+/// ```
+pub fn to_ansi256(color: Color) -> Color {
+    let Color::Rgb { r, g, b } = color else {
+        return color;
+    };
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+
+    let nearest_level = |v: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap() as u8
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_index = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+
+    let index = [cube_index, grey_index]
+        .into_iter()
+        .min_by_key(|&i| distance(to_rgb(Color::AnsiValue(i)).unwrap()))
+        .unwrap();
+    Color::AnsiValue(index)
+}
+
+// The value given to `set_truecolor_override`: 0 for none, 1 for false, and 2 for true.
+static TRUECOLOR_OVERRIDE: AtomicU8 = AtomicU8::new(0);
+
+/// Returns whether the terminal appears to support 24-bit color, based on the `COLORTERM`
+/// environment variable, unless this has been overridden via [`set_truecolor_override`].
+///
+/// When this returns `false`, iocraft automatically converts 24-bit colors to the 256-color
+/// palette via [`to_ansi256`] before writing them to the terminal.
+pub fn supports_truecolor() -> bool {
+    match TRUECOLOR_OVERRIDE.load(Ordering::Relaxed) {
+        1 => false,
+        2 => true,
+        _ => {
+            matches!(
+                env::var("COLORTERM").as_deref(),
+                Ok("truecolor") | Ok("24bit")
+            ) || (cfg!(windows) && env::var_os("WT_SESSION").is_some())
+        }
+    }
+}
+
+/// Overrides whether the terminal is considered to support 24-bit color, for terminals which
+/// are detected wrongly by [`supports_truecolor`]. With `Some(true)`, 24-bit colors are always
+/// written as-is, and with `Some(false)`, they're always converted to the 256-color palette.
+/// `None` restores the detection.
+///
+/// # Example
+///
+/// ```
+/// use iocraft::color;
+///
+/// color::set_truecolor_override(Some(true));
+/// assert!(color::supports_truecolor());
+/// ```
+pub fn set_truecolor_override(supported: Option<bool>) {
+    let value = match supported {
+        None => 0,
+        Some(false) => 1,
+        Some(true) => 2,
+    };
+    TRUECOLOR_OVERRIDE.store(value, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(rgb(1, 2, 3), Color::Rgb { r: 1, g: 2, b: 3 });
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(hex("#000000"), Ok(rgb(0, 0, 0)));
+        assert_eq!(hex("#FFffFF"), Ok(rgb(255, 255, 255)));
+        assert_eq!(hex("#123abc"), Ok(rgb(0x12, 0x3a, 0xbc)));
+        assert_eq!(hex("#fff"), Ok(rgb(255, 255, 255)));
+        assert_eq!(hex("#1a2"), Ok(rgb(0x11, 0xaa, 0x22)));
+
+        assert_eq!(hex("123abc"), Err(ParseColorError::MissingHash));
+        assert_eq!(hex(""), Err(ParseColorError::MissingHash));
+        assert_eq!(hex("#"), Err(ParseColorError::InvalidLength(0)));
+        assert_eq!(hex("#1234"), Err(ParseColorError::InvalidLength(4)));
+        assert_eq!(hex("#12345g"), Err(ParseColorError::InvalidDigit('g')));
+        assert_eq!(hex("#+12"), Err(ParseColorError::InvalidDigit('+')));
+
+        assert_eq!(
+            hex("#1").unwrap_err().to_string(),
+            "hex colors must have 3 or 6 digits, but 1 was found"
+        );
+    }
+
+    #[test]
+    fn test_named() {
+        assert_eq!(named("red"), Some(Color::Red));
+        assert_eq!(named("DARK_GREY"), Some(Color::DarkGrey));
+        assert_eq!(named("dark gray"), Some(Color::DarkGrey));
+        assert_eq!(named("orange"), Some(rgb(255, 165, 0)));
+        assert_eq!(named("reddish"), None);
+    }
+
+    #[test]
+    fn test_to_rgb() {
+        assert_eq!(to_rgb(Color::Reset), None);
+        assert_eq!(to_rgb(rgb(1, 2, 3)), Some((1, 2, 3)));
+        assert_eq!(to_rgb(Color::Red), Some((255, 0, 0)));
+        assert_eq!(to_rgb(Color::AnsiValue(9)), Some((255, 0, 0)));
+        assert_eq!(to_rgb(Color::AnsiValue(16)), Some((0, 0, 0)));
+        assert_eq!(to_rgb(Color::AnsiValue(231)), Some((255, 255, 255)));
+        assert_eq!(to_rgb(Color::AnsiValue(67)), Some((95, 135, 175)));
+        assert_eq!(to_rgb(Color::AnsiValue(232)), Some((8, 8, 8)));
+        assert_eq!(to_rgb(Color::AnsiValue(255)), Some((238, 238, 238)));
+    }
+
+    #[test]
+    fn test_gradient() {
+        assert_eq!(gradient(&[], 0.5), Color::Reset);
+        assert_eq!(gradient(&[Color::Red], 0.5), Color::Red);
+
+        let stops = [rgb(0, 0, 0), rgb(200, 100, 0), rgb(200, 200, 200)];
+        assert_eq!(gradient(&stops, -1.0), rgb(0, 0, 0));
+        assert_eq!(gradient(&stops, f32::NAN), rgb(0, 0, 0));
+        assert_eq!(gradient(&stops, 0.25), rgb(100, 50, 0));
+        assert_eq!(gradient(&stops, 0.5), rgb(200, 100, 0));
+        assert_eq!(gradient(&stops, 0.75), rgb(200, 150, 100));
+        assert_eq!(gradient(&stops, 2.0), rgb(200, 200, 200));
+
+        assert_eq!(
+            gradient(&[Color::Black, Color::White], 0.5),
+            rgb(128, 128, 128)
+        );
+        assert_eq!(gradient(&[Color::Reset, Color::White], 0.25), Color::Reset);
+        assert_eq!(gradient(&[Color::Reset, Color::White], 0.75), Color::White);
+    }
+
+    #[test]
+    fn test_to_ansi256() {
+        assert_eq!(to_ansi256(rgb(0, 0, 0)), Color::AnsiValue(16));
+        assert_eq!(to_ansi256(rgb(255, 255, 255)), Color::AnsiValue(231));
+        assert_eq!(to_ansi256(rgb(255, 0, 0)), Color::AnsiValue(196));
+        assert_eq!(to_ansi256(rgb(95, 135, 175)), Color::AnsiValue(67));
+        assert_eq!(to_ansi256(rgb(100, 130, 170)), Color::AnsiValue(67));
+        assert_eq!(to_ansi256(rgb(128, 128, 128)), Color::AnsiValue(244));
+        assert_eq!(to_ansi256(rgb(18, 18, 18)), Color::AnsiValue(233));
+        assert_eq!(to_ansi256(Color::DarkBlue), Color::DarkBlue);
+        assert_eq!(to_ansi256(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn test_truecolor_override() {
+        set_truecolor_override(Some(true));
+        assert!(supports_truecolor());
+        set_truecolor_override(Some(false));
+        assert!(!supports_truecolor());
+        set_truecolor_override(None);
+    }
+}
@@ -131,10 +131,16 @@ impl<C: Any + Component> AnyComponent for C {
 pub(crate) struct InstantiatedComponent {
     node_id: NodeId,
     component: Box<dyn AnyComponent>,
+    // The children are declared before the hooks so that they're dropped first. Hooks such as
+    // `use_resource` rely on this to outlive the children when the component is unmounted.
     children: Components,
     helper: Box<dyn ComponentHelperExt>,
     hooks: Vec<Box<dyn AnyHook>>,
     first_update: bool,
+    // Whether the component or its hooks have signaled a change since the last update.
+    has_changes: bool,
+    // Whether any of the component's descendants have signaled a change since the last update.
+    children_have_changes: bool,
 }
 
 impl InstantiatedComponent {
@@ -146,6 +152,8 @@ impl InstantiatedComponent {
             helper,
             hooks: Default::default(),
             first_update: true,
+            has_changes: false,
+            children_have_changes: false,
         }
     }
 
@@ -170,20 +178,23 @@ impl InstantiatedComponent {
             component_context_stack,
         );
         self.hooks.pre_component_update(&mut updater);
+        let has_changes = self.has_changes || self.children_have_changes || updater.has_changes();
         self.helper.update_component(
             &mut self.component,
             props,
-            Hooks::new(&mut self.hooks, self.first_update),
+            Hooks::new(&mut self.hooks, self.first_update, has_changes),
             &mut updater,
         );
         self.hooks.post_component_update(&mut updater);
         self.first_update = false;
+        self.has_changes = false;
+        self.children_have_changes = false;
     }
 
     pub fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
         self.hooks.pre_component_draw(drawer);
         self.component.draw(drawer);
-        self.children.draw(drawer);
+        drawer.draw_children(&mut self.children);
         self.hooks.post_component_draw(drawer);
     }
 
@@ -196,6 +207,12 @@ impl InstantiatedComponent {
         let component_status = Pin::new(&mut *self.component).poll_change(cx);
         let children_status = Pin::new(&mut self.children).poll_change(cx);
         let hooks_status = Pin::new(&mut self.hooks).poll_change(cx);
+        if component_status.is_ready() || hooks_status.is_ready() {
+            self.has_changes = true;
+        }
+        if children_status.is_ready() {
+            self.children_have_changes = true;
+        }
         if component_status.is_ready() || children_status.is_ready() || hooks_status.is_ready() {
             Poll::Ready(())
         } else {
@@ -207,11 +224,19 @@ impl InstantiatedComponent {
 #[derive(Default)]
 pub(crate) struct Components {
     pub components: HashMap<ElementKey, InstantiatedComponent>,
+    // The keys of the components, in the order they were declared.
+    pub order: Vec<ElementKey>,
 }
 
 impl Components {
+    /// Draws the components in the order they were declared, so that where siblings overlap,
+    /// later ones are drawn on top of earlier ones.
     pub fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
-        for (_, component) in self.components.iter_mut() {
+        for key in &self.order {
+            let component = self
+                .components
+                .get_mut(key)
+                .expect("every key in the order should have a component");
             drawer.for_child_node(component.node_id, |drawer| {
                 component.draw(drawer);
             });
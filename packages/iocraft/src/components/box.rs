@@ -1,6 +1,6 @@
 use crate::{
-    AnyElement, CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Edges, Hooks,
-    Props,
+    hooks::LayoutId, AnyElement, CanvasTextStyle, Color, Component, ComponentDrawer,
+    ComponentUpdater, Edges, GridTrack, Hooks, Props,
 };
 use iocraft_macros::with_layout_style_props;
 use taffy::{LengthPercentage, Rect};
@@ -153,6 +153,26 @@ pub struct BoxProps<'a> {
 
     /// The color of the background.
     pub background_color: Option<Color>,
+
+    /// Defines the sizes of the columns of the grid, when `display` is
+    /// [`Display::Grid`](crate::Display::Grid). Items are placed within them via their
+    /// `grid_column` properties, or automatically.
+    ///
+    /// See [the MDN documentation for grid-template-columns](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns).
+    pub grid_template_columns: Vec<GridTrack>,
+
+    /// Defines the sizes of the rows of the grid, when `display` is
+    /// [`Display::Grid`](crate::Display::Grid). Items are placed within them via their
+    /// `grid_row` properties, or automatically.
+    ///
+    /// See [the MDN documentation for grid-template-rows](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-rows).
+    pub grid_template_rows: Vec<GridTrack>,
+
+    /// An identifier which other components can use to look up the box's position and size via
+    /// [`use_layout_rect`](crate::hooks::UseLayoutRect::use_layout_rect). Unlike `key`, this has
+    /// no effect on how elements are matched up between renders, and it should be unique within
+    /// the whole tree.
+    pub id: Option<String>,
 }
 
 /// `Box` is your most fundamental building block for laying out and styling components.
@@ -175,6 +195,7 @@ pub struct Box {
     border_text_style: CanvasTextStyle,
     border_edges: Edges,
     background_color: Option<Color>,
+    layout_id: Option<LayoutId>,
 }
 
 impl Component for Box {
@@ -190,6 +211,17 @@ impl Component for Box {
         _hooks: Hooks,
         updater: &mut ComponentUpdater,
     ) {
+        match (props.id.as_deref(), &mut self.layout_id) {
+            (Some(id), Some(layout_id)) => layout_id.set_id(id),
+            (Some(id), None) => {
+                let manager = &updater
+                    .component_context_stack()
+                    .system()
+                    .layout_rect_manager;
+                self.layout_id = Some(LayoutId::new(manager, id));
+            }
+            (None, _) => self.layout_id = None,
+        }
         self.border_style = props.border_style;
         self.border_text_style = CanvasTextStyle {
             color: props.border_color,
@@ -198,6 +230,8 @@ impl Component for Box {
         self.border_edges = props.border_edges.unwrap_or(Edges::all());
         self.background_color = props.background_color;
         let mut style: taffy::style::Style = props.layout_style().into();
+        style.grid_template_columns = GridTrack::to_taffy(&props.grid_template_columns);
+        style.grid_template_rows = GridTrack::to_taffy(&props.grid_template_rows);
         style.border = if self.border_style.is_none() {
             Rect::zero()
         } else {
@@ -224,11 +258,22 @@ impl Component for Box {
                 }),
             }
         };
+        let border = style.border;
+        props
+            .layout_style()
+            .box_sizing
+            .unwrap_or_default()
+            .adjust_sizes(&mut style, border);
         updater.set_layout_style(style);
+        updater.set_intrinsic_sizes(props.layout_style().intrinsic_sizes());
         updater.update_children(props.children.iter_mut(), None);
     }
 
     fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        if let Some(layout_id) = &self.layout_id {
+            layout_id.record(drawer);
+        }
+
         let layout = drawer.layout();
 
         let mut canvas = drawer.canvas();
@@ -389,6 +434,49 @@ mod tests {
             "foo         bar\n"
         );
 
+        assert_eq!(
+            element! {
+                Box(width: 20, flex_direction: FlexDirection::Column) {
+                    Box(padding_left: 50pct) {
+                        Text(content: "foo")
+                    }
+                    Box(margin_left: 10pct, padding: 5pct) {
+                        Text(content: "bar")
+                    }
+                }
+            }
+            .to_string(),
+            "          foo\n\n   bar\n\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(width: 20) {
+                    Box(width: 4, margin: Auto) {
+                        Text(content: "foo")
+                    }
+                }
+            }
+            .to_string(),
+            "        foo\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(height: 3, align_items: AlignItems::FlexStart) {
+                    Text(content: "foo")
+                    Box(align_self: AlignSelf::FlexEnd) {
+                        Text(content: "bar")
+                    }
+                    Box(align_self: AlignSelf::Center) {
+                        Text(content: "baz")
+                    }
+                }
+            }
+            .to_string(),
+            "foo\n      baz\n   bar\n"
+        );
+
         assert_eq!(
             element! {
                 Box(width: 20, border_style: BorderStyle::Single) {
@@ -465,4 +553,378 @@ mod tests {
             "},
         );
     }
+
+    #[derive(Default, Props)]
+    struct CounterProps {
+        label: String,
+    }
+
+    #[component]
+    fn Counter(mut hooks: Hooks, props: &CounterProps) -> impl Into<AnyElement<'static>> {
+        let count = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('+'),
+                ..
+            }) = event
+            {
+                count.set(count + 1);
+            }
+        });
+        element! {
+            Text(content: format!("{}: {}", props.label, count))
+        }
+    }
+
+    #[component]
+    fn Toggle(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let visible = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Tab, ..
+            }) = event
+            {
+                visible.set(!visible.get());
+            }
+        });
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: "above")
+                Box(
+                    display: if visible.get() { Display::Flex } else { Display::None },
+                    border_style: BorderStyle::Single,
+                ) {
+                    Counter(label: "hidden")
+                }
+                Counter(label: "shown")
+                Text(content: "below")
+            }
+        }
+    }
+
+    #[test]
+    fn test_box_display_none() {
+        let mut element = element!(Toggle);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Char('+'));
+        assert_eq!(
+            renderer.frame(),
+            indoc! {"
+                above
+                ┌─────────┐
+                │hidden: 1│
+                └─────────┘
+                shown: 1
+                below
+            "},
+        );
+
+        // While hidden, the box takes up no space and isn't drawn, but its children remain
+        // mounted and keep responding to events.
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(renderer.frame(), "above\nshown: 1\nbelow\n");
+        renderer.send_key(KeyCode::Char('+'));
+        assert_eq!(renderer.frame(), "above\nshown: 2\nbelow\n");
+
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(
+            renderer.frame(),
+            indoc! {"
+                above
+                ┌─────────┐
+                │hidden: 2│
+                └─────────┘
+                shown: 2
+                below
+            "},
+        );
+    }
+
+    #[test]
+    fn test_box_negative_margin() {
+        assert_eq!(
+            element! {
+                Box {
+                    Box(border_style: BorderStyle::Single) {
+                        Text(content: "abc")
+                    }
+                    // This is pulled up above the canvas, so it's clipped.
+                    Box(margin_left: -1, margin_top: -1) {
+                        Text(content: "1")
+                    }
+                }
+            }
+            .to_string(),
+            indoc! {"
+                ┌───┐
+                │abc│
+                └───┘
+            "},
+        );
+
+        // Offsets accumulate through the tree, so the text is pulled back into view.
+        assert_eq!(
+            element! {
+                Box(padding_left: 3) {
+                    Box(width: 6) {
+                        Box(margin_left: -2) {
+                            Text(content: "X")
+                        }
+                    }
+                }
+            }
+            .to_string(),
+            " X\n",
+        );
+    }
+
+    #[component]
+    fn Grid(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let rects = [
+            hooks.use_layout_rect("a"),
+            hooks.use_layout_rect("b"),
+            hooks.use_layout_rect("c"),
+            hooks.use_layout_rect("d"),
+        ];
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Box(
+                    display: Display::Grid,
+                    width: 12,
+                    grid_template_columns: vec![GridTrack::length(4), GridTrack::fr(1.0)],
+                    grid_template_rows: vec![GridTrack::repeat(2, [1])],
+                ) {
+                    // The last two cells are placed explicitly, in reverse order.
+                    Box(id: "a".to_string()) {
+                        Text(content: "a")
+                    }
+                    Box(id: "b".to_string()) {
+                        Text(content: "b")
+                    }
+                    Box(id: "d".to_string(), grid_row: 2, grid_column: 2) {
+                        Text(content: "d")
+                    }
+                    Box(id: "c".to_string(), grid_row: 2, grid_column: GridPlacement::lines(1, 2)) {
+                        Text(content: "c")
+                    }
+                }
+                #(rects.map(|rect| element! {
+                    Text(content: format!("{:?}", rect.map(|r| (r.x, r.y, r.width, r.height))))
+                }))
+            }
+        }
+    }
+
+    #[test]
+    fn test_box_grid() {
+        let mut element = element!(Grid);
+        let renderer = TestRenderer::new(&mut element);
+        assert_eq!(
+            renderer.frame(),
+            indoc! {"
+                a   b
+                c   d
+                Some((0, 0, 4, 1))
+                Some((4, 0, 8, 1))
+                Some((0, 1, 4, 1))
+                Some((4, 1, 8, 1))
+            "},
+        );
+    }
+
+    #[test]
+    fn test_box_grid_justify_items() {
+        assert_eq!(
+            element! {
+                Box(
+                    display: Display::Grid,
+                    grid_template_columns: vec![GridTrack::length(4); 2],
+                    justify_items: JustifyItems::End,
+                ) {
+                    Text(content: "a")
+                    Text(content: "b")
+                }
+            }
+            .to_string(),
+            "   a   b\n",
+        );
+    }
+
+    #[test]
+    fn test_box_sizing() {
+        let padded = |box_sizing: BoxSizing| {
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    Box(
+                        width: 10,
+                        height: 4,
+                        padding_left: 2,
+                        border_style: BorderStyle::Single,
+                        box_sizing,
+                    ) {
+                        Text(content: "abcdefghij")
+                    }
+                }
+            }
+            .to_string()
+        };
+
+        // The padding and border take up part of the width and height.
+        assert_eq!(
+            padded(BoxSizing::BorderBox),
+            indoc! {"
+                ┌────────┐
+                │  abcdef│
+                │  ghij  │
+                └────────┘
+            "},
+        );
+
+        // The padding and border are added around the width and height.
+        assert_eq!(
+            padded(BoxSizing::ContentBox),
+            indoc! {"
+                ┌────────────┐
+                │  abcdefghij│
+                │            │
+                │            │
+                │            │
+                └────────────┘
+            "},
+        );
+    }
+
+    #[test]
+    fn test_box_intrinsic_sizes() {
+        let column = |width: Size| {
+            element! {
+                Box(width: 20, flex_direction: FlexDirection::Column) {
+                    Box(width, border_style: BorderStyle::Single, flex_direction: FlexDirection::Column) {
+                        Text(content: "a")
+                        Text(content: "longer text", overflow_wrap: OverflowWrap::Normal)
+                    }
+                }
+            }
+            .to_string()
+        };
+
+        // Without an intrinsic size, the box stretches to fill the column.
+        assert_eq!(
+            column(Size::Auto),
+            indoc! {"
+                ┌──────────────────┐
+                │a                 │
+                │longer text       │
+                └──────────────────┘
+            "},
+        );
+
+        assert_eq!(
+            column(Size::MaxContent),
+            indoc! {"
+                ┌───────────┐
+                │a          │
+                │longer text│
+                └───────────┘
+            "},
+        );
+
+        assert_eq!(
+            column(Size::MinContent),
+            indoc! {"
+                ┌──────┐
+                │a     │
+                │longer│
+                │text  │
+                └──────┘
+            "},
+        );
+
+        // The content fits, so the box takes its maximum content size.
+        assert_eq!(column(Size::FitContent), column(Size::MaxContent));
+
+        // The content doesn't fit, so the box takes the available space.
+        assert_eq!(
+            element! {
+                Box(width: 10, flex_direction: FlexDirection::Column) {
+                    Box(width: FitContent, border_style: BorderStyle::Single) {
+                        Text(content: "longer text")
+                    }
+                }
+            }
+            .to_string(),
+            indoc! {"
+                ┌────────┐
+                │longer  │
+                │text    │
+                └────────┘
+            "},
+        );
+
+        // Nested intrinsic sizes are resolved from the inside out.
+        assert_eq!(
+            element! {
+                Box(width: 20, flex_direction: FlexDirection::Column) {
+                    Box(width: MaxContent, border_style: BorderStyle::Single) {
+                        Box(width: MinContent) {
+                            Text(content: "some text", overflow_wrap: OverflowWrap::Normal)
+                        }
+                    }
+                }
+            }
+            .to_string(),
+            indoc! {"
+                ┌────┐
+                │some│
+                │text│
+                └────┘
+            "},
+        );
+
+        // A flex basis is resolved along the parent's main axis.
+        assert_eq!(
+            element! {
+                Box(width: 20) {
+                    Box(flex_basis: MinContent, flex_grow: 0.0) {
+                        Text(content: "ab cd", overflow_wrap: OverflowWrap::Normal)
+                    }
+                    Text(content: "|")
+                }
+            }
+            .to_string(),
+            "ab|\ncd\n"
+        );
+    }
+
+    #[component]
+    fn Growing(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let content = hooks.use_state(|| "a".to_string());
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            }) = event
+            {
+                content.set(format!("{}{}", content, c));
+            }
+        });
+        element!(Text(content: content.read().clone()))
+    }
+
+    #[test]
+    fn test_box_intrinsic_size_updates() {
+        let mut element = element! {
+            Box(width: 20, flex_direction: FlexDirection::Column) {
+                Box(width: MaxContent, border_style: BorderStyle::Single) {
+                    Growing
+                }
+            }
+        };
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "┌─┐\n│a│\n└─┘\n");
+
+        // The box doesn't re-render when its content grows, but it's measured again regardless.
+        renderer.send_key(KeyCode::Char('b'));
+        assert_eq!(renderer.frame(), "┌──┐\n│ab│\n└──┘\n");
+    }
 }
@@ -0,0 +1,254 @@
+use crate::{
+    hooks::{UseState, UseTerminalEvents},
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Handler, Hooks, KeyCode,
+    KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind, Props, TerminalEvent, Weight,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use taffy::Size;
+use unicode_width::UnicodeWidthStr;
+
+/// The props which can be passed to the [`Button`] component.
+#[derive(Default, Props)]
+pub struct ButtonProps {
+    /// The text to display on the button.
+    pub label: String,
+
+    /// The handler to invoke when the button is pressed.
+    pub on_press: Handler<'static, ()>,
+
+    /// True if the button has focus and should respond to the keyboard.
+    pub is_focused: bool,
+
+    /// The color to make the label.
+    pub color: Option<Color>,
+
+    /// The background color of the button.
+    pub background_color: Option<Color>,
+
+    /// The color to make the label while the button has focus. Defaults to `color`.
+    pub focused_color: Option<Color>,
+
+    /// The background color of the button while it has focus. Defaults to `background_color`.
+    pub focused_background_color: Option<Color>,
+
+    /// The background color of the button while the mouse button is held down on it. Defaults to
+    /// the focused background color.
+    pub pressed_background_color: Option<Color>,
+}
+
+/// `Button` is a component that invokes a handler when it's pressed.
+///
+/// The button displays its label with a cell of padding on each side. While it has focus, the
+/// label is drawn in bold, and pressing Enter or Space presses the button. In fullscreen mode,
+/// the button can also be clicked with the left mouse button, in which case it's pressed when the
+/// mouse button is released over it.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # #[component]
+/// # fn Counter(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+/// let count = hooks.use_state(|| 0);
+///
+/// element! {
+///     Box(flex_direction: FlexDirection::Column) {
+///         Text(content: format!("count: {}", count))
+///         Button(
+///             label: "Increment",
+///             is_focused: true,
+///             background_color: Color::DarkGrey,
+///             focused_background_color: Color::Blue,
+///             on_press: move |_| count.set(count.get() + 1),
+///         )
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Button {
+    label: String,
+    style: CanvasTextStyle,
+    background_color: Option<Color>,
+    handler: Arc<Mutex<Handler<'static, ()>>>,
+    is_focused: Arc<AtomicBool>,
+}
+
+impl Component for Button {
+    type Props<'a> = ButtonProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        *self.handler.lock().unwrap() = props.on_press.take();
+        self.is_focused.store(props.is_focused, Ordering::SeqCst);
+
+        let is_pressed = hooks.use_state(|| false);
+        hooks.use_local_terminal_events({
+            let handler = self.handler.clone();
+            let is_focused = self.is_focused.clone();
+            move |event| match event {
+                TerminalEvent::Key(KeyEvent {
+                    code: KeyCode::Enter | KeyCode::Char(' '),
+                    kind: KeyEventKind::Press,
+                    ..
+                }) if is_focused.load(Ordering::SeqCst) => {
+                    handler.lock().unwrap().invoke(());
+                }
+                TerminalEvent::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    ..
+                }) => {
+                    is_pressed.set(true);
+                }
+                TerminalEvent::Mouse(MouseEvent {
+                    kind: MouseEventKind::Up(MouseButton::Left),
+                    ..
+                }) if is_pressed.get() => {
+                    is_pressed.set(false);
+                    handler.lock().unwrap().invoke(());
+                }
+                _ => {}
+            }
+        });
+        // If the mouse button is released elsewhere, the press is cancelled.
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::Up(_),
+                ..
+            }) = event
+            {
+                if is_pressed.get() {
+                    is_pressed.set(false);
+                }
+            }
+        });
+
+        let focused_background_color = props.focused_background_color.or(props.background_color);
+        self.label = props.label.clone();
+        self.background_color = if is_pressed.get() {
+            props.pressed_background_color.or(focused_background_color)
+        } else if props.is_focused {
+            focused_background_color
+        } else {
+            props.background_color
+        };
+        self.style = CanvasTextStyle {
+            color: if props.is_focused {
+                props.focused_color.or(props.color)
+            } else {
+                props.color
+            },
+            weight: if props.is_focused {
+                Weight::Bold
+            } else {
+                Weight::Normal
+            },
+            ..Default::default()
+        };
+
+        let width = self.label.width() + 2;
+        updater.set_measure_func(Box::new(move |_, _, _| Size {
+            width: width as _,
+            height: 1.0,
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let size = drawer.size();
+        let mut canvas = drawer.canvas();
+        if let Some(color) = self.background_color {
+            canvas.set_background_color(0, 0, size.width as _, size.height as _, color);
+        }
+        canvas.set_text(1, 0, &self.label, self.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Default, Props)]
+    struct CounterProps {
+        is_focused: bool,
+    }
+
+    #[component]
+    fn Counter(mut hooks: Hooks, props: &CounterProps) -> impl Into<AnyElement<'static>> {
+        let count = hooks.use_state(|| 0);
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: format!("count: {}", count))
+                Button(
+                    label: "+1",
+                    is_focused: props.is_focused,
+                    on_press: move |_| count.set(count.get() + 1),
+                )
+            }
+        }
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> TerminalEvent {
+        TerminalEvent::Mouse(MouseEvent {
+            column,
+            row,
+            kind,
+            modifiers: KeyModifiers::empty(),
+        })
+    }
+
+    #[test]
+    fn test_button() {
+        assert_eq!(element!(Button(label: "OK")).to_string(), " OK\n");
+    }
+
+    #[test]
+    fn test_button_keyboard() {
+        let mut element = element!(Counter(is_focused: true));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "count: 0\n +1\n");
+        renderer.send_key(KeyCode::Enter);
+        renderer.send_key(KeyCode::Char(' '));
+        assert_eq!(renderer.frame(), "count: 2\n +1\n");
+        renderer.send_key(KeyCode::Char('x'));
+        assert_eq!(renderer.frame(), "count: 2\n +1\n");
+
+        let mut element = element!(Counter);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "count: 0\n +1\n");
+    }
+
+    #[test]
+    fn test_button_mouse() {
+        let mut element = element!(Counter);
+        let mut renderer = TestRenderer::new(&mut element);
+
+        // A click on the button presses it.
+        renderer.send(mouse(MouseEventKind::Down(MouseButton::Left), 1, 1));
+        assert_eq!(renderer.frame(), "count: 0\n +1\n");
+        renderer.send(mouse(MouseEventKind::Up(MouseButton::Left), 2, 1));
+        assert_eq!(renderer.frame(), "count: 1\n +1\n");
+
+        // Releasing the mouse elsewhere cancels the press.
+        renderer.send(mouse(MouseEventKind::Down(MouseButton::Left), 1, 1));
+        renderer.send(mouse(MouseEventKind::Up(MouseButton::Left), 1, 0));
+        renderer.send(mouse(MouseEventKind::Up(MouseButton::Left), 1, 1));
+        assert_eq!(renderer.frame(), "count: 1\n +1\n");
+
+        // Clicks outside of the button are ignored.
+        renderer.send(mouse(MouseEventKind::Down(MouseButton::Left), 10, 1));
+        renderer.send(mouse(MouseEventKind::Up(MouseButton::Left), 10, 1));
+        assert_eq!(renderer.frame(), "count: 1\n +1\n");
+    }
+}
@@ -75,4 +75,54 @@ mod tests {
             "foo\n"
         );
     }
+
+    #[derive(Default, Props)]
+    struct ContextProbeProps {
+        mutable: bool,
+    }
+
+    #[component]
+    fn ContextProbe(hooks: Hooks, props: &ContextProbeProps) -> impl Into<AnyElement<'static>> {
+        let content = if props.mutable {
+            hooks
+                .try_use_context_mut::<StringContext>()
+                .map(|s| s.0.clone())
+        } else {
+            hooks
+                .try_use_context::<StringContext>()
+                .map(|s| s.0.clone())
+        };
+        element! {
+            Text(content: content.unwrap_or_else(|| "none".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_context_provider_shadowing() {
+        let mut outer = StringContext("outer".into());
+        let inner = StringContext("inner".into());
+        assert_eq!(
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    ContextProbe
+                    ContextProvider(value: Context::from_mut(&mut outer)) {
+                        Box(flex_direction: FlexDirection::Column) {
+                            ContextProbe
+                            ContextProbe(mutable: true)
+                            ContextProvider(value: Context::from_ref(&inner)) {
+                                Box(flex_direction: FlexDirection::Column) {
+                                    ContextProbe
+                                    // The inner context can't be borrowed mutably, but it still
+                                    // shadows the outer one.
+                                    ContextProbe(mutable: true)
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            .to_string(),
+            "none\nouter\nouter\ninner\nnone\n"
+        );
+    }
 }
@@ -0,0 +1,378 @@
+use crate::{
+    components::{Text, TextProps},
+    terminal::install_panic_hook,
+    AnyElement, Component, ComponentDrawer, ComponentUpdater, Element, ElementKey, Handler, Hook,
+    Hooks, Props, SemanticColor,
+};
+use iocraft_macros::with_layout_style_props;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe, Location},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An error caught by an [`ErrorBoundary`], which describes a panic in one of its descendants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentError {
+    message: String,
+    location: Option<String>,
+}
+
+impl ComponentError {
+    fn from_panic(payload: Box<dyn Any + Send>, location: Option<String>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "a component panicked".to_string()
+        };
+        Self { message, location }
+    }
+
+    /// Returns the panic's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the source location of the panic, in the form `file:line:column`, if it's known.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+}
+
+impl Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ComponentError {}
+
+thread_local! {
+    // The number of calls to `catch_panic` which are in progress on this thread.
+    static CATCHING_PANICS: Cell<usize> = const { Cell::new(0) };
+    // The location of the last panic intercepted by the panic hook on this thread.
+    static CAUGHT_PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Called by the panic hook before anything else. If the panic is going to be caught by
+/// [`catch_panic`], this records its location and returns `true`, in which case the hook should
+/// leave the terminal as it is, and only pass the panic on to an application's own hook.
+pub(crate) fn intercept_caught_panic(location: Option<&Location<'_>>) -> bool {
+    if CATCHING_PANICS.with(|catching| catching.get()) == 0 {
+        return false;
+    }
+    CAUGHT_PANIC_LOCATION.with(|caught| {
+        *caught.borrow_mut() = location.map(|location| location.to_string());
+    });
+    true
+}
+
+/// Invokes the given function, returning an error if it panics.
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, ComponentError> {
+    install_panic_hook();
+    CATCHING_PANICS.with(|catching| catching.set(catching.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|catching| catching.set(catching.get() - 1));
+    result.map_err(|payload| {
+        let location = CAUGHT_PANIC_LOCATION.with(|caught| caught.borrow_mut().take());
+        ComponentError::from_panic(payload, location)
+    })
+}
+
+/// A function which renders the fallback for an [`ErrorBoundary`], given the error that it caught.
+///
+/// Like [`Handler`], this can be created from any closure of the right type.
+#[derive(Default)]
+pub enum ErrorFallback<'a> {
+    /// No fallback is set, so the error is rendered as text.
+    #[default]
+    None,
+    /// A function fallback.
+    Function(Box<dyn Fn(&ComponentError) -> AnyElement<'static> + Send + 'a>),
+}
+
+impl<'a, F> From<F> for ErrorFallback<'a>
+where
+    F: Fn(&ComponentError) -> AnyElement<'static> + Send + 'a,
+{
+    fn from(f: F) -> Self {
+        Self::Function(Box::new(f))
+    }
+}
+
+impl<'a> ErrorFallback<'a> {
+    /// Returns `true` if the fallback is not set.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    fn render(&self, error: &ComponentError) -> AnyElement<'static> {
+        match self {
+            Self::Function(f) => f(error),
+            Self::None => Element::<Text> {
+                key: ElementKey::new("fallback"),
+                props: TextProps {
+                    content: format!("error: {}", error),
+                    semantic_color: Some(SemanticColor::Danger),
+                    ..Default::default()
+                },
+            }
+            .into(),
+        }
+    }
+}
+
+/// The props which can be passed to the [`ErrorBoundary`] component.
+#[with_layout_style_props]
+#[derive(Default, Props)]
+pub struct ErrorBoundaryProps<'a> {
+    /// The elements to render until one of them panics.
+    pub children: Vec<AnyElement<'a>>,
+
+    /// The function which renders the elements to show in place of the children once one of them
+    /// panics. If not set, the error message is shown as text in the theme's danger color.
+    pub fallback: ErrorFallback<'a>,
+
+    /// A handler which is invoked with the error when a panic is caught, e.g. to log it.
+    pub on_error: Handler<'static, ComponentError>,
+}
+
+/// `ErrorBoundary` is a component which catches panics in its descendants and renders a fallback
+/// in their place, so that the rest of the application keeps running.
+///
+/// Panics are caught while the descendants are updated or drawn. Once one is caught, the children
+/// are unmounted and the fallback is rendered from then on. To try rendering the children again,
+/// remount the boundary, for example by changing its key. If the panic happens while drawing,
+/// whatever was drawn before it remains until the fallback is rendered, which is on the next frame.
+///
+/// Caught panics aren't reported by the default panic hook, and the terminal is left as it is. If
+/// the application installed its own panic hook before rendering, caught panics are still passed
+/// to it, so it can log them. A hook installed after rendering starts replaces iocraft's own, so
+/// it sees every panic, and the terminal isn't restored when one goes uncaught.
+/// Panics in futures and event handlers aren't caught, since they don't run as part of the
+/// boundary's subtree.
+///
+/// Catching a panic requires unwinding, so nothing is caught if the application is built with
+/// `panic = "abort"`.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # #[component]
+/// # fn Plugin() -> impl Into<AnyElement<'static>> {
+/// #     element!(Text(content: "plugin output"))
+/// # }
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     ErrorBoundary(
+///         fallback: |error: &ComponentError| element! {
+///             Text(content: format!("The plugin crashed: {}", error), color: Color::Red)
+///         }.into(),
+///     ) {
+///         Plugin
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ErrorBoundary {
+    error: Option<ComponentError>,
+}
+
+impl Component for ErrorBoundary {
+    type Props<'a> = ErrorBoundaryProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        updater.set_layout_style(props.layout_style().into());
+        updater.set_intrinsic_sizes(props.layout_style().intrinsic_sizes());
+
+        let mut error = hooks
+            .use_hook(UseErrorBoundaryImpl::default)
+            .caught_error
+            .take();
+        if error.is_none() && self.error.is_none() {
+            if let Err(err) =
+                catch_panic(|| updater.update_children(props.children.iter_mut(), None))
+            {
+                error = Some(err);
+            }
+        }
+        if let Some(error) = error {
+            if self.error.is_none() {
+                props.on_error.invoke(error.clone());
+                self.error = Some(error);
+            }
+        }
+
+        if let Some(error) = &self.error {
+            updater.update_children([props.fallback.render(error)], None);
+        }
+    }
+}
+
+/// Catches panics while drawing the boundary's children, and re-renders the boundary when one is
+/// caught so that it can show its fallback.
+#[derive(Default)]
+struct UseErrorBoundaryImpl {
+    caught_error: Option<ComponentError>,
+    needs_update: bool,
+}
+
+impl Hook for UseErrorBoundaryImpl {
+    fn poll_change(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if std::mem::take(&mut self.needs_update) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn pre_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        drawer.catch_children_panics();
+    }
+
+    fn post_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        if let Some(error) = drawer.take_children_panic() {
+            self.caught_error = Some(error);
+            self.needs_update = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Props)]
+    struct FaultyProps {
+        fail: bool,
+    }
+
+    #[component]
+    fn Faulty(props: &FaultyProps) -> impl Into<AnyElement<'static>> {
+        if props.fail {
+            panic!("boom");
+        }
+        element!(Text(content: "ok"))
+    }
+
+    #[test]
+    fn test_error_boundary() {
+        assert_eq!(
+            element! {
+                ErrorBoundary {
+                    Faulty
+                }
+            }
+            .to_string(),
+            "ok\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    Text(content: "before")
+                    ErrorBoundary {
+                        Box {
+                            Faulty(fail: true)
+                        }
+                    }
+                    Text(content: "after")
+                }
+            }
+            .to_string(),
+            "before\nerror: boom\nafter\n"
+        );
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        assert_eq!(
+            element! {
+                ErrorBoundary(
+                    fallback: |error: &ComponentError| element! {
+                        Text(content: format!("caught {:?}", error.message()))
+                    }.into(),
+                    on_error: {
+                        let errors = errors.clone();
+                        move |error: ComponentError| errors.lock().unwrap().push(error)
+                    },
+                ) {
+                    // Contexts provided by descendants are removed when they panic.
+                    ContextProvider(value: Context::owned(Theme::default())) {
+                        Faulty(fail: true)
+                    }
+                }
+            }
+            .to_string(),
+            "caught \"boom\"\n"
+        );
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "boom");
+        assert!(errors[0].location().unwrap().contains("error_boundary.rs"));
+    }
+
+    struct FaultyDraw;
+
+    impl Component for FaultyDraw {
+        type Props<'a> = NoProps;
+
+        fn new(_props: &Self::Props<'_>) -> Self {
+            Self
+        }
+
+        fn draw(&mut self, _drawer: &mut ComponentDrawer<'_>) {
+            panic!("draw failed");
+        }
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let fail = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('f'),
+                ..
+            }) = event
+            {
+                fail.set(true);
+            }
+        });
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                ErrorBoundary {
+                    Text(content: "child")
+                    #(fail.get().then(|| element!(FaultyDraw)))
+                }
+                Text(content: "footer")
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_boundary_draw() {
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "child\nfooter\n");
+
+        // Panics while drawing are caught, and the fallback is shown once the tree settles.
+        renderer.send_key(KeyCode::Char('f'));
+        assert_eq!(renderer.frame(), "error: draw failed\nfooter\n");
+    }
+}
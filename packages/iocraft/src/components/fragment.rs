@@ -0,0 +1,168 @@
+use crate::{AnyElement, Component, ComponentUpdater, Hooks, Props};
+
+/// The props which can be passed to the [`Fragment`] component.
+#[derive(Default, Props)]
+pub struct FragmentProps<'a> {
+    /// The elements to render in place of the fragment.
+    pub children: Vec<AnyElement<'a>>,
+}
+
+/// `Fragment` is a component which groups multiple elements without adding a node to the layout.
+///
+/// When a fragment is a child of another element, its children are spliced into its parent's
+/// children in its place, as if they'd been given to the parent directly. This allows a function
+/// or a `#()` block to produce several siblings at once.
+///
+/// The keys of the fragment's children only need to be unique within the fragment. Each child's
+/// key is combined with the fragment's own key, so if several fragments are produced in a loop,
+/// giving each fragment a key is enough to keep the state of all of their children when they're
+/// reordered.
+///
+/// A fragment which is rendered as the root element can't be spliced into anything, so it lays out
+/// its children like a [`Box`](crate::components::Box) with the default properties.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # struct Setting { name: String, value: String }
+/// # fn my_element(settings: Vec<Setting>) -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Box(flex_direction: FlexDirection::Column) {
+///         #(settings.iter().map(|setting| element! {
+///             Fragment(key: setting.name.clone()) {
+///                 Text(content: &setting.name, weight: Weight::Bold)
+///                 Text(content: &setting.value)
+///             }
+///         }))
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Fragment;
+
+impl Component for Fragment {
+    type Props<'a> = FragmentProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        updater.update_children(props.children.iter_mut(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_fragment() {
+        assert_eq!(
+            element! {
+                Fragment {
+                    Text(content: "a")
+                    Text(content: "b")
+                }
+            }
+            .to_string(),
+            "ab\n"
+        );
+
+        // Fragments are spliced into their parents, so the parent lays out their children.
+        assert_eq!(
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    Text(content: "a")
+                    Fragment {
+                        Text(content: "b")
+                        Fragment {
+                            Text(content: "c")
+                        }
+                    }
+                    #((0..2).map(|i| element! {
+                        Fragment(key: i) {
+                            Text(content: format!("{}x", i))
+                            Text(content: format!("{}y", i))
+                        }
+                    }))
+                }
+            }
+            .to_string(),
+            "a\nb\nc\n0x\n0y\n1x\n1y\n"
+        );
+    }
+
+    #[derive(Default, Props)]
+    struct CounterProps {
+        label: String,
+    }
+
+    #[component]
+    fn Counter(mut hooks: Hooks, props: &CounterProps) -> impl Into<AnyElement<'static>> {
+        let count = hooks.use_state(|| 0);
+        let label = props.label.clone();
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            }) = event
+            {
+                if label.starts_with(c) {
+                    count.set(count + 1);
+                }
+            }
+        });
+        element!(Text(content: format!("{}: {}", props.label, count)))
+    }
+
+    #[component]
+    fn Groups(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let reversed = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('r'),
+                ..
+            }) = event
+            {
+                reversed.set(!reversed.get());
+            }
+        });
+        let mut groups = vec!["a", "b"];
+        if reversed.get() {
+            groups.reverse();
+        }
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                #(groups.into_iter().map(|group| element! {
+                    Fragment(key: group) {
+                        Counter(key: "first", label: format!("{}1", group))
+                        Counter(key: "second", label: format!("{}2", group))
+                    }
+                }))
+            }
+        }
+    }
+
+    #[test]
+    fn test_fragment_reordering() {
+        let mut element = element!(Groups);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.send_key(KeyCode::Char('b'));
+        renderer.send_key(KeyCode::Char('b'));
+        assert_eq!(renderer.frame(), "a1: 1\na2: 1\nb1: 2\nb2: 2\n");
+
+        // The children's keys are unique across fragments, and they keep their state when the
+        // fragments are reordered.
+        renderer.send_key(KeyCode::Char('r'));
+        assert_eq!(renderer.frame(), "b1: 2\nb2: 2\na1: 1\na2: 1\n");
+    }
+}
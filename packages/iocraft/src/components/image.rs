@@ -0,0 +1,405 @@
+use crate::{CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hooks, Props};
+use taffy::{AvailableSpace, Size};
+
+/// The way an [`Image`] component maps pixels onto terminal cells.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ImageMode {
+    /// Each cell displays two vertically stacked pixels using half block characters, with one
+    /// pixel as the foreground color and the other as the background color. This is the default.
+    #[default]
+    HalfBlock,
+    /// Each cell displays a 2x4 grid of pixels using braille characters. This gives a higher
+    /// resolution, but only one color per cell. A dot is shown for each pixel which is at least
+    /// half as bright as white, so this works best for line art or plots on a dark background.
+    Braille,
+}
+
+/// A bitmap to be displayed by an [`Image`] component.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageData {
+    /// The width of the image, in pixels.
+    pub width: usize,
+
+    /// The height of the image, in pixels.
+    pub height: usize,
+
+    /// The pixel data, in rows from top to bottom, with four bytes per pixel: red, green, blue,
+    /// and alpha. Pixels which are less than half opaque aren't drawn. If there's less data than
+    /// the dimensions call for, the missing pixels are treated as transparent.
+    pub pixels: Vec<u8>,
+}
+
+impl ImageData {
+    /// Creates a new image from the given dimensions and RGBA pixel data.
+    pub fn new(width: usize, height: usize, pixels: Vec<u8>) -> Self {
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// The props which can be passed to the [`Image`] component.
+#[derive(Default, Props)]
+pub struct ImageProps {
+    /// The image to display.
+    pub data: ImageData,
+
+    /// The way pixels are mapped onto terminal cells.
+    pub mode: ImageMode,
+
+    /// The width of the component, in cells. If not given, it's determined by the height or the
+    /// available space, preserving the image's aspect ratio.
+    pub width: Option<u16>,
+
+    /// The height of the component, in cells. If not given, it's determined by the width or the
+    /// available space, preserving the image's aspect ratio.
+    pub height: Option<u16>,
+
+    /// The color of the dots in braille mode. If not given, each cell is colored with the average
+    /// color of its dots. This has no effect in half block mode.
+    pub color: Option<Color>,
+}
+
+/// `Image` is a component that renders a bitmap using text characters.
+///
+/// The image is scaled to fit the component's size. Without an explicit size, each pixel takes up
+/// half of a cell in half block mode, or an eighth of a cell in braille mode, and the image is
+/// scaled down if it doesn't fit within the available width.
+///
+/// Decoding image files is out of scope, so the pixel data needs to be provided as raw RGBA.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// // A 16x16 gradient.
+/// let pixels = (0..16)
+///     .flat_map(|y| (0..16).flat_map(move |x| [x * 16, y * 16, 128, 255]))
+///     .collect();
+///
+/// element! {
+///     Image(data: ImageData::new(16, 16, pixels))
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Image {
+    data: ImageData,
+    mode: ImageMode,
+    color: Option<Color>,
+}
+
+/// The number of pixels displayed by each cell, horizontally and vertically.
+fn pixels_per_cell(mode: ImageMode) -> (usize, usize) {
+    match mode {
+        ImageMode::HalfBlock => (1, 2),
+        ImageMode::Braille => (2, 4),
+    }
+}
+
+/// The bits of a braille character's dots, indexed by row and then column.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+impl Image {
+    /// Returns the average color of the pixels which map to the given pixel of a grid of the
+    /// given size, or `None` if they're mostly transparent.
+    fn sample(&self, grid_width: usize, grid_height: usize, x: usize, y: usize) -> Option<[u8; 3]> {
+        let span = |i: usize, grid: usize, image: usize| {
+            let start = i * image / grid;
+            let end = ((i + 1) * image / grid).max(start + 1).min(image);
+            start..end
+        };
+        let mut sum = [0u32; 4];
+        let mut count = 0;
+        for py in span(y, grid_height, self.data.height) {
+            for px in span(x, grid_width, self.data.width) {
+                let offset = (py * self.data.width + px) * 4;
+                if let Some(pixel) = self.data.pixels.get(offset..offset + 4) {
+                    for (sum, &channel) in sum.iter_mut().zip(pixel) {
+                        *sum += channel as u32;
+                    }
+                }
+                count += 1;
+            }
+        }
+        if count == 0 || sum[3] < count * 128 {
+            return None;
+        }
+        Some([
+            (sum[0] / count) as u8,
+            (sum[1] / count) as u8,
+            (sum[2] / count) as u8,
+        ])
+    }
+
+    /// Returns the size, in cells, at which the image is displayed without scaling.
+    fn natural_size(data: &ImageData, mode: ImageMode) -> Size<f32> {
+        let (x, y) = pixels_per_cell(mode);
+        Size {
+            width: data.width.div_ceil(x) as _,
+            height: data.height.div_ceil(y) as _,
+        }
+    }
+
+    fn measure(
+        natural: Size<f32>,
+        known_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        let scale_to_width = |width: f32| {
+            if natural.width > 0.0 {
+                (width * natural.height / natural.width).round()
+            } else {
+                0.0
+            }
+        };
+        let scale_to_height = |height: f32| {
+            if natural.height > 0.0 {
+                (height * natural.width / natural.height).round()
+            } else {
+                0.0
+            }
+        };
+        match (known_size.width, known_size.height) {
+            (Some(width), Some(height)) => Size { width, height },
+            (Some(width), None) => Size {
+                width,
+                height: scale_to_width(width),
+            },
+            (None, Some(height)) => Size {
+                width: scale_to_height(height),
+                height,
+            },
+            // Images can be scaled down arbitrarily, so they don't have a minimum width.
+            (None, None) => match available_space.width {
+                AvailableSpace::Definite(width) if width < natural.width => Size {
+                    width: width.floor(),
+                    height: scale_to_width(width.floor()),
+                },
+                AvailableSpace::MinContent => Size::ZERO,
+                _ => natural,
+            },
+        }
+    }
+}
+
+impl Component for Image {
+    type Props<'a> = ImageProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        self.data = props.data.clone();
+        self.mode = props.mode;
+        self.color = props.color;
+
+        let dimension = |cells: Option<u16>| match cells {
+            Some(cells) => taffy::Dimension::Length(cells as _),
+            None => taffy::Dimension::Auto,
+        };
+        updater.set_layout_style(taffy::style::Style {
+            size: taffy::Size {
+                width: dimension(props.width),
+                height: dimension(props.height),
+            },
+            ..Default::default()
+        });
+        let natural = Self::natural_size(&self.data, self.mode);
+        updater.set_measure_func(Box::new(move |known_size, available_space, _| {
+            Self::measure(natural, known_size, available_space)
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let size = drawer.size();
+        let (width, height) = (size.width as usize, size.height as usize);
+        if self.data.width == 0 || self.data.height == 0 {
+            return;
+        }
+        let (x_pixels, y_pixels) = pixels_per_cell(self.mode);
+        let (grid_width, grid_height) = (width * x_pixels, height * y_pixels);
+        let rgb = |[r, g, b]: [u8; 3]| Color::Rgb { r, g, b };
+        let mut canvas = drawer.canvas();
+        for y in 0..height {
+            for x in 0..width {
+                let (c, color) = match self.mode {
+                    ImageMode::HalfBlock => {
+                        let top = self.sample(grid_width, grid_height, x, y * 2);
+                        let bottom = self.sample(grid_width, grid_height, x, y * 2 + 1);
+                        match (top, bottom) {
+                            (Some(top), Some(bottom)) => {
+                                canvas.set_background_color(x as _, y as _, 1, 1, rgb(bottom));
+                                ('▀', rgb(top))
+                            }
+                            (Some(top), None) => ('▀', rgb(top)),
+                            (None, Some(bottom)) => ('▄', rgb(bottom)),
+                            (None, None) => continue,
+                        }
+                    }
+                    ImageMode::Braille => {
+                        let mut bits = 0;
+                        let mut sum = [0u32; 3];
+                        let mut dots = 0;
+                        for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+                            for (dx, bit) in row.iter().enumerate() {
+                                let pixel =
+                                    self.sample(grid_width, grid_height, x * 2 + dx, y * 4 + dy);
+                                if let Some(pixel) = pixel {
+                                    let luminance = 0.2126 * pixel[0] as f32
+                                        + 0.7152 * pixel[1] as f32
+                                        + 0.0722 * pixel[2] as f32;
+                                    if luminance >= 128.0 {
+                                        bits |= bit;
+                                        for (sum, &channel) in sum.iter_mut().zip(&pixel) {
+                                            *sum += channel as u32;
+                                        }
+                                        dots += 1;
+                                    }
+                                }
+                            }
+                        }
+                        if dots == 0 {
+                            continue;
+                        }
+                        let c = char::from_u32(0x2800 + bits).expect("braille is valid unicode");
+                        let color = self
+                            .color
+                            .unwrap_or_else(|| rgb(sum.map(|channel| (channel / dots) as u8)));
+                        (c, color)
+                    }
+                };
+                canvas.set_text(
+                    x as _,
+                    y as _,
+                    c.encode_utf8(&mut [0; 4]),
+                    CanvasTextStyle {
+                        color: Some(color),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    #[test]
+    fn test_image_half_block() {
+        let mut expected = Canvas::new(2, 1);
+        let mut subview = expected.subview_mut(0, 0, 2, 1, false);
+        subview.set_background_color(0, 0, 1, 1, Color::Rgb { r: 0, g: 0, b: 255 });
+        subview.set_text(
+            0,
+            0,
+            "▀",
+            CanvasTextStyle {
+                color: Some(Color::Rgb { r: 255, g: 0, b: 0 }),
+                ..Default::default()
+            },
+        );
+        subview.set_text(
+            1,
+            0,
+            "▀",
+            CanvasTextStyle {
+                color: Some(Color::Rgb { r: 0, g: 255, b: 0 }),
+                ..Default::default()
+            },
+        );
+        let mut expected_ansi = Vec::new();
+        expected.write_ansi(&mut expected_ansi).unwrap();
+
+        let pixels = [RED, GREEN, BLUE, CLEAR].concat();
+        assert_eq!(
+            element!(Image(data: ImageData::new(2, 2, pixels))).render_to_string(None),
+            String::from_utf8(expected_ansi).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_image_braille() {
+        // The left column is lit, and the right column is dark.
+        let pixels = [WHITE, CLEAR].repeat(4).concat();
+        assert_eq!(
+            element!(Image(data: ImageData::new(2, 4, pixels), mode: ImageMode::Braille))
+                .to_string(),
+            "⡇\n"
+        );
+
+        let pixels = WHITE.repeat(8);
+        assert_eq!(
+            element!(Image(data: ImageData::new(2, 4, pixels), mode: ImageMode::Braille))
+                .to_string(),
+            "⣿\n"
+        );
+    }
+
+    #[test]
+    fn test_image_sizing() {
+        let data = ImageData::new(8, 8, WHITE.repeat(8 * 8));
+        let size = |mut e: Element<Box>| {
+            let output = e.to_string();
+            let lines: Vec<_> = output.lines().collect();
+            (lines.first().map_or(0, |l| l.chars().count()), lines.len())
+        };
+
+        // Natural size.
+        assert_eq!(
+            size(element! {
+                Box {
+                    Image(data: data.clone())
+                }
+            }),
+            (8, 4)
+        );
+
+        // Scaled down to fit.
+        assert_eq!(
+            size(element! {
+                Box(width: 4) {
+                    Image(data: data.clone())
+                }
+            }),
+            (4, 2)
+        );
+
+        // Scaled to the given height.
+        assert_eq!(
+            size(element! {
+                Box {
+                    Image(data: data.clone(), height: 8)
+                }
+            }),
+            (16, 8)
+        );
+
+        // Stretched to the given size.
+        assert_eq!(
+            size(element! {
+                Box {
+                    Image(data, width: 3, height: 1)
+                }
+            }),
+            (3, 1)
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{AnyElement, Component, ComponentUpdater, Element, ElementExt, Hooks, Props};
+use iocraft_macros::with_layout_style_props;
+use taffy::{FlexDirection, LengthPercentageAuto, Position, Rect};
+
+/// The props which can be passed to the [`Layers`] component.
+#[with_layout_style_props]
+#[derive(Default, Props)]
+pub struct LayersProps<'a> {
+    /// The layers, from bottom to top.
+    pub children: Vec<AnyElement<'a>>,
+}
+
+/// `Layers` is a component which stacks its children on top of one another, rather than laying
+/// them out side by side. This can be used to render multiple root elements together, such as an
+/// application and a modal dialog or status overlay above it.
+///
+/// The first child is the base layer, which is laid out normally and determines the size of the
+/// component. Each of the other children covers the same area, and is laid out in a column as if
+/// it were the only thing in it. For example, a layer can be centered over the base layer by
+/// giving it a [`Box`](crate::components::Box) with `width: 100pct`, `height: 100pct`, and
+/// centered alignment.
+///
+/// Layers are drawn in order, so later layers are on top of earlier ones. Drawing isn't opaque by
+/// default: only the cells that a layer actually draws to, such as its text, borders, and
+/// backgrounds, replace what's underneath. To hide the layers below, give a layer a background
+/// color. Layers don't affect input: every layer still receives terminal events, so components in
+/// lower layers should typically stop handling the keyboard while an overlay is shown.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element(show_help: bool) -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Layers(width: 40, height: 10) {
+///         Box(flex_direction: FlexDirection::Column) {
+///             Text(content: "Main content")
+///         }
+///         #(show_help.then(|| element! {
+///             Box(
+///                 width: 100pct,
+///                 height: 100pct,
+///                 justify_content: JustifyContent::Center,
+///                 align_items: AlignItems::Center,
+///             ) {
+///                 Box(border_style: BorderStyle::Round, background_color: Color::DarkGrey) {
+///                     Text(content: "Press q to quit")
+///                 }
+///             }
+///         }))
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Layers;
+
+impl Component for Layers {
+    type Props<'a> = LayersProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        updater.set_layout_style(props.layout_style().into());
+        updater.set_intrinsic_sizes(props.layout_style().intrinsic_sizes());
+
+        // Each layer is wrapped in a slot which positions it. The slots reuse the keys of the
+        // layers, so that the layers keep their state as others come and go.
+        let mut slots = props
+            .children
+            .iter_mut()
+            .enumerate()
+            .map(|(index, child)| Element::<Layer> {
+                key: child.key().clone(),
+                props: LayerProps {
+                    child: Some(child.into()),
+                    is_overlay: index > 0,
+                },
+            })
+            .collect::<Vec<_>>();
+        updater.update_children(slots.iter_mut(), None);
+    }
+}
+
+#[derive(Default, Props)]
+struct LayerProps<'a> {
+    child: Option<AnyElement<'a>>,
+    is_overlay: bool,
+}
+
+#[derive(Default)]
+struct Layer;
+
+impl Component for Layer {
+    type Props<'a> = LayerProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        updater.set_layout_style(if props.is_overlay {
+            taffy::style::Style {
+                position: Position::Absolute,
+                inset: Rect {
+                    left: LengthPercentageAuto::Length(0.0),
+                    right: LengthPercentageAuto::Length(0.0),
+                    top: LengthPercentageAuto::Length(0.0),
+                    bottom: LengthPercentageAuto::Length(0.0),
+                },
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+        } else {
+            taffy::style::Style {
+                flex_grow: 1.0,
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+        });
+        updater.update_children(props.child.iter_mut(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_layers() {
+        assert_eq!(element!(Layers).to_string(), "");
+
+        assert_eq!(
+            element! {
+                Layers {
+                    Box(flex_direction: FlexDirection::Column) {
+                        Text(content: "aaaaaaa")
+                        Text(content: "aaaaaaa")
+                        Text(content: "aaaaaaa")
+                    }
+                    Box(
+                        width: 100pct,
+                        height: 100pct,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                    ) {
+                        Text(content: "bbb")
+                    }
+                }
+            }
+            .to_string(),
+            "aaaaaaa\naabbbaa\naaaaaaa\n"
+        );
+
+        // Later layers are drawn on top of earlier ones.
+        assert_eq!(
+            element! {
+                Layers {
+                    Text(content: "aaaa")
+                    Text(content: "bbb")
+                    Text(content: "cc")
+                    Text(content: "d")
+                }
+            }
+            .to_string(),
+            "dcba\n"
+        );
+    }
+
+    #[component]
+    fn Counter(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let count = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('+'),
+                ..
+            }) = event
+            {
+                count.set(count + 1);
+            }
+        });
+        element!(Text(content: format!("count: {}", count)))
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let show_overlay = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('o'),
+                ..
+            }) = event
+            {
+                show_overlay.set(!show_overlay.get());
+            }
+        });
+        element! {
+            Layers(width: 10, height: 2) {
+                Counter(key: "counter")
+                #(show_overlay.get().then(|| element! {
+                    Box(key: "overlay", width: 100pct, height: 100pct, align_items: AlignItems::End) {
+                        Text(content: "[o]")
+                    }
+                }))
+            }
+        }
+    }
+
+    #[test]
+    fn test_layers_state() {
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Char('+'));
+        assert_eq!(renderer.frame(), "count: 1\n\n");
+
+        // Adding and removing a layer doesn't reset the state of the others.
+        renderer.send_key(KeyCode::Char('o'));
+        renderer.send_key(KeyCode::Char('+'));
+        assert_eq!(renderer.frame(), "count: 2\n[o]\n");
+        renderer.send_key(KeyCode::Char('o'));
+        assert_eq!(renderer.frame(), "count: 2\n\n");
+    }
+}
@@ -0,0 +1,488 @@
+use crate::{
+    hooks::{State, UseState, UseTerminalEvents},
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hook, Hooks, KeyCode,
+    KeyEvent, KeyEventKind, MouseEvent, MouseEventKind, Props, TerminalEvent,
+};
+use iocraft_macros::with_layout_style_props;
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+use taffy::{AvailableSpace, Overflow, Size};
+use unicode_width::UnicodeWidthStr;
+
+struct LogBufferInner {
+    lines: VecDeque<String>,
+    // The number of lines which have ever been removed from the front of the buffer, which is the
+    // absolute index of the first line.
+    first_index: u64,
+    max_lines: usize,
+    // Incremented whenever the buffer changes.
+    generation: u64,
+    next_watcher_id: u64,
+    watchers: HashMap<u64, Option<Waker>>,
+}
+
+impl LogBufferInner {
+    fn wake_watchers(&mut self) {
+        self.generation += 1;
+        for waker in self.watchers.values_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// A buffer of lines to be displayed by a [`LogView`].
+///
+/// The buffer holds up to a maximum number of lines. Once it's full, the oldest lines are
+/// discarded as new ones are appended, so memory use stays bounded no matter how much output
+/// there is.
+///
+/// Buffers are cheap to clone, and clones refer to the same lines, so a clone can be moved into a
+/// future or another thread to append lines while the original is given to a [`LogView`]. Any
+/// views of the buffer are re-rendered as lines are appended.
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<LogBufferInner>>,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_LINES)
+    }
+}
+
+impl LogBuffer {
+    /// The maximum number of lines in a buffer created via [`LogBuffer::default`].
+    pub const DEFAULT_MAX_LINES: usize = 10_000;
+
+    /// Creates an empty buffer which holds up to the given number of lines, which must be at least
+    /// one.
+    pub fn new(max_lines: usize) -> Self {
+        let max_lines = max_lines.max(1);
+        Self {
+            inner: Arc::new(Mutex::new(LogBufferInner {
+                lines: VecDeque::with_capacity(max_lines.min(1024)),
+                first_index: 0,
+                max_lines,
+                generation: 0,
+                next_watcher_id: 0,
+                watchers: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Appends each line of the given text to the buffer, discarding the oldest lines if the
+    /// buffer is full.
+    pub fn push<S: AsRef<str>>(&self, text: S) {
+        let mut inner = self.inner.lock().unwrap();
+        for line in text.as_ref().split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if inner.lines.len() == inner.max_lines {
+                inner.lines.pop_front();
+                inner.first_index += 1;
+            }
+            inner.lines.push_back(line.to_string());
+        }
+        inner.wake_watchers();
+    }
+
+    /// Removes all of the lines from the buffer.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        let len = inner.lines.len();
+        inner.lines.clear();
+        inner.first_index += len as u64;
+        inner.wake_watchers();
+    }
+
+    /// Returns the number of lines in the buffer.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().lines.len()
+    }
+
+    /// Returns whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of lines that the buffer holds.
+    pub fn max_lines(&self) -> usize {
+        self.inner.lock().unwrap().max_lines
+    }
+
+    /// Returns a copy of the lines in the buffer, from oldest to newest.
+    pub fn lines(&self) -> Vec<String> {
+        self.inner.lock().unwrap().lines.iter().cloned().collect()
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns the offset of the top visible line from the start of the buffer, given the
+    /// absolute index of the line that the view is pinned to, if any.
+    fn top_offset(&self, pinned_top: Option<u64>, viewport_height: usize) -> usize {
+        let inner = self.inner.lock().unwrap();
+        let max_offset = inner.lines.len().saturating_sub(viewport_height);
+        match pinned_top {
+            Some(top) => (top.saturating_sub(inner.first_index) as usize).min(max_offset),
+            None => max_offset,
+        }
+    }
+}
+
+/// The props which can be passed to the [`LogView`] component.
+#[with_layout_style_props]
+#[derive(Default, Props)]
+pub struct LogViewProps {
+    /// The buffer whose lines are displayed.
+    pub buffer: LogBuffer,
+
+    /// True if the log view has focus and should respond to the keyboard.
+    pub has_focus: bool,
+
+    /// The color to make the lines.
+    pub color: Option<Color>,
+}
+
+/// `LogView` is a component which displays the tail of a [`LogBuffer`], such as the output of a
+/// long-running process.
+///
+/// As lines are appended to the buffer, the view stays scrolled to the bottom, unless the user has
+/// scrolled up, in which case it stays on the lines they're looking at until they scroll back
+/// down to the bottom. While the log view has focus, the Up, Down, PageUp, and PageDown keys
+/// scroll it, and Home and End jump to the oldest and newest lines. The mouse wheel scrolls it
+/// while the mouse is over it.
+///
+/// Lines aren't wrapped, and any part of a line which is too wide to fit is clipped. Only the
+/// visible lines are drawn, so appending is cheap regardless of how many lines the buffer holds.
+/// The log view is as tall as the number of lines in the buffer by default, so it should typically
+/// be given a height or a maximum height, or allowed to grow to fill its parent.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::time::Duration;
+/// #[component]
+/// fn Tail(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let buffer = hooks.use_ref(|| LogBuffer::new(1000)).borrow().clone();
+///     hooks.use_future({
+///         let buffer = buffer.clone();
+///         async move {
+///             for i in 0.. {
+///                 buffer.push(format!("request {} handled", i));
+///                 smol::Timer::after(Duration::from_millis(100)).await;
+///             }
+///         }
+///     });
+///
+///     element! {
+///         LogView(buffer, height: 10, has_focus: true)
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct LogView {
+    buffer: Arc<Mutex<LogBuffer>>,
+    has_focus: Arc<AtomicBool>,
+    viewport_height: Arc<AtomicUsize>,
+    pinned_top: Option<State<Option<u64>>>,
+    style: CanvasTextStyle,
+}
+
+impl LogView {
+    fn scroll_by<F: FnOnce(usize, usize, usize) -> usize>(
+        pinned_top: State<Option<u64>>,
+        buffer: &Mutex<LogBuffer>,
+        viewport_height: usize,
+        f: F,
+    ) {
+        let buffer = buffer.lock().unwrap();
+        let top = buffer.top_offset(pinned_top.get(), viewport_height);
+        let inner = buffer.inner.lock().unwrap();
+        let max_offset = inner.lines.len().saturating_sub(viewport_height);
+        let new_top = f(top, max_offset, viewport_height.max(1)).min(max_offset);
+        // Once the user scrolls back to the bottom, the view follows new lines again.
+        let new_pinned_top = (new_top < max_offset).then(|| inner.first_index + new_top as u64);
+        if new_pinned_top != pinned_top.get() {
+            pinned_top.set(new_pinned_top);
+        }
+    }
+}
+
+impl Component for LogView {
+    type Props<'a> = LogViewProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        self.has_focus.store(props.has_focus, Ordering::SeqCst);
+        *self.buffer.lock().unwrap() = props.buffer.clone();
+        self.style = CanvasTextStyle {
+            color: props.color,
+            ..Default::default()
+        };
+
+        let watcher = hooks.use_hook(|| UseLogBufferImpl {
+            buffer: props.buffer.clone(),
+            watcher_id: None,
+            generation: 0,
+        });
+        watcher.set_buffer(&props.buffer);
+
+        let pinned_top = hooks.use_state(|| None);
+        self.pinned_top = Some(pinned_top);
+        hooks.use_terminal_events({
+            let buffer = self.buffer.clone();
+            let has_focus = self.has_focus.clone();
+            let viewport_height = self.viewport_height.clone();
+            move |event| match event {
+                TerminalEvent::Key(KeyEvent { code, kind, .. })
+                    if kind != KeyEventKind::Release && has_focus.load(Ordering::SeqCst) =>
+                {
+                    let height = viewport_height.load(Ordering::SeqCst);
+                    Self::scroll_by(pinned_top, &buffer, height, |top, max, page| match code {
+                        KeyCode::Up => top.saturating_sub(1),
+                        KeyCode::Down => top.saturating_add(1),
+                        KeyCode::PageUp => top.saturating_sub(page),
+                        KeyCode::PageDown => top.saturating_add(page),
+                        KeyCode::Home => 0,
+                        KeyCode::End => max,
+                        _ => top,
+                    });
+                }
+                _ => {}
+            }
+        });
+        hooks.use_local_terminal_events({
+            let buffer = self.buffer.clone();
+            let viewport_height = self.viewport_height.clone();
+            move |event| {
+                if let TerminalEvent::Mouse(MouseEvent { kind, .. }) = event {
+                    let height = viewport_height.load(Ordering::SeqCst);
+                    Self::scroll_by(pinned_top, &buffer, height, |top, _, _| match kind {
+                        MouseEventKind::ScrollUp => top.saturating_sub(1),
+                        MouseEventKind::ScrollDown => top.saturating_add(1),
+                        _ => top,
+                    });
+                }
+            }
+        });
+
+        let buffer = props.buffer.clone();
+        let mut style: taffy::style::Style = props.layout_style().into();
+        // This allows the log view to be shorter than its lines, like a scroll view.
+        style.overflow.y = Overflow::Scroll;
+        updater.set_layout_style(style);
+        updater.set_intrinsic_sizes(props.layout_style().intrinsic_sizes());
+        updater.set_measure_func(Box::new(move |known_size, available_space, _| {
+            let inner = buffer.inner.lock().unwrap();
+            // Unless the height is given, the log view is as tall as its lines, up to the space
+            // available to it.
+            let height = match (known_size.height, available_space.height) {
+                (Some(height), _) => height as usize,
+                (None, AvailableSpace::Definite(height)) => inner.lines.len().min(height as _),
+                (None, _) => inner.lines.len(),
+            };
+            // Unless the width is constrained, the log view is as wide as the lines at the bottom,
+            // which are the ones that are visible unless it's scrolled.
+            let width = known_size.width.unwrap_or(match available_space.width {
+                AvailableSpace::Definite(width) => width,
+                _ => inner
+                    .lines
+                    .iter()
+                    .rev()
+                    .take(height)
+                    .map(|line| line.width())
+                    .max()
+                    .unwrap_or(0) as _,
+            });
+            Size {
+                width,
+                height: height as _,
+            }
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let height = drawer.size().height as usize;
+        self.viewport_height.store(height, Ordering::SeqCst);
+        let buffer = self.buffer.lock().unwrap();
+        let top = buffer.top_offset(self.pinned_top.and_then(|top| top.get()), height);
+        let inner = buffer.inner.lock().unwrap();
+        let mut canvas = drawer.canvas();
+        for (y, line) in inner.lines.iter().skip(top).take(height).enumerate() {
+            canvas.set_text(0, y as _, line, self.style);
+        }
+    }
+}
+
+/// Re-renders the log view whenever its buffer changes.
+struct UseLogBufferImpl {
+    buffer: LogBuffer,
+    watcher_id: Option<u64>,
+    generation: u64,
+}
+
+impl UseLogBufferImpl {
+    fn set_buffer(&mut self, buffer: &LogBuffer) {
+        if !self.buffer.ptr_eq(buffer) {
+            self.remove_watcher();
+            self.buffer = buffer.clone();
+        }
+        let mut inner = self.buffer.inner.lock().unwrap();
+        if self.watcher_id.is_none() {
+            let id = inner.next_watcher_id;
+            inner.next_watcher_id += 1;
+            inner.watchers.insert(id, None);
+            self.watcher_id = Some(id);
+        }
+        self.generation = inner.generation;
+    }
+
+    fn remove_watcher(&mut self) {
+        if let Some(id) = self.watcher_id.take() {
+            self.buffer.inner.lock().unwrap().watchers.remove(&id);
+        }
+    }
+}
+
+impl Hook for UseLogBufferImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.buffer.inner.lock().unwrap();
+        if inner.generation != self.generation {
+            Poll::Ready(())
+        } else {
+            if let Some(id) = self.watcher_id {
+                inner.watchers.insert(id, Some(cx.waker().clone()));
+            }
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for UseLogBufferImpl {
+    fn drop(&mut self) {
+        self.remove_watcher();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_log_buffer() {
+        let buffer = LogBuffer::new(3);
+        assert!(buffer.is_empty());
+        buffer.push("a");
+        buffer.push("b\r\nc\nd");
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.lines(), vec!["b", "c", "d"]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(LogBuffer::new(0).max_lines(), 1);
+    }
+
+    #[test]
+    fn test_log_view() {
+        let buffer = LogBuffer::default();
+        assert_eq!(element!(LogView(buffer: buffer.clone())).to_string(), "");
+
+        buffer.push("one\ntwo\nthree\nfour");
+        assert_eq!(
+            element!(LogView(buffer: buffer.clone())).to_string(),
+            "one\ntwo\nthree\nfour\n"
+        );
+        assert_eq!(
+            element!(LogView(buffer: buffer.clone(), height: 2)).to_string(),
+            "three\nfour\n"
+        );
+
+        // Without a height of its own, it fits within the height available to it.
+        assert_eq!(
+            element! {
+                Box(height: 3) {
+                    Box(flex_direction: FlexDirection::Column) {
+                        LogView(buffer)
+                    }
+                }
+            }
+            .to_string(),
+            "two\nthree\nfour\n"
+        );
+    }
+
+    #[component]
+    fn Tail(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let buffer = hooks.use_ref(|| LogBuffer::new(5)).borrow().clone();
+        let count = hooks.use_ref(|| 0);
+        hooks.use_terminal_events({
+            let buffer = buffer.clone();
+            move |event| {
+                if let TerminalEvent::Key(KeyEvent {
+                    code: KeyCode::Char('a'),
+                    ..
+                }) = event
+                {
+                    count.set(count.get() + 1);
+                    buffer.push(format!("line {}", count.get()));
+                }
+            }
+        });
+        element! {
+            LogView(buffer, height: 2, has_focus: true)
+        }
+    }
+
+    #[test]
+    fn test_log_view_scrolling() {
+        let mut element = element!(Tail);
+        let mut renderer = TestRenderer::new(&mut element);
+        for _ in 0..3 {
+            renderer.send_key(KeyCode::Char('a'));
+        }
+        assert_eq!(renderer.frame(), "line 2\nline 3\n");
+
+        // The view follows new lines while it's at the bottom.
+        renderer.send_key(KeyCode::Char('a'));
+        assert_eq!(renderer.frame(), "line 3\nline 4\n");
+
+        // Once scrolled up, it stays put as lines are appended.
+        renderer.send_key(KeyCode::Up);
+        assert_eq!(renderer.frame(), "line 2\nline 3\n");
+        renderer.send_key(KeyCode::Char('a'));
+        assert_eq!(renderer.frame(), "line 2\nline 3\n");
+
+        // Unless the lines it's on are discarded.
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.send_key(KeyCode::Char('a'));
+        assert_eq!(renderer.frame(), "line 3\nline 4\n");
+
+        renderer.send_key(KeyCode::Home);
+        assert_eq!(renderer.frame(), "line 3\nline 4\n");
+
+        // Scrolling back to the bottom follows new lines again.
+        renderer.send_key(KeyCode::End);
+        assert_eq!(renderer.frame(), "line 6\nline 7\n");
+        renderer.send_key(KeyCode::Char('a'));
+        assert_eq!(renderer.frame(), "line 7\nline 8\n");
+    }
+}
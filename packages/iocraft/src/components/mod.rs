@@ -1,9 +1,51 @@
 mod r#box;
 pub use r#box::*;
 
+mod button;
+pub use button::*;
+
 mod context_provider;
 pub use context_provider::*;
 
+mod error_boundary;
+pub use error_boundary::*;
+
+mod fragment;
+pub use fragment::*;
+
+mod image;
+pub use image::*;
+
+mod layers;
+pub use layers::*;
+
+mod log_view;
+pub use log_view::*;
+
+mod multi_select;
+pub use multi_select::*;
+
+mod portal;
+pub use portal::*;
+
+mod progress_bar;
+pub use progress_bar::*;
+
+mod scroll_view;
+pub use scroll_view::*;
+
+mod select;
+pub use select::*;
+
+mod spacer;
+pub use spacer::*;
+
+mod spinner;
+pub use spinner::*;
+
+mod table;
+pub use table::*;
+
 mod text;
 pub use text::*;
 
@@ -0,0 +1,303 @@
+use crate::{
+    hooks::{UseState, UseTerminalEvents},
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Handler, Hooks, KeyCode,
+    KeyEvent, KeyEventKind, Props, TerminalEvent,
+};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use taffy::Size;
+use unicode_width::UnicodeWidthStr;
+
+const CHECKED: &str = "[x] ";
+const UNCHECKED: &str = "[ ] ";
+
+/// The props which can be passed to the [`MultiSelect`] component.
+#[derive(Default, Props)]
+pub struct MultiSelectProps {
+    /// The items to choose from, one per row.
+    pub items: Vec<String>,
+
+    /// The indices of the items which are checked when the component is first rendered. If this
+    /// changes, the checked items are replaced with the new ones.
+    pub selected: Vec<usize>,
+
+    /// The handler to invoke with the indices of all of the checked items, in ascending order,
+    /// when an item is checked or unchecked.
+    pub on_change: Handler<'static, Vec<usize>>,
+
+    /// The handler to invoke with the indices of all of the checked items, in ascending order,
+    /// when Enter is pressed.
+    pub on_confirm: Handler<'static, Vec<usize>>,
+
+    /// True if the multi-select has focus and should respond to the keyboard.
+    pub has_focus: bool,
+
+    /// The color to make the items.
+    pub color: Option<Color>,
+
+    /// The color to make the highlighted item. Defaults to `color`.
+    pub highlight_color: Option<Color>,
+
+    /// The text to draw before the highlighted item. Other items are indented by its width.
+    /// Defaults to `"> "`.
+    pub marker: Option<String>,
+}
+
+/// `MultiSelect` is a component that lets the user pick any number of items from a list.
+///
+/// Each item is drawn with a checkbox showing whether it's checked. Navigation works like
+/// [`Select`](crate::components::Select): one item is highlighted at a time, and while the
+/// multi-select has focus, the Up and Down keys move the highlight, wrapping around at the ends of
+/// the list. Space checks or unchecks the highlighted item without moving the highlight, and
+/// invokes the `on_change` handler. Enter invokes the `on_confirm` handler.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # #[component]
+/// # fn Toppings(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+/// let count = hooks.use_state(|| 0);
+///
+/// element! {
+///     Box(flex_direction: FlexDirection::Column) {
+///         MultiSelect(
+///             items: vec!["Cheese".to_string(), "Olives".to_string(), "Basil".to_string()],
+///             has_focus: true,
+///             on_change: move |checked: Vec<usize>| count.set(checked.len()),
+///         )
+///         Text(content: format!("{} toppings", count))
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MultiSelect {
+    items: Vec<String>,
+    checked: Vec<usize>,
+    highlighted: usize,
+    marker: String,
+    has_focus: Arc<AtomicBool>,
+    item_count: Arc<AtomicUsize>,
+    change_handler: Arc<Mutex<Handler<'static, Vec<usize>>>>,
+    confirm_handler: Arc<Mutex<Handler<'static, Vec<usize>>>>,
+    last_selected: Option<Vec<usize>>,
+    style: CanvasTextStyle,
+    highlight_style: CanvasTextStyle,
+}
+
+impl Component for MultiSelect {
+    type Props<'a> = MultiSelectProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        *self.change_handler.lock().unwrap() = props.on_change.take();
+        *self.confirm_handler.lock().unwrap() = props.on_confirm.take();
+        self.has_focus.store(props.has_focus, Ordering::SeqCst);
+        self.item_count.store(props.items.len(), Ordering::SeqCst);
+
+        let highlighted = hooks.use_state(|| 0usize);
+        let checked = hooks.use_state(|| normalize(props.selected.clone()));
+        if self
+            .last_selected
+            .as_ref()
+            .is_some_and(|last| *last != props.selected)
+        {
+            checked.set(normalize(props.selected.clone()));
+        }
+        self.last_selected = Some(props.selected.clone());
+
+        hooks.use_terminal_events({
+            let change_handler = self.change_handler.clone();
+            let confirm_handler = self.confirm_handler.clone();
+            let has_focus = self.has_focus.clone();
+            let item_count = self.item_count.clone();
+            move |event| match event {
+                TerminalEvent::Key(KeyEvent { code, kind, .. })
+                    if kind != KeyEventKind::Release && has_focus.load(Ordering::SeqCst) =>
+                {
+                    let count = item_count.load(Ordering::SeqCst);
+                    if count == 0 {
+                        return;
+                    }
+                    let index = highlighted.get().min(count - 1);
+                    match code {
+                        KeyCode::Up => highlighted.set((index + count - 1) % count),
+                        KeyCode::Down => highlighted.set((index + 1) % count),
+                        KeyCode::Char(' ') if kind == KeyEventKind::Press => {
+                            let mut new_checked = checked.read().clone();
+                            match new_checked.binary_search(&index) {
+                                Ok(position) => {
+                                    new_checked.remove(position);
+                                }
+                                Err(position) => new_checked.insert(position, index),
+                            }
+                            checked.set(new_checked.clone());
+                            change_handler.lock().unwrap().invoke(new_checked);
+                        }
+                        KeyCode::Enter if kind == KeyEventKind::Press => {
+                            let checked = checked.read().clone();
+                            confirm_handler.lock().unwrap().invoke(checked);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        });
+
+        self.items = props.items.clone();
+        self.checked = checked.read().clone();
+        self.highlighted = highlighted.get();
+        self.marker = props.marker.clone().unwrap_or_else(|| "> ".to_string());
+        self.style = CanvasTextStyle {
+            color: props.color,
+            ..Default::default()
+        };
+        self.highlight_style = CanvasTextStyle {
+            color: props.highlight_color.or(props.color),
+            reverse: props.has_focus,
+            ..Default::default()
+        };
+
+        let prefix_width = self.marker.width() + CHECKED.width();
+        let width = self
+            .items
+            .iter()
+            .map(|item| prefix_width + item.width())
+            .max()
+            .unwrap_or(0);
+        let height = self.items.len();
+        updater.set_measure_func(Box::new(move |_, _, _| Size {
+            width: width as _,
+            height: height as _,
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let marker_width = self.marker.width() as isize;
+        let prefix_width = marker_width + CHECKED.width() as isize;
+        let highlighted = self.highlighted.min(self.items.len().saturating_sub(1));
+        let mut canvas = drawer.canvas();
+        for (y, item) in self.items.iter().enumerate() {
+            let checkbox = if self.checked.binary_search(&y).is_ok() {
+                CHECKED
+            } else {
+                UNCHECKED
+            };
+            canvas.set_text(marker_width, y as _, checkbox, self.style);
+            if y == highlighted {
+                canvas.set_text(0, y as _, &self.marker, self.style);
+                canvas.set_text(prefix_width, y as _, item, self.highlight_style);
+            } else {
+                canvas.set_text(prefix_width, y as _, item, self.style);
+            }
+        }
+    }
+}
+
+/// Sorts the indices and removes duplicates, so that they can be binary searched.
+fn normalize(mut indices: Vec<usize>) -> Vec<usize> {
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Default, Props)]
+    struct ToppingsProps {
+        items: Vec<String>,
+    }
+
+    #[component]
+    fn Toppings(mut hooks: Hooks, props: &ToppingsProps) -> impl Into<AnyElement<'static>> {
+        let changed = hooks.use_state(Vec::new);
+        let confirmed = hooks.use_state(|| None);
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                MultiSelect(
+                    items: props.items.clone(),
+                    selected: vec![2],
+                    has_focus: true,
+                    on_change: move |checked| changed.set(checked),
+                    on_confirm: move |checked| confirmed.set(Some(checked)),
+                )
+                Text(content: format!("changed: {:?}", *changed.read()))
+                Text(content: format!("confirmed: {:?}", *confirmed.read()))
+            }
+        }
+    }
+
+    fn items() -> Vec<String> {
+        vec![
+            "cheese".to_string(),
+            "olives".to_string(),
+            "basil".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_multi_select() {
+        assert_eq!(
+            element!(MultiSelect(items: items(), selected: vec![0, 2])).to_string(),
+            "> [x] cheese\n  [ ] olives\n  [x] basil\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_select_keyboard() {
+        let mut element = element!(Toppings(items: items()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(
+            renderer.frame(),
+            "> [ ] cheese\n  [ ] olives\n  [x] basil\nchanged: []\nconfirmed: None\n"
+        );
+
+        // Toggling an item doesn't move the highlight.
+        renderer.send_key(KeyCode::Down);
+        renderer.send_key(KeyCode::Char(' '));
+        assert_eq!(
+            renderer.frame(),
+            "  [ ] cheese\n> [x] olives\n  [x] basil\nchanged: [1, 2]\nconfirmed: None\n"
+        );
+
+        renderer.send_key(KeyCode::Up);
+        renderer.send_key(KeyCode::Char(' '));
+        renderer.send_key(KeyCode::Up);
+        renderer.send_key(KeyCode::Char(' '));
+        assert_eq!(
+            renderer.frame(),
+            "  [x] cheese\n  [x] olives\n> [ ] basil\nchanged: [0, 1]\nconfirmed: None\n"
+        );
+
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(
+            renderer.frame(),
+            "  [x] cheese\n  [x] olives\n> [ ] basil\nchanged: [0, 1]\nconfirmed: Some([0, 1])\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_select_empty() {
+        assert_eq!(element!(MultiSelect).to_string(), "");
+
+        let mut element = element!(Toppings);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Char(' '));
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "changed: []\nconfirmed: None\n");
+    }
+}
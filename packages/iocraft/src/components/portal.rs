@@ -0,0 +1,489 @@
+use crate::{
+    canvas::Canvas, hooks::LayoutRect, AnyElement, Component, ComponentDrawer, ComponentUpdater,
+    Hook, Hooks, Props,
+};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+use taffy::{AlignSelf, Dimension, FlexDirection, LengthPercentageAuto, Point, Position, Rect};
+
+/// The props which can be passed to the [`Portal`] component.
+#[derive(Default, Props)]
+pub struct PortalProps<'a> {
+    /// The elements to render into the target.
+    pub children: Vec<AnyElement<'a>>,
+
+    /// The name of the [`PortalTarget`] to render the children into.
+    pub target: String,
+}
+
+/// `Portal` is a component which renders its children into a [`PortalTarget`] elsewhere in the
+/// tree, rather than in place. This allows a deeply nested component to show something above
+/// everything else, such as a modal dialog or a toast, without being clipped by its ancestors.
+///
+/// The children are laid out as if they were the only thing in the target, in a column. They still
+/// belong to the portal's position in the tree, so they keep their state as usual and have access
+/// to the contexts provided by the portal's ancestors. The portal itself takes up no space.
+///
+/// If multiple portals render into the same target, they're drawn in the order in which they were
+/// first drawn, with later ones on top. If there's no target with the given name, the children
+/// aren't drawn at all.
+///
+/// The children are sized and positioned according to where the target was in the previous frame,
+/// so when the target first appears or moves, the content catches up on the next render. This
+/// means that when an element is only rendered once, such as via
+/// [`to_string`](crate::ElementExt::to_string), the content is laid out at its natural size in the
+/// top left corner of the target.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn SaveButton(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let confirming = hooks.use_state(|| false);
+///     element! {
+///         Box {
+///             Button(label: "Save", is_focused: true, on_press: move |_| confirming.set(true))
+///             #(confirming.get().then(|| element! {
+///                 Portal(target: "overlay") {
+///                     Box(
+///                         width: 100pct,
+///                         height: 100pct,
+///                         justify_content: JustifyContent::Center,
+///                         align_items: AlignItems::Center,
+///                     ) {
+///                         Box(border_style: BorderStyle::Round, background_color: Color::DarkGrey) {
+///                             Text(content: "Saving...")
+///                         }
+///                     }
+///                 }
+///             }))
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl Into<AnyElement<'static>> {
+///     element! {
+///         Layers(width: 80, height: 24) {
+///             Box(flex_direction: FlexDirection::Column) {
+///                 SaveButton
+///             }
+///             PortalTarget(name: "overlay")
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct Portal;
+
+impl Component for Portal {
+    type Props<'a> = PortalProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        let rect = {
+            let mut hooks = hooks.with_context_stack(updater.component_context_stack());
+            let manager = hooks.system().portal_manager.clone();
+            let hook = hooks.use_hook(move || {
+                let owner = manager.inner.lock().unwrap().allocate_id();
+                UsePortalImpl {
+                    manager,
+                    owner,
+                    target: String::new(),
+                    rect: None,
+                }
+            });
+            if hook.target != props.target {
+                hook.remove();
+                hook.target = props.target.clone();
+            }
+            hook.rect = hook.manager.target_rect(&hook.target);
+            hook.rect
+        };
+
+        let size = |length: Option<u16>| match length {
+            Some(length) => Dimension::Length(length as _),
+            None => Dimension::Auto,
+        };
+        updater.set_layout_style(taffy::style::Style {
+            position: Position::Absolute,
+            inset: Rect {
+                left: LengthPercentageAuto::Length(0.0),
+                top: LengthPercentageAuto::Length(0.0),
+                right: LengthPercentageAuto::Auto,
+                bottom: LengthPercentageAuto::Auto,
+            },
+            size: taffy::Size {
+                width: size(rect.map(|rect| rect.width)),
+                height: size(rect.map(|rect| rect.height)),
+            },
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        });
+        updater.update_children(props.children.iter_mut(), None);
+    }
+}
+
+/// The props which can be passed to the [`PortalTarget`] component.
+#[derive(Default, Props)]
+pub struct PortalTargetProps {
+    /// The name that [`Portal`] components use to render into the target. This should be unique
+    /// within the whole tree.
+    pub name: String,
+}
+
+/// `PortalTarget` is a component which draws the children of any [`Portal`] components with its
+/// name. It expands to fill the space available to it.
+///
+/// A target is typically the last child of a [`Layers`](crate::components::Layers) component, so
+/// that it covers the rest of the application and is drawn on top of it. Portals should be drawn
+/// before their target, or their content lags a frame behind.
+///
+/// See [`Portal`] for an example.
+#[derive(Default)]
+pub struct PortalTarget;
+
+impl Component for PortalTarget {
+    type Props<'a> = PortalTargetProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        {
+            let mut hooks = hooks.with_context_stack(updater.component_context_stack());
+            let manager = hooks.system().portal_manager.clone();
+            let hook = hooks.use_hook(move || {
+                let watcher_id = manager.inner.lock().unwrap().add_watcher();
+                UsePortalTargetImpl {
+                    manager,
+                    watcher_id,
+                    name: String::new(),
+                    generation: None,
+                }
+            });
+            if hook.name != props.name {
+                hook.remove();
+                hook.name = props.name.clone();
+                hook.generation = None;
+            }
+        }
+
+        updater.set_layout_style(taffy::style::Style {
+            flex_grow: 1.0,
+            align_self: Some(AlignSelf::Stretch),
+            ..Default::default()
+        });
+    }
+}
+
+#[derive(Default)]
+struct PortalTargetState {
+    rect: Option<LayoutRect>,
+    // The content drawn by each portal, in the order the portals were first drawn.
+    contents: Vec<(u64, Canvas)>,
+    // Incremented whenever the contents change.
+    generation: u64,
+}
+
+#[derive(Default)]
+struct PortalManagerInner {
+    next_id: u64,
+    targets: HashMap<String, PortalTargetState>,
+    watchers: HashMap<u64, Option<Waker>>,
+}
+
+impl PortalManagerInner {
+    fn allocate_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn add_watcher(&mut self) -> u64 {
+        let id = self.allocate_id();
+        self.watchers.insert(id, None);
+        id
+    }
+
+    fn wake_watchers(&mut self) {
+        for waker in self.watchers.values_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn set_content(&mut self, target: &str, owner: u64, canvas: Canvas) {
+        let state = self.targets.entry(target.to_string()).or_default();
+        match state.contents.iter_mut().find(|(id, _)| *id == owner) {
+            Some((_, content)) if *content == canvas => return,
+            Some((_, content)) => *content = canvas,
+            None => state.contents.push((owner, canvas)),
+        }
+        state.generation += 1;
+        self.wake_watchers();
+    }
+
+    fn remove_content(&mut self, target: &str, owner: u64) {
+        let Some(state) = self.targets.get_mut(target) else {
+            return;
+        };
+        let len = state.contents.len();
+        state.contents.retain(|(id, _)| *id != owner);
+        if state.contents.len() != len {
+            state.generation += 1;
+            self.wake_watchers();
+        }
+    }
+}
+
+/// Keeps track of the portal targets and the content which portals have drawn into them.
+#[derive(Clone, Default)]
+pub(crate) struct PortalManager {
+    inner: Arc<Mutex<PortalManagerInner>>,
+}
+
+impl PortalManager {
+    fn target_rect(&self, name: &str) -> Option<LayoutRect> {
+        let inner = self.inner.lock().unwrap();
+        inner.targets.get(name).and_then(|state| state.rect)
+    }
+}
+
+struct UsePortalImpl {
+    manager: PortalManager,
+    owner: u64,
+    target: String,
+    rect: Option<LayoutRect>,
+}
+
+impl UsePortalImpl {
+    fn remove(&mut self) {
+        self.manager
+            .inner
+            .lock()
+            .unwrap()
+            .remove_content(&self.target, self.owner);
+    }
+}
+
+impl Hook for UsePortalImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.targets.get(&self.target).and_then(|state| state.rect) != self.rect {
+            Poll::Ready(())
+        } else {
+            inner.watchers.insert(self.owner, Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+
+    fn pre_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        let rect = self.rect.unwrap_or_default();
+        drawer.detach_children(Point {
+            x: rect.x as _,
+            y: rect.y as _,
+        });
+    }
+
+    fn post_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        if let Some(canvas) = drawer.take_detached_children() {
+            self.manager
+                .inner
+                .lock()
+                .unwrap()
+                .set_content(&self.target, self.owner, canvas);
+        }
+    }
+}
+
+impl Drop for UsePortalImpl {
+    fn drop(&mut self) {
+        self.remove();
+        self.manager
+            .inner
+            .lock()
+            .unwrap()
+            .watchers
+            .remove(&self.owner);
+    }
+}
+
+struct UsePortalTargetImpl {
+    manager: PortalManager,
+    watcher_id: u64,
+    name: String,
+    // The generation of the contents as of the most recent draw.
+    generation: Option<u64>,
+}
+
+impl UsePortalTargetImpl {
+    fn remove(&mut self) {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if let Some(state) = inner.targets.get_mut(&self.name) {
+            if state.rect.take().is_some() {
+                inner.wake_watchers();
+            }
+        }
+    }
+}
+
+impl Hook for UsePortalTargetImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.manager.inner.lock().unwrap();
+        let generation = inner
+            .targets
+            .get(&self.name)
+            .map(|state| state.generation)
+            .unwrap_or_default();
+        if self.generation.is_some_and(|drawn| drawn != generation) {
+            Poll::Ready(())
+        } else {
+            inner
+                .watchers
+                .insert(self.watcher_id, Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+
+    fn pre_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        let position = drawer.canvas_position();
+        let size = drawer.size();
+        let rect = LayoutRect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        };
+        let mut inner = self.manager.inner.lock().unwrap();
+        let state = inner.targets.entry(self.name.clone()).or_default();
+        let did_move = state.rect != Some(rect);
+        state.rect = Some(rect);
+        for (_, content) in &state.contents {
+            drawer.draw_canvas(content);
+        }
+        self.generation = Some(state.generation);
+        if did_move {
+            inner.wake_watchers();
+        }
+    }
+}
+
+impl Drop for UsePortalTargetImpl {
+    fn drop(&mut self) {
+        self.remove();
+        self.manager
+            .inner
+            .lock()
+            .unwrap()
+            .watchers
+            .remove(&self.watcher_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    struct Label(&'static str);
+
+    #[derive(Default, Props)]
+    struct DialogProps {
+        is_open: bool,
+    }
+
+    #[component]
+    fn Dialog(props: &DialogProps, hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let label = hooks.use_context::<Label>().0;
+        element! {
+            ScrollView(height: 1) {
+                Text(content: "x")
+                #(props.is_open.then(|| element! {
+                    Portal(target: "overlay") {
+                        Box(
+                            width: 100pct,
+                            height: 100pct,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                        ) {
+                            Text(content: label)
+                        }
+                    }
+                }))
+            }
+        }
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let is_open = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                ..
+            }) = event
+            {
+                is_open.set(false);
+            }
+        });
+        element! {
+            Layers(width: 7, height: 3) {
+                Box(flex_direction: FlexDirection::Column) {
+                    Text(content: "aaaaaaa")
+                    ContextProvider(value: Context::owned(Label("hi!"))) {
+                        Dialog(is_open: is_open.get())
+                    }
+                }
+                PortalTarget(name: "overlay")
+            }
+        }
+    }
+
+    #[test]
+    fn test_portal() {
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        // The content escapes the clipping of its parent, and is laid out within the target
+        // using the context at the portal's position.
+        assert_eq!(renderer.frame(), "aaaaaaa\nx hi!\n\n");
+
+        renderer.send_key(KeyCode::Char('c'));
+        assert_eq!(renderer.frame(), "aaaaaaa\nx\n\n");
+    }
+
+    #[test]
+    fn test_portal_without_target() {
+        assert_eq!(
+            element! {
+                Box {
+                    Text(content: "a")
+                    Portal(target: "overlay") {
+                        Text(content: "b")
+                    }
+                }
+            }
+            .to_string(),
+            "a\n"
+        );
+    }
+}
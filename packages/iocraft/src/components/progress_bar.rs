@@ -0,0 +1,169 @@
+use crate::{CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hooks, Props};
+use taffy::{AvailableSpace, Size};
+
+/// The glyphs used to render partially filled cells, indexed by the number of filled eighths.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// The glyph used to render completely filled cells.
+const FULL_BLOCK: char = '█';
+
+/// The props which can be passed to the [`ProgressBar`] component.
+#[derive(Default, Props)]
+pub struct ProgressBarProps {
+    /// The progress to display, from 0.0 to 1.0. Values outside of this range are clamped.
+    pub value: f32,
+
+    /// The color of the filled portion of the bar.
+    pub color: Option<Color>,
+
+    /// The width of the bar. If not given, the bar will grow to fill its container.
+    pub width: Option<u16>,
+}
+
+/// `ProgressBar` is a component that renders a horizontal progress bar.
+///
+/// The bar is one row tall, and the filled portion is drawn using block characters, with eighth
+/// blocks used to render fractional cells smoothly.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Box(width: 40, border_style: BorderStyle::Round) {
+///         ProgressBar(value: 0.42, color: Color::Green)
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ProgressBar {
+    value: f32,
+    style: CanvasTextStyle,
+}
+
+impl ProgressBar {
+    /// Renders the filled portion of a bar of the given width.
+    fn bar(value: f32, width: usize) -> String {
+        let value = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+        let eighths = (value * width as f32 * 8.0).round() as usize;
+        let mut bar = String::with_capacity(width * FULL_BLOCK.len_utf8());
+        for _ in 0..eighths / 8 {
+            bar.push(FULL_BLOCK);
+        }
+        let partial = eighths % 8;
+        if partial > 0 {
+            bar.push(PARTIAL_BLOCKS[partial]);
+        }
+        bar
+    }
+}
+
+impl Component for ProgressBar {
+    type Props<'a> = ProgressBarProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        self.value = props.value;
+        self.style = CanvasTextStyle {
+            color: props.color,
+            ..Default::default()
+        };
+        updater.set_layout_style(match props.width {
+            Some(width) => taffy::style::Style {
+                size: taffy::Size {
+                    width: taffy::Dimension::Length(width as _),
+                    height: taffy::Dimension::Length(1.0),
+                },
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            None => taffy::style::Style {
+                flex_grow: 1.0,
+                max_size: taffy::Size {
+                    width: taffy::Dimension::Auto,
+                    height: taffy::Dimension::Length(1.0),
+                },
+                ..Default::default()
+            },
+        });
+        updater.set_measure_func(Box::new(|known_size, available_space, _| Size {
+            width: known_size.width.unwrap_or(match available_space.width {
+                AvailableSpace::Definite(w) => w,
+                AvailableSpace::MinContent | AvailableSpace::MaxContent => 0.0,
+            }),
+            height: 1.0,
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let width = drawer.layout().size.width as usize;
+        let bar = Self::bar(self.value, width);
+        drawer.canvas().set_text(0, 0, &bar, self.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn filled_cells(value: f32, width: usize) -> (usize, Option<char>) {
+        let bar = ProgressBar::bar(value, width);
+        let full = bar.chars().filter(|&c| c == FULL_BLOCK).count();
+        let partial = bar.chars().find(|&c| c != FULL_BLOCK);
+        (full, partial)
+    }
+
+    #[test]
+    fn test_progress_bar_fill() {
+        assert_eq!(filled_cells(0.0, 10), (0, None));
+        assert_eq!(filled_cells(0.5, 10), (5, None));
+        assert_eq!(filled_cells(1.0, 10), (10, None));
+        assert_eq!(filled_cells(0.25, 10), (2, Some('▌')));
+        assert_eq!(filled_cells(0.01, 10), (0, Some('▏')));
+        assert_eq!(filled_cells(0.3333, 3), (1, None));
+
+        // Out-of-range values are clamped.
+        assert_eq!(filled_cells(-1.0, 10), (0, None));
+        assert_eq!(filled_cells(2.0, 10), (10, None));
+        assert_eq!(filled_cells(f32::NAN, 10), (0, None));
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(
+            element! {
+                Box(width: 10) {
+                    ProgressBar(value: 0.5)
+                }
+            }
+            .to_string(),
+            "█████\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(width: 20, flex_direction: FlexDirection::Column) {
+                    ProgressBar(value: 0.5, width: 4)
+                    ProgressBar(value: 0.25)
+                }
+            }
+            .to_string(),
+            "██\n█████\n"
+        );
+    }
+}
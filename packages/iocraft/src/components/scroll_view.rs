@@ -0,0 +1,424 @@
+use crate::{
+    hooks::{State, UseState, UseTerminalEvents},
+    AnyElement, CanvasTextStyle, Component, ComponentDrawer, ComponentUpdater, Handler, Hook,
+    Hooks, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind, Props, TerminalEvent,
+    Weight,
+};
+use iocraft_macros::with_layout_style_props;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use taffy::{FlexDirection, Overflow, Point};
+
+/// Describes the scroll position of a [`ScrollView`], which is passed to its `on_scroll` handler.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScrollViewState {
+    /// The number of rows that the content is scrolled by.
+    pub offset: u16,
+
+    /// The height of the content, including any padding.
+    pub content_height: u16,
+
+    /// The height of the area that the content is visible in.
+    pub viewport_height: u16,
+}
+
+impl ScrollViewState {
+    /// Returns the largest offset that the content can be scrolled by without scrolling past its
+    /// end.
+    pub fn max_offset(&self) -> u16 {
+        self.content_height.saturating_sub(self.viewport_height)
+    }
+}
+
+/// An indicator which a [`ScrollView`] can draw at an edge that its content is clipped at, to
+/// show that there's more to scroll to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowIndicator {
+    /// No indicator is drawn.
+    #[default]
+    None,
+    /// The row at the clipped edge is dimmed.
+    Dim,
+    /// An ellipsis is drawn at the end of the row at the clipped edge.
+    Ellipsis,
+}
+
+/// The props which can be passed to the [`ScrollView`] component.
+#[with_layout_style_props]
+#[derive(Default, Props)]
+pub struct ScrollViewProps<'a> {
+    /// The elements to render inside of the scroll view.
+    pub children: Vec<AnyElement<'a>>,
+
+    /// True if the scroll view has focus and should respond to the keyboard.
+    pub has_focus: bool,
+
+    /// The handler to invoke when the scroll position, content height, or viewport height changes.
+    /// This can be used to render a scrollbar or a position indicator.
+    pub on_scroll: Handler<'static, ScrollViewState>,
+
+    /// The indicator to draw at the top or bottom edge while there's content clipped beyond it.
+    pub overflow_indicator: OverflowIndicator,
+}
+
+/// `ScrollView` is a component which clips its children to its bounds and allows them to be
+/// scrolled vertically.
+///
+/// Children are laid out in a column. Typically, you'll want to give the scroll view a height or
+/// allow it to grow, so that it doesn't simply expand to fit its content.
+///
+/// While it has focus, the content can be scrolled with the up and down arrow keys, the page up
+/// and page down keys, and the home and end keys. In fullscreen mode, it can also be scrolled
+/// with the mouse wheel. The offset is clamped so that the content can't be scrolled past its
+/// end, even when the content or the scroll view is resized.
+///
+/// To make it clear that there's more content than is visible, an
+/// [`OverflowIndicator`] can be drawn at the edges that the content is clipped at.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # #[component]
+/// # fn Log(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+/// let position = hooks.use_state(ScrollViewState::default);
+///
+/// element! {
+///     Box(flex_direction: FlexDirection::Column, border_style: BorderStyle::Round) {
+///         ScrollView(
+///             height: 10,
+///             has_focus: true,
+///             on_scroll: move |state| position.set(state),
+///         ) {
+///             #((0..100).map(|i| element!(Text(content: format!("line {}", i)))))
+///         }
+///         Text(content: format!(
+///             "{}/{}",
+///             position.get().offset,
+///             position.get().max_offset(),
+///         ))
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ScrollView {
+    offset: Option<State<u16>>,
+    state: Arc<Mutex<ScrollViewState>>,
+    has_focus: Arc<AtomicBool>,
+    handler: Handler<'static, ScrollViewState>,
+    reported_state: Option<ScrollViewState>,
+}
+
+impl ScrollView {
+    fn scroll_by(
+        offset: State<u16>,
+        state: &Mutex<ScrollViewState>,
+        f: impl FnOnce(u16, ScrollViewState) -> u16,
+    ) {
+        let state = *state.lock().unwrap();
+        let new_offset = f(offset.get(), state).min(state.max_offset());
+        if new_offset != offset.get() {
+            offset.set(new_offset);
+        }
+    }
+}
+
+impl Component for ScrollView {
+    type Props<'a> = ScrollViewProps<'a>;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        self.handler = props.on_scroll.take();
+        self.has_focus.store(props.has_focus, Ordering::SeqCst);
+
+        let offset = hooks.use_state(|| 0u16);
+        self.offset = Some(offset);
+        hooks.use_terminal_events({
+            let state = self.state.clone();
+            let has_focus = self.has_focus.clone();
+            move |event| match event {
+                TerminalEvent::Key(KeyEvent { code, kind, .. })
+                    if kind != KeyEventKind::Release && has_focus.load(Ordering::SeqCst) =>
+                {
+                    Self::scroll_by(offset, &state, |offset, state| {
+                        let page = state.viewport_height.max(1);
+                        match code {
+                            KeyCode::Up => offset.saturating_sub(1),
+                            KeyCode::Down => offset.saturating_add(1),
+                            KeyCode::PageUp => offset.saturating_sub(page),
+                            KeyCode::PageDown => offset.saturating_add(page),
+                            KeyCode::Home => 0,
+                            KeyCode::End => state.max_offset(),
+                            _ => offset,
+                        }
+                    });
+                }
+                _ => {}
+            }
+        });
+        hooks.use_local_terminal_events({
+            let state = self.state.clone();
+            move |event| {
+                if let TerminalEvent::Mouse(MouseEvent { kind, .. }) = event {
+                    Self::scroll_by(offset, &state, |offset, _| match kind {
+                        MouseEventKind::ScrollUp => offset.saturating_sub(1),
+                        MouseEventKind::ScrollDown => offset.saturating_add(1),
+                        _ => offset,
+                    });
+                }
+            }
+        });
+
+        let indicator = hooks.use_hook(|| UseOverflowIndicatorImpl {
+            indicator: OverflowIndicator::None,
+            state: self.state.clone(),
+        });
+        indicator.indicator = props.overflow_indicator;
+
+        let mut style: taffy::style::Style = props.layout_style().into();
+        style.flex_direction = FlexDirection::Column;
+        // This allows the scroll view to be smaller than its content.
+        style.overflow.y = Overflow::Scroll;
+        updater.set_layout_style(style);
+        updater.set_intrinsic_sizes(props.layout_style().intrinsic_sizes());
+        updater.update_children(props.children.iter_mut(), None);
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let Some(offset) = self.offset else {
+            return;
+        };
+        let mut state = ScrollViewState {
+            offset: offset.get(),
+            content_height: drawer.content_size().height,
+            viewport_height: drawer.size().height,
+        };
+        if state.offset > state.max_offset() {
+            // The content or viewport has changed size, so the offset needs to be brought back
+            // into range.
+            state.offset = state.max_offset();
+            offset.set(state.offset);
+        }
+        *self.state.lock().unwrap() = state;
+        drawer.scroll_children(Point {
+            x: 0,
+            y: state.offset,
+        });
+
+        if self.reported_state != Some(state) {
+            self.reported_state = Some(state);
+            self.handler.invoke(state);
+        }
+    }
+}
+
+/// Draws the overflow indicator. This is done by a hook since it needs to happen after the
+/// children are drawn.
+struct UseOverflowIndicatorImpl {
+    indicator: OverflowIndicator,
+    state: Arc<Mutex<ScrollViewState>>,
+}
+
+impl Hook for UseOverflowIndicatorImpl {
+    fn post_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        if self.indicator == OverflowIndicator::None {
+            return;
+        }
+        let state = *self.state.lock().unwrap();
+        let layout = drawer.layout();
+        let left = layout.border.left as usize;
+        let width =
+            (layout.size.width - layout.border.left - layout.border.right).max(0.0) as usize;
+        let top = layout.border.top as usize;
+        let height =
+            (layout.size.height - layout.border.top - layout.border.bottom).max(0.0) as usize;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let mut rows = Vec::with_capacity(2);
+        if state.offset > 0 {
+            rows.push(top);
+        }
+        if state.offset < state.max_offset() {
+            rows.push(top + height - 1);
+        }
+        let mut canvas = drawer.canvas();
+        for y in rows {
+            match self.indicator {
+                OverflowIndicator::None => {}
+                OverflowIndicator::Dim => canvas.map_text_style(left, y, width, 1, |style| {
+                    style.weight = Weight::Light;
+                }),
+                OverflowIndicator::Ellipsis => canvas.set_text(
+                    (left + width - 1) as _,
+                    y as _,
+                    "…",
+                    CanvasTextStyle::default(),
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Default, Props)]
+    struct LogProps {
+        lines: i32,
+    }
+
+    #[component]
+    fn Log(mut hooks: Hooks, props: &LogProps) -> impl Into<AnyElement<'static>> {
+        let position = hooks.use_state(ScrollViewState::default);
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                ScrollView(
+                    height: 3,
+                    has_focus: true,
+                    on_scroll: move |state| position.set(state),
+                ) {
+                    #((0..props.lines).map(|i| element!(Text(content: format!("line {}", i)))))
+                }
+                Text(content: format!(
+                    "{} of {}, {} visible",
+                    position.get().offset,
+                    position.get().content_height,
+                    position.get().viewport_height,
+                ))
+            }
+        }
+    }
+
+    #[test]
+    fn test_scroll_view() {
+        let mut element = element!(Log(lines: 10));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(
+            renderer.frame(),
+            "line 0\nline 1\nline 2\n0 of 10, 3 visible\n"
+        );
+
+        renderer.send_key(KeyCode::Down);
+        assert_eq!(
+            renderer.frame(),
+            "line 1\nline 2\nline 3\n1 of 10, 3 visible\n"
+        );
+
+        renderer.send_key(KeyCode::PageDown);
+        assert_eq!(
+            renderer.frame(),
+            "line 4\nline 5\nline 6\n4 of 10, 3 visible\n"
+        );
+
+        // The offset is clamped to the end of the content.
+        renderer.send_key(KeyCode::PageDown);
+        renderer.send_key(KeyCode::PageDown);
+        assert_eq!(
+            renderer.frame(),
+            "line 7\nline 8\nline 9\n7 of 10, 3 visible\n"
+        );
+
+        renderer.send_key(KeyCode::Up);
+        renderer.send_key(KeyCode::Home);
+        assert_eq!(
+            renderer.frame(),
+            "line 0\nline 1\nline 2\n0 of 10, 3 visible\n"
+        );
+
+        renderer.send_key(KeyCode::End);
+        assert_eq!(
+            renderer.frame(),
+            "line 7\nline 8\nline 9\n7 of 10, 3 visible\n"
+        );
+    }
+
+    #[derive(Default, Props)]
+    struct IndicatedLogProps {
+        indicator: OverflowIndicator,
+    }
+
+    #[component]
+    fn IndicatedLog(props: &IndicatedLogProps) -> impl Into<AnyElement<'static>> {
+        element! {
+            ScrollView(height: 3, has_focus: true, overflow_indicator: props.indicator) {
+                #((0..5).map(|i| element!(Text(content: format!("line {}", i)))))
+            }
+        }
+    }
+
+    #[test]
+    fn test_scroll_view_overflow_indicator() {
+        let mut element = element!(IndicatedLog(indicator: OverflowIndicator::Ellipsis));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "line 0\nline 1\nline …\n");
+
+        renderer.send_key(KeyCode::Down);
+        assert_eq!(renderer.frame(), "line …\nline 2\nline …\n");
+
+        renderer.send_key(KeyCode::End);
+        assert_eq!(renderer.frame(), "line …\nline 3\nline 4\n");
+
+        let mut element = element!(IndicatedLog(indicator: OverflowIndicator::Dim));
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::End);
+        let canvas = renderer.canvas();
+        let mut ansi = Vec::new();
+        canvas.write_ansi(&mut ansi).unwrap();
+        let ansi = String::from_utf8(ansi).unwrap();
+        let lines = ansi.lines().collect::<Vec<_>>();
+        // Only the top row, beyond which content is clipped, is dimmed.
+        assert!(lines[0].contains("\x1b[2m"));
+        assert!(!lines[1].contains("\x1b[2m"));
+        assert!(!lines[2].contains("\x1b[2m"));
+        assert_eq!(canvas.to_string(), "line 2\nline 3\nline 4\n");
+    }
+
+    #[test]
+    fn test_scroll_view_short_content() {
+        let mut element = element!(Log(lines: 2));
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Down);
+        assert_eq!(renderer.frame(), "line 0\nline 1\n\n0 of 2, 3 visible\n");
+    }
+
+    #[test]
+    fn test_scroll_view_mouse() {
+        let mut element = element!(Log(lines: 10));
+        let mut renderer = TestRenderer::new(&mut element);
+        let scroll = |kind, row| {
+            TerminalEvent::Mouse(MouseEvent {
+                column: 0,
+                row,
+                kind,
+                modifiers: KeyModifiers::empty(),
+            })
+        };
+        renderer.send(scroll(MouseEventKind::ScrollDown, 1));
+        renderer.send(scroll(MouseEventKind::ScrollDown, 1));
+        assert_eq!(
+            renderer.frame(),
+            "line 2\nline 3\nline 4\n2 of 10, 3 visible\n"
+        );
+
+        // Scrolling outside of the scroll view does nothing.
+        renderer.send(scroll(MouseEventKind::ScrollUp, 3));
+        assert_eq!(
+            renderer.frame(),
+            "line 2\nline 3\nline 4\n2 of 10, 3 visible\n"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{
+    hooks::{UseState, UseTerminalEvents},
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Handler, Hooks, KeyCode,
+    KeyEvent, KeyEventKind, Props, TerminalEvent,
+};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use taffy::Size;
+use unicode_width::UnicodeWidthStr;
+
+/// The props which can be passed to the [`Select`] component.
+#[derive(Default, Props)]
+pub struct SelectProps {
+    /// The items to choose from, one per row.
+    pub items: Vec<String>,
+
+    /// The index of the item which is highlighted when the component is first rendered. If this
+    /// changes, the highlight moves to the new index.
+    pub selected: usize,
+
+    /// The handler to invoke with the index of the highlighted item when Enter is pressed.
+    pub on_select: Handler<'static, usize>,
+
+    /// True if the select has focus and should respond to the keyboard.
+    pub has_focus: bool,
+
+    /// The color to make the items.
+    pub color: Option<Color>,
+
+    /// The color to make the highlighted item. Defaults to `color`.
+    pub highlight_color: Option<Color>,
+
+    /// The text to draw before the highlighted item. Other items are indented by its width.
+    /// Defaults to `"> "`.
+    pub marker: Option<String>,
+}
+
+/// `Select` is a component that lets the user pick one item from a list.
+///
+/// One item is highlighted at a time, and is drawn with a marker before it. While the select has
+/// focus, the highlighted item is also drawn in reverse video, the Up and Down keys move the
+/// highlight, wrapping around at the ends of the list, and pressing Enter invokes the `on_select`
+/// handler with the index of the highlighted item. If there are no items, nothing is drawn and
+/// the handler is never invoked.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # #[component]
+/// # fn Picker(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+/// let flavor = hooks.use_state(|| None);
+///
+/// element! {
+///     Box(flex_direction: FlexDirection::Column) {
+///         Select(
+///             items: vec!["Vanilla".to_string(), "Chocolate".to_string()],
+///             has_focus: true,
+///             on_select: move |index| flavor.set(Some(index)),
+///         )
+///         Text(content: match flavor.get() {
+///             Some(index) => format!("you picked item {}", index),
+///             None => "pick a flavor".to_string(),
+///         })
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Select {
+    items: Vec<String>,
+    highlighted: usize,
+    marker: String,
+    has_focus: Arc<AtomicBool>,
+    item_count: Arc<AtomicUsize>,
+    handler: Arc<Mutex<Handler<'static, usize>>>,
+    last_selected: Option<usize>,
+    style: CanvasTextStyle,
+    highlight_style: CanvasTextStyle,
+}
+
+impl Component for Select {
+    type Props<'a> = SelectProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        *self.handler.lock().unwrap() = props.on_select.take();
+        self.has_focus.store(props.has_focus, Ordering::SeqCst);
+        self.item_count.store(props.items.len(), Ordering::SeqCst);
+
+        let highlighted = hooks.use_state(|| props.selected);
+        if self
+            .last_selected
+            .is_some_and(|last| last != props.selected)
+        {
+            highlighted.set(props.selected);
+        }
+        self.last_selected = Some(props.selected);
+
+        hooks.use_terminal_events({
+            let handler = self.handler.clone();
+            let has_focus = self.has_focus.clone();
+            let item_count = self.item_count.clone();
+            move |event| match event {
+                TerminalEvent::Key(KeyEvent { code, kind, .. })
+                    if kind != KeyEventKind::Release && has_focus.load(Ordering::SeqCst) =>
+                {
+                    let count = item_count.load(Ordering::SeqCst);
+                    if count == 0 {
+                        return;
+                    }
+                    let index = highlighted.get().min(count - 1);
+                    match code {
+                        KeyCode::Up => highlighted.set((index + count - 1) % count),
+                        KeyCode::Down => highlighted.set((index + 1) % count),
+                        KeyCode::Enter if kind == KeyEventKind::Press => {
+                            handler.lock().unwrap().invoke(index)
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        });
+
+        self.items = props.items.clone();
+        self.highlighted = highlighted.get();
+        self.marker = props.marker.clone().unwrap_or_else(|| "> ".to_string());
+        self.style = CanvasTextStyle {
+            color: props.color,
+            ..Default::default()
+        };
+        self.highlight_style = CanvasTextStyle {
+            color: props.highlight_color.or(props.color),
+            reverse: props.has_focus,
+            ..Default::default()
+        };
+
+        let marker_width = self.marker.width();
+        let width = self
+            .items
+            .iter()
+            .map(|item| marker_width + item.width())
+            .max()
+            .unwrap_or(0);
+        let height = self.items.len();
+        updater.set_measure_func(Box::new(move |_, _, _| Size {
+            width: width as _,
+            height: height as _,
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let marker_width = self.marker.width() as isize;
+        let highlighted = self.highlighted.min(self.items.len().saturating_sub(1));
+        let mut canvas = drawer.canvas();
+        for (y, item) in self.items.iter().enumerate() {
+            if y == highlighted {
+                canvas.set_text(0, y as _, &self.marker, self.style);
+                canvas.set_text(marker_width, y as _, item, self.highlight_style);
+            } else {
+                canvas.set_text(marker_width, y as _, item, self.style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Default, Props)]
+    struct PickerProps {
+        items: Vec<String>,
+        selected: usize,
+    }
+
+    #[component]
+    fn Picker(mut hooks: Hooks, props: &PickerProps) -> impl Into<AnyElement<'static>> {
+        let picked = hooks.use_state(|| None);
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Select(
+                    items: props.items.clone(),
+                    selected: props.selected,
+                    has_focus: true,
+                    on_select: move |index| picked.set(Some(index)),
+                )
+                Text(content: format!("picked: {:?}", picked.get()))
+            }
+        }
+    }
+
+    fn items() -> Vec<String> {
+        vec!["red".to_string(), "green".to_string(), "blue".to_string()]
+    }
+
+    #[test]
+    fn test_select() {
+        assert_eq!(
+            element!(Select(items: items(), selected: 1usize)).to_string(),
+            "  red\n> green\n  blue\n"
+        );
+        assert_eq!(
+            element!(Select(items: items(), marker: "* ".to_string())).to_string(),
+            "* red\n  green\n  blue\n"
+        );
+    }
+
+    #[test]
+    fn test_select_keyboard() {
+        let mut element = element!(Picker(items: items()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "> red\n  green\n  blue\npicked: None\n");
+
+        renderer.send_key(KeyCode::Down);
+        renderer.send_key(KeyCode::Down);
+        assert_eq!(renderer.frame(), "  red\n  green\n> blue\npicked: None\n");
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(
+            renderer.frame(),
+            "  red\n  green\n> blue\npicked: Some(2)\n"
+        );
+
+        // The highlight wraps around at both ends.
+        renderer.send_key(KeyCode::Down);
+        assert_eq!(
+            renderer.frame(),
+            "> red\n  green\n  blue\npicked: Some(2)\n"
+        );
+        renderer.send_key(KeyCode::Up);
+        renderer.send_key(KeyCode::Up);
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(
+            renderer.frame(),
+            "  red\n> green\n  blue\npicked: Some(1)\n"
+        );
+    }
+
+    #[test]
+    fn test_select_empty() {
+        assert_eq!(element!(Select).to_string(), "");
+
+        let mut element = element!(Picker);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Down);
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "picked: None\n");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{Component, ComponentUpdater, Hooks, Props};
+
+/// The props which can be passed to the [`Spacer`] component.
+#[derive(Props)]
+pub struct SpacerProps {
+    /// How much of the available space the spacer takes, relative to its siblings. Defaults to
+    /// 1.0, which splits the space evenly with any other spacers.
+    #[props(default = 1.0)]
+    pub flex_grow: f32,
+}
+
+/// `Spacer` is a component that takes up the available space along its parent's main axis,
+/// pushing its siblings apart. It doesn't draw anything.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Box(width: 40) {
+///         Text(content: "left")
+///         Spacer
+///         Text(content: "right")
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Spacer;
+
+impl Component for Spacer {
+    type Props<'a> = SpacerProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        updater.set_layout_style(taffy::style::Style {
+            flex_grow: props.flex_grow,
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_spacer() {
+        assert_eq!(element!(Spacer).to_string(), "");
+
+        assert_eq!(
+            element! {
+                Box(width: 10) {
+                    Text(content: "a")
+                    Spacer
+                    Text(content: "b")
+                }
+            }
+            .to_string(),
+            "a        b\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(height: 3, flex_direction: FlexDirection::Column) {
+                    Text(content: "a")
+                    Spacer
+                    Text(content: "b")
+                }
+            }
+            .to_string(),
+            "a\n\nb\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(width: 11) {
+                    Text(content: "a")
+                    Spacer
+                    Text(content: "b")
+                    Spacer(flex_grow: 3.0)
+                    Text(content: "c")
+                }
+            }
+            .to_string(),
+            "a  b      c\n"
+        );
+    }
+}
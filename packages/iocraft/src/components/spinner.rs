@@ -0,0 +1,149 @@
+use crate::{
+    hooks::{UseInterval, UseState},
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hooks, Props,
+};
+use std::time::Duration;
+use taffy::Size;
+use unicode_width::UnicodeWidthStr;
+
+/// The frames used by a [`Spinner`] if none are given.
+pub const DEFAULT_SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The interval used by a [`Spinner`] if none is given.
+pub const DEFAULT_SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// The props which can be passed to the [`Spinner`] component.
+#[derive(Default, Props)]
+pub struct SpinnerProps {
+    /// The frames of the animation. If empty, [`DEFAULT_SPINNER_FRAMES`] is used.
+    pub frames: Vec<String>,
+
+    /// The time between frames. If not given, [`DEFAULT_SPINNER_INTERVAL`] is used. Intervals
+    /// shorter than a millisecond are treated as one millisecond.
+    pub interval: Option<Duration>,
+
+    /// The color to make the spinner.
+    pub color: Option<Color>,
+}
+
+/// `Spinner` is a component that renders an animation to indicate indeterminate progress.
+///
+/// The animation is advanced by a timer on the render loop, so it never blocks the handling of
+/// input.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Box {
+///         Spinner(color: Color::Cyan)
+///         Text(content: " Loading...")
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Spinner {
+    frame: String,
+    style: CanvasTextStyle,
+}
+
+impl Spinner {
+    fn frame(frames: &[String], index: usize) -> String {
+        if frames.is_empty() {
+            DEFAULT_SPINNER_FRAMES[index % DEFAULT_SPINNER_FRAMES.len()].to_string()
+        } else {
+            frames[index % frames.len()].clone()
+        }
+    }
+}
+
+impl Component for Spinner {
+    type Props<'a> = SpinnerProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        mut hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        let index = {
+            let mut hooks = hooks.with_context_stack(updater.component_context_stack());
+            let index = hooks.use_state(|| 0usize);
+            hooks.use_interval(
+                props.interval.unwrap_or(DEFAULT_SPINNER_INTERVAL),
+                move || index.set(index.get().wrapping_add(1)),
+            );
+            index
+        };
+
+        self.frame = Self::frame(&props.frames, index.get());
+        self.style = CanvasTextStyle {
+            color: props.color,
+            ..Default::default()
+        };
+
+        let width = self.frame.width();
+        updater.set_measure_func(Box::new(move |_, _, _| Size {
+            width: width as _,
+            height: 1.0,
+        }));
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        drawer.canvas().set_text(0, 0, &self.frame, self.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use macro_rules_attribute::apply;
+    use smol_macros::test;
+
+    #[test]
+    fn test_spinner() {
+        assert_eq!(element!(Spinner).to_string(), "⠋\n");
+        assert_eq!(
+            element!(Spinner(frames: vec!["ab".to_string(), "cd".to_string()])).to_string(),
+            "ab\n"
+        );
+        assert_eq!(Spinner::frame(&[], 11), "⠙");
+        assert_eq!(Spinner::frame(&["a".into(), "b".into()], 3), "b");
+    }
+
+    #[component]
+    fn MySpinner(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let ticks = hooks.use_state(|| 0);
+        hooks.use_interval(Duration::from_millis(5), move || ticks.set(ticks.get() + 1));
+
+        if ticks.get() >= 5 {
+            system.exit();
+        }
+
+        element! {
+            Spinner(
+                frames: (0..100).map(|i| i.to_string()).collect::<Vec<_>>(),
+                interval: Duration::from_millis(1),
+            )
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_spinner_animation() {
+        let canvases = mock_terminal_render_loop(element!(MySpinner))
+            .await
+            .unwrap();
+        let actual = canvases.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(actual.first().unwrap(), "0\n");
+        assert_ne!(actual.last().unwrap(), "0\n");
+    }
+}
@@ -0,0 +1,457 @@
+use crate::{
+    components::{Text, TextAlign},
+    CanvasSubviewMut, CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hooks,
+    Props, Weight,
+};
+use std::borrow::Cow;
+use taffy::{AvailableSpace, Size};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The width of a column in a [`Table`] component.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TableColumnWidth {
+    /// The column is as wide as its widest cell, including the header. This is the default.
+    #[default]
+    Auto,
+    /// The column is exactly the given number of cells wide.
+    Fixed(u16),
+    /// The column takes the given percentage of the table's width, not counting the separators.
+    /// If the table's width isn't constrained, the column is as wide as its widest cell.
+    Percent(f32),
+    /// The column takes a share of the space left over by the other columns, proportional to the
+    /// given weight. If the table's width isn't constrained, the column is as wide as its widest
+    /// cell.
+    Flex(f32),
+}
+
+/// Describes a column of a [`Table`] component.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableColumn {
+    /// The text to display in the header row. If none of the columns have a header, the header
+    /// row is omitted.
+    pub header: String,
+
+    /// The width of the column.
+    pub width: TableColumnWidth,
+
+    /// The alignment of the cells in the column, including the header.
+    pub align: TextAlign,
+}
+
+impl TableColumn {
+    /// Creates a new left-aligned, auto-sized column with the given header.
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the width of the column.
+    pub fn width(mut self, width: TableColumnWidth) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the alignment of the cells in the column.
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+}
+
+/// The props which can be passed to the [`Table`] component.
+#[derive(Default, Props)]
+pub struct TableProps {
+    /// The columns of the table.
+    pub columns: Vec<TableColumn>,
+
+    /// The rows of the table. Each row holds one cell per column. Missing cells are left blank,
+    /// and cells beyond the number of columns are ignored.
+    pub rows: Vec<Vec<String>>,
+
+    /// The text to draw between adjacent columns. Defaults to a single space.
+    pub separator: Option<String>,
+
+    /// If true, a horizontal line is drawn between the header row and the rest of the table.
+    pub header_separator: bool,
+
+    /// The color to make the text.
+    pub color: Option<Color>,
+}
+
+/// `Table` is a component that renders rows of text in aligned columns.
+///
+/// Columns are sized to fit their content by default, but can also be given a fixed width, a
+/// percentage of the table's width, or a share of the space left over by the other columns. If the
+/// table is too wide to fit, the widest columns are shrunk first, and any cells that no longer fit
+/// are truncated with an ellipsis.
+///
+/// The header row is drawn in bold.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Table(
+///         columns: vec![
+///             TableColumn::new("Name"),
+///             TableColumn::new("Size").align(TextAlign::Right),
+///             TableColumn::new("Description").width(TableColumnWidth::Flex(1.0)),
+///         ],
+///         rows: vec![
+///             vec!["README.md".to_string(), "2 KB".to_string(), "Project overview".to_string()],
+///             vec!["Cargo.toml".to_string(), "512 B".to_string(), "Package manifest".to_string()],
+///         ],
+///         separator: " │ ".to_string(),
+///         header_separator: true,
+///     )
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Table {
+    columns: Vec<TableColumn>,
+    rows: Vec<Vec<String>>,
+    separator: String,
+    header_separator: bool,
+    color: Option<Color>,
+}
+
+impl Table {
+    fn has_header(columns: &[TableColumn]) -> bool {
+        columns.iter().any(|column| !column.header.is_empty())
+    }
+
+    fn height(columns: &[TableColumn], rows: &[Vec<String>], header_separator: bool) -> usize {
+        let header_height = match (Self::has_header(columns), header_separator) {
+            (false, _) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+        };
+        header_height + rows.len()
+    }
+
+    /// Computes the width of each column, given the width available to the whole table,
+    /// including the separators.
+    fn column_widths(
+        columns: &[TableColumn],
+        rows: &[Vec<String>],
+        separator: &str,
+        available_width: Option<usize>,
+    ) -> Vec<usize> {
+        let content_widths = columns.iter().enumerate().map(|(i, column)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.width())
+                .fold(column.header.width(), usize::max)
+        });
+        let separators_width = separator.width() * columns.len().saturating_sub(1);
+        let budget = available_width.map(|width| width.saturating_sub(separators_width));
+        let mut widths: Vec<usize> = columns
+            .iter()
+            .zip(content_widths)
+            .map(|(column, content_width)| match (column.width, budget) {
+                (TableColumnWidth::Auto, _) => content_width,
+                (TableColumnWidth::Fixed(width), _) => width as _,
+                (TableColumnWidth::Percent(percent), Some(budget)) => {
+                    (budget as f32 * percent.clamp(0.0, 100.0) / 100.0) as _
+                }
+                (TableColumnWidth::Flex(_), Some(_)) => 0,
+                (TableColumnWidth::Percent(_) | TableColumnWidth::Flex(_), None) => content_width,
+            })
+            .collect();
+        let Some(budget) = budget else {
+            return widths;
+        };
+
+        // Give any leftover space to the flex columns, with the remainder from rounding going to
+        // the last of them.
+        let remaining = budget.saturating_sub(widths.iter().sum());
+        let total_weight: f32 = columns
+            .iter()
+            .filter_map(|column| match column.width {
+                TableColumnWidth::Flex(weight) => Some(weight.max(0.0)),
+                _ => None,
+            })
+            .sum();
+        if total_weight > 0.0 {
+            let mut distributed = 0;
+            let mut last_flex = None;
+            for (i, column) in columns.iter().enumerate() {
+                if let TableColumnWidth::Flex(weight) = column.width {
+                    let share = (remaining as f32 * weight.max(0.0) / total_weight) as usize;
+                    widths[i] += share;
+                    distributed += share;
+                    last_flex = Some(i);
+                }
+            }
+            if let Some(i) = last_flex {
+                widths[i] += remaining - distributed;
+            }
+        }
+
+        // If the table is too wide, shrink the widest columns until it fits, leaving fixed-width
+        // columns alone for as long as possible.
+        let mut excess = widths.iter().sum::<usize>().saturating_sub(budget);
+        while excess > 0 {
+            let widest = |fixed: bool| {
+                (0..widths.len())
+                    .filter(|&i| fixed || !matches!(columns[i].width, TableColumnWidth::Fixed(_)))
+                    .filter(|&i| widths[i] > 0)
+                    .max_by_key(|&i| (widths[i], usize::MAX - i))
+            };
+            let Some(i) = widest(false).or_else(|| widest(true)) else {
+                break;
+            };
+            widths[i] -= 1;
+            excess -= 1;
+        }
+        widths
+    }
+
+    /// Truncates the text to the given width, replacing the end with an ellipsis if it doesn't
+    /// fit.
+    fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+        if text.width() <= width {
+            return Cow::Borrowed(text);
+        }
+        let mut truncated = String::new();
+        if width > 0 {
+            let mut truncated_width = 0;
+            for c in text.chars() {
+                let c_width = c.width().unwrap_or(0);
+                if truncated_width + c_width > width - 1 {
+                    break;
+                }
+                truncated.push(c);
+                truncated_width += c_width;
+            }
+            truncated.push('…');
+        }
+        Cow::Owned(truncated)
+    }
+}
+
+impl Component for Table {
+    type Props<'a> = TableProps;
+
+    fn new(_props: &Self::Props<'_>) -> Self {
+        Self::default()
+    }
+
+    fn update(
+        &mut self,
+        props: &mut Self::Props<'_>,
+        _hooks: Hooks,
+        updater: &mut ComponentUpdater,
+    ) {
+        self.columns = props.columns.clone();
+        self.rows = props.rows.clone();
+        self.separator = props.separator.clone().unwrap_or_else(|| " ".to_string());
+        self.header_separator = props.header_separator;
+        self.color = props.color;
+
+        {
+            let columns = self.columns.clone();
+            let rows = self.rows.clone();
+            let separator = self.separator.clone();
+            let header_separator = self.header_separator;
+            updater.set_measure_func(Box::new(move |known_size, available_space, _| {
+                let available_width = match known_size.width {
+                    Some(w) => Some(w as usize),
+                    None => match available_space.width {
+                        AvailableSpace::Definite(w) => Some(w as usize),
+                        AvailableSpace::MaxContent => None,
+                        AvailableSpace::MinContent => Some(0),
+                    },
+                };
+                let widths = Self::column_widths(&columns, &rows, &separator, available_width);
+                let separators_width = separator.width() * widths.len().saturating_sub(1);
+                Size {
+                    width: (widths.iter().sum::<usize>() + separators_width) as _,
+                    height: Self::height(&columns, &rows, header_separator) as _,
+                }
+            }));
+        }
+    }
+
+    fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+        let width = drawer.layout().size.width as usize;
+        let widths = Self::column_widths(&self.columns, &self.rows, &self.separator, Some(width));
+        let style = CanvasTextStyle {
+            color: self.color,
+            ..Default::default()
+        };
+        let mut canvas = drawer.canvas();
+        let draw_line = |canvas: &mut CanvasSubviewMut,
+                         y: usize,
+                         cells: &mut dyn Iterator<Item = &str>,
+                         style| {
+            let mut x = 0;
+            for (i, (column, &column_width)) in self.columns.iter().zip(&widths).enumerate() {
+                if i > 0 {
+                    canvas.set_text(x as _, y as _, &self.separator, style);
+                    x += self.separator.width();
+                }
+                let cell = Self::truncate(cells.next().unwrap_or_default(), column_width);
+                let offset = Text::align_offset(cell.width(), column.align, column_width);
+                canvas.set_text((x + offset) as _, y as _, &cell, style);
+                x += column_width;
+            }
+        };
+
+        let mut y = 0;
+        if Self::has_header(&self.columns) {
+            let header_style = CanvasTextStyle {
+                weight: Weight::Bold,
+                ..style
+            };
+            draw_line(
+                &mut canvas,
+                y,
+                &mut self.columns.iter().map(|column| column.header.as_str()),
+                header_style,
+            );
+            y += 1;
+            if self.header_separator {
+                let line = "─".repeat(width);
+                canvas.set_text(0, y as _, &line, style);
+                y += 1;
+            }
+        }
+        for row in &self.rows {
+            draw_line(
+                &mut canvas,
+                y,
+                &mut row.iter().map(|cell| cell.as_str()),
+                style,
+            );
+            y += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(element!(Table).to_string(), "");
+
+        assert_eq!(
+            element! {
+                Table(
+                    columns: vec![
+                        TableColumn::new("Name"),
+                        TableColumn::new("Qty").align(TextAlign::Right),
+                        TableColumn::new("Notes").align(TextAlign::Center),
+                    ],
+                    rows: rows(&[
+                        &["apple", "3", "red"],
+                        &["kiwi", "1200", "fuzzy"],
+                        &["🍉", "7"],
+                    ]),
+                    separator: " | ".to_string(),
+                )
+            }
+            .to_string(),
+            "Name  |  Qty | Notes\napple |    3 |  red\nkiwi  | 1200 | fuzzy\n🍉    |    7 | \n"
+        );
+
+        assert_eq!(
+            element! {
+                Table(
+                    columns: vec![
+                        TableColumn::new("A").width(TableColumnWidth::Fixed(3)),
+                        TableColumn::new("B"),
+                    ],
+                    rows: rows(&[&["x", "y"]]),
+                    header_separator: true,
+                )
+            }
+            .to_string(),
+            "A   B\n─────\nx   y\n"
+        );
+    }
+
+    #[test]
+    fn test_table_sizing() {
+        let columns = vec![
+            TableColumn::new("Name").width(TableColumnWidth::Fixed(6)),
+            TableColumn::new("Description").width(TableColumnWidth::Flex(1.0)),
+            TableColumn::new("Owner"),
+        ];
+        let rows = rows(&[
+            &["build", "Compiles the project", "ci"],
+            &["deploy", "Ships it", "ops"],
+        ]);
+
+        // Flex columns take up the leftover space.
+        assert_eq!(
+            element! {
+                Box(width: 30) {
+                    Table(columns: columns.clone(), rows: rows.clone())
+                }
+            }
+            .to_string(),
+            "Name   Description       Owner\nbuild  Compiles the pro… ci\ndeploy Ships it          ops\n"
+        );
+
+        // Columns are truncated when there isn't enough space.
+        assert_eq!(
+            element! {
+                Box(width: 20) {
+                    Table(columns: columns, rows: rows)
+                }
+            }
+            .to_string(),
+            "Name   Descri… Owner\nbuild  Compil… ci\ndeploy Ships … ops\n"
+        );
+    }
+
+    #[test]
+    fn test_table_mixed_widths() {
+        let columns = vec![
+            TableColumn::new("Id").width(TableColumnWidth::Fixed(3)),
+            TableColumn::new("Name").width(TableColumnWidth::Percent(25.0)),
+            TableColumn::new("Owner"),
+            TableColumn::new("Description").width(TableColumnWidth::Flex(2.0)),
+            TableColumn::new("Tags").width(TableColumnWidth::Flex(1.0)),
+        ];
+        let rows = rows(&[&["1", "build", "ci", "Compiles the project", "fast"]]);
+
+        // The separators take 4 cells, leaving 36 for the columns: 3 for the fixed column, 9 for
+        // the percentage, 5 for the auto column, and the remaining 19 split 2:1 between the flex
+        // columns, with the rounding remainder going to the last one.
+        let output = element! {
+            Box(width: 40) {
+                Table(columns: columns.clone(), rows: rows.clone())
+            }
+        }
+        .to_string();
+        assert_eq!(
+            output,
+            "Id  Name      Owner Description  Tags\n1   build     ci    Compiles th… fast\n"
+        );
+
+        let widths = super::Table::column_widths(&columns, &rows, " ", Some(40));
+        assert_eq!(widths, vec![3, 9, 5, 12, 7]);
+        assert_eq!(widths.iter().sum::<usize>() + 4, 40);
+
+        // Without a constrained width, percentage and flex columns fit their content.
+        assert_eq!(
+            super::Table::column_widths(&columns, &rows, " ", None),
+            vec![3, 5, 5, 20, 4]
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
-    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Hooks, Props, Weight,
+    canvas::graphemes, CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater,
+    ContentStyle, EmojiWidth, Hooks, Props, SemanticColor, Theme, Weight,
 };
 use taffy::{AvailableSpace, Size};
-use unicode_width::UnicodeWidthStr;
+use unicode_width::UnicodeWidthChar;
 
 /// The text wrapping behavior of a [`Text`] component.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -14,7 +15,45 @@ pub enum TextWrap {
     NoWrap,
 }
 
+/// The behavior of a [`Text`] component when a single word is too long to fit on a line.
+///
+/// This only applies when the text is wrapped.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OverflowWrap {
+    /// The word is broken at whichever character reaches the end of the line. This is the
+    /// default.
+    #[default]
+    Anywhere,
+    /// The word is kept whole on its own line, and may overflow the bounds of the component.
+    Normal,
+    /// The word is broken at the end of the line, and a hyphen is added to the end of each line
+    /// that it's broken across.
+    Hyphenate,
+}
+
+/// The truncation behavior of a [`Text`] component, for lines which are too wide to fit.
+///
+/// This only applies when the text isn't wrapped.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Truncate {
+    /// Lines aren't truncated, and may overflow the bounds of the component. This is the default.
+    #[default]
+    None,
+    /// The end of each line is replaced with the ellipsis.
+    End,
+    /// The start of each line is replaced with the ellipsis.
+    Start,
+    /// The middle of each line is replaced with the ellipsis, keeping both ends visible. This is
+    /// useful for file paths.
+    Middle,
+}
+
 /// The text alignment of a [`Text`] component.
+///
+/// Each line, including lines created by wrapping, is aligned within the width of the component.
+/// Note that a component's width is determined by its layout: in a column, text stretches to the
+/// width of its container, but in a row, it's only as wide as its content unless given a width or
+/// allowed to grow.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum TextAlign {
     /// Text is aligned to the left. This is the default.
@@ -22,7 +61,8 @@ pub enum TextAlign {
     Left,
     /// Text is aligned to the right.
     Right,
-    /// Text is aligned to the center.
+    /// Text is aligned to the center. If a line can't be centered exactly, it's placed one cell
+    /// closer to the left.
     Center,
 }
 
@@ -36,26 +76,194 @@ pub enum TextDecoration {
     Underline,
 }
 
+/// A segment of text with its own style, which can be passed to the [`Text`] component via its
+/// `spans` property.
+///
+/// Any style that isn't set on the span is inherited from the [`Text`] component.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledSpan {
+    /// The content of the span.
+    pub text: String,
+
+    /// The color to make the span.
+    pub color: Option<Color>,
+
+    /// The weight of the span.
+    pub weight: Option<Weight>,
+
+    /// The text decoration of the span.
+    pub decoration: Option<TextDecoration>,
+
+    /// Whether the span is dimmed.
+    pub dim: Option<bool>,
+
+    /// Whether the span is italic.
+    pub italic: Option<bool>,
+
+    /// Whether the span is struck through.
+    pub strikethrough: Option<bool>,
+
+    /// Whether the foreground and background colors of the span are swapped.
+    pub reverse: Option<bool>,
+
+    /// Whether the span blinks.
+    pub blink: Option<bool>,
+}
+
+impl StyledSpan {
+    /// Creates a new span with the given content, inheriting its style from the [`Text`]
+    /// component.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the color of the span.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the weight of the span.
+    pub fn weight(mut self, weight: Weight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Sets the text decoration of the span.
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.decoration = Some(decoration);
+        self
+    }
+
+    /// Sets whether the span is dimmed.
+    pub fn dim(mut self, dim: bool) -> Self {
+        self.dim = Some(dim);
+        self
+    }
+
+    /// Sets whether the span is italic.
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Sets whether the span is struck through.
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    /// Sets whether the foreground and background colors of the span are swapped.
+    pub fn reverse(mut self, reverse: bool) -> Self {
+        self.reverse = Some(reverse);
+        self
+    }
+
+    /// Sets whether the span blinks.
+    pub fn blink(mut self, blink: bool) -> Self {
+        self.blink = Some(blink);
+        self
+    }
+
+    fn style(&self, base: CanvasTextStyle) -> CanvasTextStyle {
+        CanvasTextStyle {
+            color: self.color.or(base.color),
+            weight: self.weight.unwrap_or(base.weight),
+            dim: self.dim.unwrap_or(base.dim),
+            italic: self.italic.unwrap_or(base.italic),
+            underline: self
+                .decoration
+                .map_or(base.underline, |d| d == TextDecoration::Underline),
+            strikethrough: self.strikethrough.unwrap_or(base.strikethrough),
+            reverse: self.reverse.unwrap_or(base.reverse),
+            blink: self.blink.unwrap_or(base.blink),
+            background_color: base.background_color,
+        }
+    }
+}
+
 /// The props which can be passed to the [`Text`] component.
 #[derive(Default, Props)]
 pub struct TextProps {
     /// The color to make the text.
     pub color: Option<Color>,
 
+    /// The color to make the text, as a role which is resolved against the current [`Theme`]. If
+    /// given, this takes precedence over `color`.
+    pub semantic_color: Option<SemanticColor>,
+
     /// The content of the text.
     pub content: String,
 
+    /// Styled segments of content. If not empty, these are rendered contiguously in place of
+    /// `content`, and are wrapped as if they were a single string.
+    pub spans: Vec<StyledSpan>,
+
     /// The weight of the text.
     pub weight: Weight,
 
     /// The text wrapping behavior.
     pub wrap: TextWrap,
 
+    /// The behavior when a word is too long to fit on a line while wrapping.
+    pub overflow_wrap: OverflowWrap,
+
     /// The text alignment.
     pub align: TextAlign,
 
     /// The text decoration.
     pub decoration: TextDecoration,
+
+    /// Whether the text is dimmed, also known as faint. Unlike [`Weight::Light`], this can be
+    /// combined with a bold weight.
+    pub dim: bool,
+
+    /// Whether the text is italic. Not all terminals support this.
+    pub italic: bool,
+
+    /// Whether the text is struck through, e.g. to indicate deleted content. This can be combined
+    /// with an underline. Not all terminals support this, in which case the text is displayed
+    /// without it.
+    pub strikethrough: bool,
+
+    /// Whether to swap the foreground and background colors of the text. This is commonly used to
+    /// highlight a selection. If no colors are given, the terminal's default colors are swapped.
+    pub reverse: bool,
+
+    /// Whether the text blinks. Blinking can be disabled for the whole application via
+    /// [`SystemContext::set_blink_enabled`](crate::SystemContext::set_blink_enabled), and
+    /// isn't supported by all terminals.
+    pub blink: bool,
+
+    /// The truncation behavior for lines which are too wide to fit, when the text isn't wrapped.
+    pub truncate: Truncate,
+
+    /// The text to replace truncated content with. Defaults to `"…"`.
+    pub ellipsis: Option<String>,
+
+    /// The number of columns between tab stops. Each tab character in the content is expanded to
+    /// spaces, up to the next tab stop on its line. Defaults to 8.
+    pub tab_width: Option<usize>,
+
+    /// A crossterm style to use as the base style of the text, e.g. to reuse styles which are
+    /// built elsewhere.
+    ///
+    /// The other styling properties take precedence over it when they're set: `color` and
+    /// `semantic_color` replace its foreground color, a weight other than [`Weight::Normal`]
+    /// replaces its bold attribute, and a decoration other than [`TextDecoration::None`] replaces
+    /// its underline. `dim`, `italic`, `strikethrough`, `reverse` and `blink` can only turn those
+    /// attributes on. Its background color is applied to the cells which the text is written to.
+    /// Its underline color, and attributes which text can't display, such as hidden, are ignored.
+    pub style: Option<ContentStyle>,
+
+    /// How to measure sequences of emoji which are joined into a single glyph, such as 👨‍👩‍👧.
+    /// Text is measured by its grapheme clusters, so combining marks, flags, and skin tones are
+    /// always handled, but terminals differ on joined sequences. Defaults to
+    /// [`EmojiWidth::Joined`].
+    pub emoji_width: EmojiWidth,
 }
 
 /// `Text` is a component that renders a text string.
@@ -70,56 +278,212 @@ pub struct TextProps {
 /// }
 /// # }
 /// ```
+///
+/// To style parts of the text differently, for example to highlight search matches, you can
+/// give it spans instead of content:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn my_element() -> impl Into<AnyElement<'static>> {
+/// element! {
+///     Text(spans: vec![
+///         StyledSpan::new("fn ").color(Color::Magenta),
+///         StyledSpan::new("main").color(Color::Blue).weight(Weight::Bold),
+///         StyledSpan::new("() {}"),
+///     ])
+/// }
+/// # }
+/// ```
 #[derive(Default)]
 pub struct Text {
-    style: CanvasTextStyle,
     content: String,
+    // The style of each character of the content.
+    styles: Vec<CanvasTextStyle>,
     wrap: TextWrap,
+    overflow_wrap: OverflowWrap,
     align: TextAlign,
+    truncate: Truncate,
+    ellipsis: String,
+    emoji_width: EmojiWidth,
+}
+
+/// A line of wrapped text.
+struct WrappedLine {
+    text: String,
+    // Whether a hyphen which isn't part of the content should be drawn after the text.
+    hyphenated: bool,
+}
+
+impl WrappedLine {
+    fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            hyphenated: false,
+        }
+    }
+
+    fn width(&self, emoji_width: EmojiWidth) -> usize {
+        emoji_width.str_width(&self.text) + if self.hyphenated { 1 } else { 0 }
+    }
 }
 
 impl Text {
+    /// The default value of the `tab_width` prop.
+    const DEFAULT_TAB_WIDTH: usize = 8;
+
+    /// Replaces each tab in the content with spaces up to the next tab stop, along with its
+    /// style. This is done before both measuring and drawing so that they always agree.
+    fn expand_tabs(
+        content: &str,
+        styles: &[CanvasTextStyle],
+        tab_width: usize,
+    ) -> (String, Vec<CanvasTextStyle>) {
+        let mut expanded_content = String::with_capacity(content.len());
+        let mut expanded_styles = Vec::with_capacity(styles.len());
+        let mut column = 0;
+        for (c, &style) in content.chars().zip(styles) {
+            match c {
+                '\t' => {
+                    let spaces = if tab_width > 0 {
+                        tab_width - column % tab_width
+                    } else {
+                        0
+                    };
+                    expanded_content.extend(std::iter::repeat(' ').take(spaces));
+                    expanded_styles.extend(std::iter::repeat(style).take(spaces));
+                    column += spaces;
+                }
+                '\n' => {
+                    expanded_content.push(c);
+                    expanded_styles.push(style);
+                    column = 0;
+                }
+                _ => {
+                    expanded_content.push(c);
+                    expanded_styles.push(style);
+                    column += c.width().unwrap_or(0);
+                }
+            }
+        }
+        (expanded_content, expanded_styles)
+    }
+
     fn wrap(
         content: &str,
         text_wrap: TextWrap,
+        overflow_wrap: OverflowWrap,
         known_width: Option<f32>,
         available_width: AvailableSpace,
-    ) -> String {
-        match text_wrap {
+        emoji_width: EmojiWidth,
+    ) -> Vec<WrappedLine> {
+        let width = match text_wrap {
             TextWrap::Wrap => match known_width {
-                Some(w) => textwrap::fill(content, w as usize),
+                Some(w) => Some(w as usize),
                 None => match available_width {
-                    AvailableSpace::Definite(w) => textwrap::fill(content, w as usize),
-                    AvailableSpace::MaxContent => content.to_string(),
-                    AvailableSpace::MinContent => textwrap::fill(content, 1),
+                    AvailableSpace::Definite(w) => Some(w as usize),
+                    AvailableSpace::MaxContent => None,
+                    AvailableSpace::MinContent => Some(1),
                 },
             },
-            TextWrap::NoWrap => content.to_string(),
+            TextWrap::NoWrap => None,
+        };
+        let Some(width) = width else {
+            return content.lines().map(WrappedLine::new).collect();
+        };
+
+        let options =
+            textwrap::Options::new(width).break_words(overflow_wrap == OverflowWrap::Anywhere);
+        let mut lines = Vec::new();
+        for line in textwrap::fill(content, options).lines() {
+            // Words which are too long are left on their own line, so we just need to break up
+            // the lines that overflow. There needs to be room for at least one character before
+            // the hyphen.
+            if overflow_wrap == OverflowWrap::Hyphenate
+                && width > 1
+                && emoji_width.str_width(line) > width
+            {
+                let mut chunk = WrappedLine::new("");
+                let mut chunk_width = 0;
+                for g in graphemes(line) {
+                    let g_width = emoji_width.grapheme_width(g);
+                    if chunk_width + g_width > width - 1 {
+                        chunk.hyphenated = true;
+                        lines.push(chunk);
+                        chunk = WrappedLine::new("");
+                        chunk_width = 0;
+                    }
+                    chunk.text.push_str(g);
+                    chunk_width += g_width;
+                }
+                lines.push(chunk);
+            } else {
+                lines.push(WrappedLine::new(line));
+            }
         }
+        lines
     }
 
-    fn align(content: String, align: TextAlign, width: usize) -> String {
-        match align {
-            TextAlign::Left => content,
-            TextAlign::Right => content
-                .lines()
-                .map(|line| {
-                    let padding = width - line.width();
-                    format!("{:width$}{}", "", line, width = padding)
+    /// Shortens the line to fit within the given width by replacing part of it with the
+    /// ellipsis. The ellipsis takes on the style of the first grapheme that it replaces.
+    fn truncate<'a>(
+        line: &[(&'a str, CanvasTextStyle)],
+        width: usize,
+        truncate: Truncate,
+        ellipsis: &'a str,
+        emoji_width: EmojiWidth,
+    ) -> Vec<(&'a str, CanvasTextStyle)> {
+        let grapheme_width = |(g, _): &&(&str, CanvasTextStyle)| emoji_width.grapheme_width(g);
+        // Returns the number of graphemes which fit in the given width.
+        let fit = |graphemes: &mut dyn Iterator<Item = &(&str, CanvasTextStyle)>, width: usize| {
+            let mut total = 0;
+            graphemes
+                .take_while(|g| {
+                    total += grapheme_width(g);
+                    total <= width
                 })
-                .collect::<Vec<_>>()
-                .join("\n"),
-            TextAlign::Center => {
-                let padding = width / 2;
-                content
-                    .lines()
-                    .map(|line| {
-                        let padding = padding - line.width() / 2;
-                        format!("{:width$}{}", "", line, width = padding)
-                    })
-                    .collect::<Vec<_>>()
-                    .join("\n")
-            }
+                .count()
+        };
+        let ellipsis_with_style = |style: CanvasTextStyle, width: usize| {
+            let mut ellipsis_width = 0;
+            graphemes(ellipsis)
+                .take_while(move |g| {
+                    ellipsis_width += emoji_width.grapheme_width(g);
+                    ellipsis_width <= width
+                })
+                .map(move |g| (g, style))
+        };
+
+        if truncate == Truncate::None
+            || line.iter().map(|g| grapheme_width(&g)).sum::<usize>() <= width
+        {
+            return line.to_vec();
+        }
+        let available = width.saturating_sub(emoji_width.str_width(ellipsis));
+        let (head, tail) = match truncate {
+            Truncate::None => unreachable!(),
+            Truncate::End => (fit(&mut line.iter(), available), 0),
+            Truncate::Start => (0, fit(&mut line.iter().rev(), available)),
+            Truncate::Middle => (
+                fit(&mut line.iter(), available - available / 2),
+                fit(&mut line.iter().rev(), available / 2),
+            ),
+        };
+        let style = line[head].1;
+        line[..head]
+            .iter()
+            .copied()
+            .chain(ellipsis_with_style(style, width))
+            .chain(line[line.len() - tail..].iter().copied())
+            .collect()
+    }
+
+    /// Returns the number of columns to offset a line by to achieve the given alignment.
+    pub(crate) fn align_offset(line_width: usize, align: TextAlign, width: usize) -> usize {
+        match align {
+            TextAlign::Left => 0,
+            TextAlign::Right => width.saturating_sub(line_width),
+            // If the space can't be split evenly, the extra cell goes on the right.
+            TextAlign::Center => width.saturating_sub(line_width) / 2,
         }
     }
 }
@@ -137,27 +501,100 @@ impl Component for Text {
         _hooks: Hooks,
         updater: &mut ComponentUpdater,
     ) {
-        self.style = CanvasTextStyle {
-            color: props.color,
-            weight: props.weight,
-            underline: props.decoration == TextDecoration::Underline,
+        let color = match props.semantic_color {
+            Some(semantic_color) => Some(
+                updater
+                    .get_context::<Theme>()
+                    .map(|theme| theme.color(semantic_color))
+                    .unwrap_or_else(|| Theme::default().color(semantic_color)),
+            ),
+            None => props.color,
         };
-        self.content = props.content.clone();
+        let base = props.style.map(CanvasTextStyle::from).unwrap_or_default();
+        let style = CanvasTextStyle {
+            color: color.or(base.color),
+            weight: if props.weight == Weight::Normal {
+                base.weight
+            } else {
+                props.weight
+            },
+            dim: props.dim || base.dim,
+            italic: props.italic || base.italic,
+            underline: match props.decoration {
+                TextDecoration::None => base.underline,
+                TextDecoration::Underline => true,
+            },
+            strikethrough: props.strikethrough || base.strikethrough,
+            reverse: props.reverse || base.reverse,
+            blink: props.blink || base.blink,
+            background_color: base.background_color,
+        };
+        if props.spans.is_empty() {
+            self.content = props.content.clone();
+            self.styles = vec![style; self.content.chars().count()];
+        } else {
+            self.content = props.spans.iter().map(|span| span.text.as_str()).collect();
+            self.styles = props
+                .spans
+                .iter()
+                .flat_map(|span| {
+                    let style = span.style(style);
+                    span.text.chars().map(move |_| style)
+                })
+                .collect();
+        }
+        if self.content.contains('\t') {
+            (self.content, self.styles) = Self::expand_tabs(
+                &self.content,
+                &self.styles,
+                props.tab_width.unwrap_or(Self::DEFAULT_TAB_WIDTH),
+            );
+        }
         self.wrap = props.wrap;
+        self.overflow_wrap = props.overflow_wrap;
         self.align = props.align;
+        self.truncate = props.truncate;
+        self.ellipsis = props.ellipsis.clone().unwrap_or_else(|| "…".to_string());
+        self.emoji_width = props.emoji_width;
 
         {
             let content = self.content.clone();
             let text_wrap = props.wrap;
+            let overflow_wrap = props.overflow_wrap;
+            let emoji_width = props.emoji_width;
+            let min_width = match (text_wrap, props.truncate) {
+                (TextWrap::NoWrap, Truncate::None) | (TextWrap::Wrap, _) => None,
+                // Truncated text can shrink down to just the ellipsis.
+                (TextWrap::NoWrap, _) => Some(emoji_width.str_width(&self.ellipsis)),
+            };
             updater.set_measure_func(Box::new(move |known_size, available_space, _| {
-                let content =
-                    Self::wrap(&content, text_wrap, known_size.width, available_space.width);
+                let lines = Self::wrap(
+                    &content,
+                    text_wrap,
+                    overflow_wrap,
+                    known_size.width,
+                    available_space.width,
+                    emoji_width,
+                );
                 let mut max_width = 0;
                 let mut num_lines = 0;
-                for line in content.lines() {
-                    max_width = max_width.max(line.width());
+                for line in lines {
+                    max_width = max_width.max(line.width(emoji_width));
                     num_lines += 1;
                 }
+                if let Some(min_width) = min_width {
+                    let limit = match known_size.width {
+                        Some(w) => Some(w as usize),
+                        None => match available_space.width {
+                            AvailableSpace::Definite(w) => Some(w as usize),
+                            AvailableSpace::MaxContent => None,
+                            AvailableSpace::MinContent => Some(min_width),
+                        },
+                    };
+                    if let Some(limit) = limit {
+                        max_width = max_width.min(limit.max(min_width));
+                    }
+                }
                 Size {
                     width: max_width as _,
                     height: num_lines.max(1) as _,
@@ -168,14 +605,87 @@ impl Component for Text {
 
     fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
         let width = drawer.layout().size.width;
-        let content = Self::wrap(
+        let lines = Self::wrap(
             &self.content,
             self.wrap,
+            self.overflow_wrap,
             None,
             AvailableSpace::Definite(width),
+            self.emoji_width,
         );
-        let content = Self::align(content, self.align, width as _);
-        drawer.canvas().set_text(0, 0, &content, self.style);
+
+        // Wrapping only removes whitespace from the content, so we can find the style of each
+        // wrapped character by matching it up with the original content.
+        let mut original = self.content.chars().zip(self.styles.iter().copied());
+        let mut canvas = drawer.canvas();
+        for (y, line) in lines.iter().enumerate() {
+            let mut chars: Vec<_> = line
+                .text
+                .chars()
+                .map(|c| {
+                    let style = original
+                        .find(|(original_c, _)| *original_c == c)
+                        .map(|(_, style)| style)
+                        .unwrap_or_default();
+                    (c, style)
+                })
+                .collect();
+            if line.hyphenated {
+                // The hyphen takes on the style of the character before it.
+                let style = chars.last().map(|(_, style)| *style).unwrap_or_default();
+                chars.push(('-', style));
+            }
+
+            // Each grapheme takes on the style of its first character.
+            let text: String = chars.iter().map(|(c, _)| *c).collect();
+            let mut styles = chars.iter().map(|(_, style)| *style);
+            let mut graphemes: Vec<_> = graphemes(&text)
+                .map(|g| {
+                    let style = styles.next().unwrap_or_default();
+                    for _ in 1..g.chars().count() {
+                        styles.next();
+                    }
+                    (g, style)
+                })
+                .collect();
+            if self.wrap == TextWrap::NoWrap && line.width(self.emoji_width) > width as usize {
+                graphemes = Self::truncate(
+                    &graphemes,
+                    width as _,
+                    self.truncate,
+                    &self.ellipsis,
+                    self.emoji_width,
+                );
+            }
+
+            let line_width = graphemes
+                .iter()
+                .map(|(g, _)| self.emoji_width.grapheme_width(g))
+                .sum();
+            let mut x = Self::align_offset(line_width, self.align, width as _);
+            let mut run = String::new();
+            let mut run_style = None;
+            for (g, style) in graphemes {
+                if run_style != Some(style) {
+                    if let Some(run_style) = run_style {
+                        canvas.set_text_with_emoji_width(
+                            x as _,
+                            y as _,
+                            &run,
+                            run_style,
+                            self.emoji_width,
+                        );
+                        x += self.emoji_width.str_width(&run);
+                        run.clear();
+                    }
+                    run_style = Some(style);
+                }
+                run.push_str(g);
+            }
+            if let Some(run_style) = run_style {
+                canvas.set_text_with_emoji_width(x as _, y as _, &run, run_style, self.emoji_width);
+            }
+        }
     }
 }
 
@@ -225,5 +735,424 @@ mod tests {
             .to_string(),
             "  this is an\nalignment test\n"
         );
+
+        assert_eq!(
+            element! {
+                Box(width: 14, flex_direction: FlexDirection::Column) {
+                    Text(content: "title", align: TextAlign::Center)
+                    Text(content: "titles", align: TextAlign::Center)
+                }
+            }
+            .to_string(),
+            "    title\n    titles\n"
+        );
+    }
+
+    #[test]
+    fn test_text_spans() {
+        assert_eq!(
+            element! {
+                Text(spans: vec![StyledSpan::new("foo"), StyledSpan::new("bar")])
+            }
+            .to_string(),
+            "foobar\n"
+        );
+
+        assert_eq!(
+            element! {
+                Box(width: 14) {
+                    Text(
+                        content: "ignored",
+                        spans: vec![
+                            StyledSpan::new("this is a ").color(Color::Red),
+                            StyledSpan::new("wra"),
+                            StyledSpan::new("pping test").weight(Weight::Bold),
+                        ],
+                    )
+                }
+            }
+            .to_string(),
+            "this is a\nwrapping test\n"
+        );
+
+        let mut expected = Canvas::new(14, 2);
+        expected.subview_mut(0, 0, 14, 2, false).set_text(
+            0,
+            0,
+            "this is a",
+            CanvasTextStyle {
+                color: Some(Color::Red),
+                ..Default::default()
+            },
+        );
+        expected.subview_mut(0, 0, 14, 2, false).set_text(
+            0,
+            1,
+            "wra",
+            CanvasTextStyle {
+                color: Some(Color::Green),
+                ..Default::default()
+            },
+        );
+        expected.subview_mut(0, 0, 14, 2, false).set_text(
+            3,
+            1,
+            "pping test",
+            CanvasTextStyle {
+                color: Some(Color::Green),
+                weight: Weight::Bold,
+                underline: true,
+                ..Default::default()
+            },
+        );
+        let mut expected_ansi = Vec::new();
+        expected.write_ansi(&mut expected_ansi).unwrap();
+        assert_eq!(
+            element! {
+                Box(width: 14) {
+                    Text(
+                        color: Color::Green,
+                        decoration: TextDecoration::Underline,
+                        spans: vec![
+                            StyledSpan::new("this is a ")
+                                .color(Color::Red)
+                                .decoration(TextDecoration::None),
+                            StyledSpan::new("wra").decoration(TextDecoration::None),
+                            StyledSpan::new("pping test").weight(Weight::Bold),
+                        ],
+                    )
+                }
+            }
+            .render_to_string(None),
+            String::from_utf8(expected_ansi).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_text_strikethrough() {
+        let mut expected = Canvas::new(7, 1);
+        expected.subview_mut(0, 0, 7, 1, false).set_text(
+            0,
+            0,
+            "old",
+            CanvasTextStyle {
+                dim: true,
+                strikethrough: true,
+                ..Default::default()
+            },
+        );
+        expected
+            .subview_mut(0, 0, 7, 1, false)
+            .set_text(4, 0, "new", CanvasTextStyle::default());
+        let mut expected_ansi = Vec::new();
+        expected.write_ansi(&mut expected_ansi).unwrap();
+        assert!(String::from_utf8_lossy(&expected_ansi).contains("\x1b[9m"));
+        assert_eq!(
+            element! {
+                Text(
+                    strikethrough: true,
+                    dim: true,
+                    spans: vec![
+                        StyledSpan::new("old"),
+                        StyledSpan::new(" new").dim(false).strikethrough(false),
+                    ],
+                )
+            }
+            .render_to_string(None),
+            String::from_utf8(expected_ansi).unwrap()
+        );
+    }
+
+    #[derive(Default, Props)]
+    struct BlinkerProps {
+        blink_enabled: bool,
+    }
+
+    #[component]
+    fn Blinker(hooks: Hooks, props: &BlinkerProps) -> impl Into<AnyElement<'static>> {
+        hooks
+            .use_context_mut::<SystemContext>()
+            .set_blink_enabled(props.blink_enabled);
+        element! {
+            Text(content: "alert", color: Color::Red, reverse: true, blink: true)
+        }
+    }
+
+    #[test]
+    fn test_text_reverse_blink() {
+        let expected_ansi = |blink: bool| {
+            let mut expected = Canvas::new(5, 1);
+            expected.subview_mut(0, 0, 5, 1, false).set_text(
+                0,
+                0,
+                "alert",
+                CanvasTextStyle {
+                    color: Some(Color::Red),
+                    reverse: true,
+                    blink,
+                    ..Default::default()
+                },
+            );
+            let mut ansi = Vec::new();
+            expected.write_ansi(&mut ansi).unwrap();
+            String::from_utf8(ansi).unwrap()
+        };
+
+        assert!(expected_ansi(true).contains("\x1b[7m\x1b[5m"));
+        assert_eq!(
+            element!(Blinker(blink_enabled: true)).render_to_string(None),
+            expected_ansi(true)
+        );
+        assert_eq!(
+            element!(Blinker(blink_enabled: false)).render_to_string(None),
+            expected_ansi(false)
+        );
+    }
+
+    #[test]
+    fn test_text_semantic_color() {
+        assert_eq!(
+            element!(Text(content: "x", semantic_color: SemanticColor::Danger))
+                .render_to_string(None),
+            element!(Text(content: "x", color: Color::Red)).render_to_string(None)
+        );
+
+        let theme = Theme {
+            danger: Color::Magenta,
+            ..Default::default()
+        };
+        assert_eq!(
+            element! {
+                ContextProvider(value: Context::owned(theme)) {
+                    Text(content: "x", color: Color::Blue, semantic_color: SemanticColor::Danger)
+                }
+            }
+            .render_to_string(None),
+            element!(Text(content: "x", color: Color::Magenta)).render_to_string(None)
+        );
+    }
+
+    #[test]
+    fn test_text_content_style() {
+        let style = ContentStyle {
+            foreground_color: Some(Color::Green),
+            background_color: Some(Color::Blue),
+            attributes: [Attribute::Bold, Attribute::Underlined, Attribute::Italic]
+                .as_slice()
+                .into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            element!(Text(content: "x", style)).render_to_debug_grid(None, false),
+            element! {
+                Box(background_color: Color::Blue) {
+                    Text(
+                        content: "x",
+                        color: Color::Green,
+                        weight: Weight::Bold,
+                        italic: true,
+                        decoration: TextDecoration::Underline,
+                    )
+                }
+            }
+            .render_to_debug_grid(None, false)
+        );
+        assert!(element!(Text(content: "x", style))
+            .render_to_string(None)
+            .contains("\x1b[3m"));
+
+        // The convenience properties take precedence when they're set.
+        assert_eq!(
+            element!(Text(
+                content: "x",
+                style,
+                color: Color::Red,
+                weight: Weight::Light,
+                strikethrough: true,
+                reverse: true,
+            ))
+            .render_to_debug_grid(None, false),
+            "┌─┐\n│[red dim italic underline strikethrough reverse bg:blue]x[/red dim italic \
+             underline strikethrough reverse bg:blue]│\n└─┘\n"
+        );
+    }
+
+    #[test]
+    fn test_text_combined_attributes() {
+        assert_eq!(
+            element!(Text(
+                content: "x",
+                weight: Weight::Bold,
+                dim: true,
+                decoration: TextDecoration::Underline,
+                strikethrough: true,
+            ))
+            .render_to_debug_grid(None, false),
+            "┌─┐\n│[bold dim underline strikethrough]x[/bold dim underline strikethrough]│\n└─┘\n"
+        );
+
+        let ansi =
+            element!(Text(content: "x", weight: Weight::Bold, dim: true, strikethrough: true))
+                .render_to_string(None);
+        assert!(ansi.contains("\x1b[1m\x1b[2m"));
+        assert!(ansi.contains("\x1b[9m"));
+    }
+
+    #[test]
+    fn test_text_truncate() {
+        let truncated = |truncate: Truncate, ellipsis: Option<&str>, width: u32| {
+            element! {
+                Box(width: width) {
+                    Text(
+                        content: "/very/long/path/to/file.txt",
+                        wrap: TextWrap::NoWrap,
+                        truncate,
+                        ellipsis: ellipsis.map(|s| s.to_string()),
+                    )
+                }
+            }
+            .to_string()
+        };
+
+        assert_eq!(truncated(Truncate::End, None, 20), "/very/long/path/to/…\n");
+        assert_eq!(
+            truncated(Truncate::Start, None, 20),
+            "…ng/path/to/file.txt\n"
+        );
+        assert_eq!(
+            truncated(Truncate::Middle, None, 20),
+            "/very/long…/file.txt\n"
+        );
+        assert_eq!(
+            truncated(Truncate::Middle, Some("/.../"), 20),
+            "/very/lo/.../ile.txt\n"
+        );
+        assert_eq!(
+            truncated(Truncate::End, None, 30),
+            "/very/long/path/to/file.txt\n"
+        );
+        assert_eq!(truncated(Truncate::End, Some("..."), 2), "..\n");
+
+        // Wide characters are never split.
+        assert_eq!(
+            element! {
+                Box(width: 6) {
+                    Text(content: "😀😀😀😀", wrap: TextWrap::NoWrap, truncate: Truncate::End)
+                }
+            }
+            .to_string(),
+            "😀😀…\n"
+        );
+
+        // In a row, truncated text shrinks to make room for its siblings.
+        assert_eq!(
+            element! {
+                Box(width: 20) {
+                    Text(
+                        content: "/very/long/path/to/file.txt",
+                        wrap: TextWrap::NoWrap,
+                        truncate: Truncate::Middle,
+                    )
+                    Text(content: " (2 KB)", wrap: TextWrap::NoWrap)
+                }
+            }
+            .to_string(),
+            "/very/…le.txt (2 KB)\n"
+        );
+    }
+
+    #[test]
+    fn test_text_tabs() {
+        assert_eq!(
+            element!(Text(content: "a\tb\n😀\tc\nabcdefgh\td")).to_string(),
+            "a       b\n😀      c\nabcdefgh        d\n"
+        );
+
+        assert_eq!(
+            element!(Text(content: "😀\t😀😀\tx", tab_width: 4)).to_string(),
+            "😀  😀😀    x\n"
+        );
+
+        // The expanded tabs are part of the line when it's aligned.
+        assert_eq!(
+            element! {
+                Box(flex_direction: FlexDirection::Column) {
+                    Text(content: "\t😀", align: TextAlign::Right)
+                    Text(content: "123456789012")
+                }
+            }
+            .to_string(),
+            "          😀\n123456789012\n"
+        );
+    }
+
+    #[test]
+    fn test_text_overflow_wrap() {
+        let token = "0123456789".repeat(20);
+        let content = format!("see {} for details", token);
+        let render = |overflow_wrap: OverflowWrap| {
+            element! {
+                Box(width: 20, flex_direction: FlexDirection::Column) {
+                    Text(content: content.clone(), overflow_wrap: overflow_wrap)
+                }
+            }
+            .to_string()
+        };
+
+        let mut expected = "see\n".to_string();
+        for _ in 0..10 {
+            expected += "01234567890123456789\n";
+        }
+        expected += "for details\n";
+        assert_eq!(render(OverflowWrap::Anywhere), expected);
+        assert_eq!(render(OverflowWrap::default()), expected);
+
+        // The token is kept whole, and overflows the box.
+        assert_eq!(
+            render(OverflowWrap::Normal),
+            "see\n01234567890123456789\nfor details\n"
+        );
+
+        let mut expected = "see\n".to_string();
+        let chars = token.chars().collect::<Vec<_>>();
+        let mut chunks = chars.chunks(19).peekable();
+        while let Some(chunk) = chunks.next() {
+            expected.extend(chunk);
+            if chunks.peek().is_some() {
+                expected.push('-');
+            }
+            expected.push('\n');
+        }
+        expected += "for details\n";
+        assert_eq!(render(OverflowWrap::Hyphenate), expected);
+    }
+
+    #[test]
+    fn test_text_emoji_width() {
+        let content = "a\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}b";
+        let render = |emoji_width: EmojiWidth, truncate: Truncate, width: u32| {
+            element! {
+                Box(width) {
+                    Text(content, emoji_width, truncate, wrap: TextWrap::NoWrap)
+                }
+            }
+            .to_string()
+        };
+
+        // The joined sequence fits in two columns...
+        assert_eq!(
+            render(EmojiWidth::Joined, Truncate::End, 4),
+            format!("{}\n", content)
+        );
+        assert_eq!(render(EmojiWidth::Joined, Truncate::End, 3), "a…\n");
+        // ...but takes up six when its emoji are counted separately.
+        assert_eq!(
+            render(EmojiWidth::Separate, Truncate::End, 8),
+            format!("{}\n", content)
+        );
+        assert_eq!(render(EmojiWidth::Separate, Truncate::End, 4), "a…\n");
+
+        // The sequence is never split in two.
+        assert_eq!(render(EmojiWidth::Joined, Truncate::Start, 3), "…b\n");
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
-    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, Handler, Hooks, KeyCode,
-    KeyEvent, KeyEventKind, Props, TerminalEvent, TerminalEvents,
+    CanvasTextStyle, Color, Component, ComponentDrawer, ComponentUpdater, EmojiWidth, Handler,
+    Hooks, KeyCode, KeyEvent, KeyEventKind, Props, TerminalEvent, TerminalEvents, Weight,
 };
 use futures::stream::Stream;
 use std::{
+    borrow::Cow,
+    iter,
     pin::{pin, Pin},
     task::{Context, Poll},
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 /// The props which can be passed to the [`TextInput`] component.
 #[derive(Default, Props)]
@@ -18,11 +20,24 @@ pub struct TextInputProps {
     /// The current value.
     pub value: String,
 
-    /// True if the input has focus and should process keyboard input.
+    /// Text to display in place of the value when the value is empty.
+    pub placeholder: String,
+
+    /// True if the input has focus and should process keyboard input. The cursor is only
+    /// displayed while the input has focus.
     pub has_focus: bool,
 
+    /// The color of the cursor. Defaults to [`Color::Grey`].
+    pub cursor_color: Option<Color>,
+
     /// The handler to invoke when the value changes.
     pub on_change: Handler<'static, String>,
+
+    /// If given, each character of the value is displayed as this character instead, such as for
+    /// password inputs. Characters which are made up of multiple code points, such as letters with
+    /// combining accents, are masked as one. The `on_change` handler still receives the real value,
+    /// but it never appears in the rendered output.
+    pub mask: Option<char>,
 }
 
 /// `TextInput` is a component that can receive text input from the user.
@@ -30,6 +45,17 @@ pub struct TextInputProps {
 /// It will fill the available space and display the current value. Typically, you will want to
 /// render it in a [`Box`] component of the desired text field size.
 ///
+/// While focused, the input displays a cursor which can be moved with the left and right arrow keys
+/// or the home and end keys. Characters are inserted at the cursor, and backspace and delete remove
+/// the characters before and after it. The cursor moves over, and backspace and delete remove,
+/// whole grapheme clusters, so a letter with combining accents or an emoji sequence is treated as a
+/// single character. Wide characters, such as CJK characters, take up two columns. The cursor
+/// position is retained across renders. If the value is changed externally, the cursor is clamped
+/// to the new value's length.
+///
+/// If a `mask` is given, the value is displayed with every character replaced by the mask, and
+/// the cursor is positioned within the masked text.
+///
 /// # Example
 ///
 /// ```
@@ -63,17 +89,143 @@ pub struct TextInputProps {
 #[derive(Default)]
 pub struct TextInput {
     value: String,
+    placeholder: String,
+    cursor: usize,
     events: Option<TerminalEvents>,
     style: CanvasTextStyle,
+    cursor_color: Option<Color>,
     handler: Option<Handler<'static, String>>,
     has_focus: bool,
+    mask: Option<char>,
+}
+
+impl TextInput {
+    /// Sets the value, clamping the cursor to the new value's length and moving it to the start
+    /// of the grapheme cluster it's in, if any.
+    fn set_value(&mut self, value: String) {
+        self.value = value;
+        self.cursor = self
+            .value
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .chain(iter::once(self.value.len()))
+            .take_while(|offset| *offset <= self.cursor)
+            .last()
+            .unwrap_or(0);
+    }
+
+    /// Returns the byte offset of the grapheme cluster boundary before the cursor, if any.
+    fn previous_boundary(&self) -> Option<usize> {
+        GraphemeCursor::new(self.cursor, self.value.len(), true)
+            .prev_boundary(&self.value, 0)
+            .ok()
+            .flatten()
+    }
+
+    /// Returns the byte offset of the grapheme cluster boundary after the cursor, if any.
+    fn next_boundary(&self) -> Option<usize> {
+        GraphemeCursor::new(self.cursor, self.value.len(), true)
+            .next_boundary(&self.value, 0)
+            .ok()
+            .flatten()
+    }
+
+    /// Applies a key press to the value, returning `true` if the value changed.
+    fn handle_key_code(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char(c) => {
+                self.value.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+                true
+            }
+            KeyCode::Backspace => match self.previous_boundary() {
+                Some(boundary) => {
+                    self.value.replace_range(boundary..self.cursor, "");
+                    self.cursor = boundary;
+                    true
+                }
+                None => false,
+            },
+            KeyCode::Delete => match self.next_boundary() {
+                Some(boundary) => {
+                    self.value.replace_range(self.cursor..boundary, "");
+                    true
+                }
+                None => false,
+            },
+            KeyCode::Left => {
+                if let Some(boundary) = self.previous_boundary() {
+                    self.cursor = boundary;
+                }
+                false
+            }
+            KeyCode::Right => {
+                if let Some(boundary) = self.next_boundary() {
+                    self.cursor = boundary;
+                }
+                false
+            }
+            KeyCode::Home => {
+                self.cursor = self.value[..self.cursor]
+                    .rfind('\n')
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                false
+            }
+            KeyCode::End => {
+                self.cursor = self.value[self.cursor..]
+                    .find('\n')
+                    .map(|i| self.cursor + i)
+                    .unwrap_or(self.value.len());
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the given part of the value as it's displayed, with the mask applied.
+    fn display<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.mask {
+            Some(mask) => text
+                .graphemes(true)
+                .map(|g| if g == "\n" { '\n' } else { mask })
+                .collect::<String>()
+                .into(),
+            None => text.into(),
+        }
+    }
+
+    /// Returns the line and column of the cursor within the displayed value.
+    fn cursor_position(&self) -> (usize, usize) {
+        let before_cursor = self.display(&self.value[..self.cursor]);
+        let line = before_cursor.matches('\n').count();
+        let line_start = before_cursor.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        (
+            line,
+            EmojiWidth::default().str_width(&before_cursor[line_start..]),
+        )
+    }
+
+    /// Returns the number of columns the cursor covers, which is the width of the character
+    /// under it, or one if there's no such character.
+    fn cursor_width(&self) -> usize {
+        self.display(&self.value[self.cursor..])
+            .graphemes(true)
+            .next()
+            .map(|g| EmojiWidth::default().grapheme_width(g))
+            .filter(|width| *width > 0)
+            .unwrap_or(1)
+    }
 }
 
 impl Component for TextInput {
     type Props<'a> = TextInputProps;
 
-    fn new(_props: &Self::Props<'_>) -> Self {
-        Self::default()
+    fn new(props: &Self::Props<'_>) -> Self {
+        Self {
+            cursor: props.value.len(),
+            ..Default::default()
+        }
     }
 
     fn update(
@@ -89,9 +241,12 @@ impl Component for TextInput {
             color: props.color,
             ..Default::default()
         };
-        self.value = props.value.clone();
+        self.set_value(props.value.clone());
+        self.placeholder = props.placeholder.clone();
+        self.cursor_color = props.cursor_color;
         self.handler = Some(props.on_change.take());
         self.has_focus = props.has_focus;
+        self.mask = props.mask;
         updater.set_layout_style(taffy::style::Style {
             size: taffy::Size::percent(1.0),
             ..Default::default()
@@ -100,32 +255,50 @@ impl Component for TextInput {
 
     fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
         let size = drawer.layout().size;
+        let mut canvas = drawer.canvas();
 
-        let mut max_width = 0;
-        let mut num_lines = 0;
-        for line in self.value.lines() {
-            max_width = max_width.max(line.width());
-            num_lines += 1;
-        }
-        num_lines = num_lines.max(1);
+        let (cursor_line, cursor_column) = self.cursor_position();
+        let cursor_width = self.cursor_width();
 
-        let y = if num_lines > size.height as usize {
-            -(num_lines as isize - size.height as isize)
+        // Scroll as needed to keep the cursor cell visible.
+        let y = if cursor_line >= size.height as usize {
+            -(cursor_line as isize - size.height as isize + 1)
         } else {
             0
         };
-
-        let x = if max_width > size.width as usize {
-            -(max_width as isize - size.width as isize)
+        let x = if cursor_column + cursor_width > size.width as usize {
+            -((cursor_column + cursor_width) as isize - size.width as isize)
         } else {
             0
         };
 
-        drawer.canvas().set_text(x, y, &self.value, self.style);
+        if self.value.is_empty() && !self.placeholder.is_empty() {
+            canvas.set_text(
+                0,
+                0,
+                &self.placeholder,
+                CanvasTextStyle {
+                    weight: Weight::Light,
+                    ..self.style
+                },
+            );
+        } else {
+            canvas.set_text(x, y, &self.display(&self.value), self.style);
+        }
+
+        if self.has_focus {
+            canvas.set_background_color(
+                x + cursor_column as isize,
+                y + cursor_line as isize,
+                cursor_width,
+                1,
+                self.cursor_color.unwrap_or(Color::Grey),
+            );
+        }
     }
 
     fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
-        let mut changed = false;
+        let mut needs_redraw = false;
         while let Some(Poll::Ready(Some(event))) = self
             .events
             .as_mut()
@@ -138,33 +311,31 @@ impl Component for TextInput {
                 TerminalEvent::Key(KeyEvent { code, kind, .. })
                     if kind != KeyEventKind::Release =>
                 {
-                    match code {
-                        KeyCode::Char(c) => {
-                            changed = true;
-                            self.value.push(c);
+                    let prev_cursor = self.cursor;
+                    if self.handle_key_code(code) {
+                        let new_value = self.value.clone();
+                        if let Some(handler) = self.handler.as_mut() {
+                            handler.invoke(new_value);
                         }
-                        KeyCode::Backspace => {
-                            changed = true;
-                            self.value.pop();
-                        }
-                        _ => {}
+                        needs_redraw = true;
+                    } else if self.cursor != prev_cursor {
+                        needs_redraw = true;
                     }
                 }
                 _ => {}
             }
         }
-        if changed {
-            let new_value = self.value.clone();
-            if let Some(handler) = self.handler.as_mut() {
-                handler.invoke(new_value);
-            }
+        if needs_redraw {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
         }
-        Poll::Pending
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::prelude::*;
     use macro_rules_attribute::apply;
     use smol_macros::test;
@@ -195,7 +366,173 @@ mod tests {
             .await
             .unwrap();
         let actual = canvases.iter().map(|c| c.to_string()).collect::<Vec<_>>();
-        let expected = vec!["\n", "foo\n"];
+        // The trailing space is the cursor.
+        let expected = vec![" \n", "foo \n"];
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_text_input_editing() {
+        let mut input = TextInput::new(&TextInputProps {
+            value: "ab".to_string(),
+            ..Default::default()
+        });
+        input.set_value("ab".to_string());
+        assert_eq!(input.cursor, 2);
+
+        assert!(input.handle_key_code(KeyCode::Char('c')));
+        assert_eq!(input.value, "abc");
+
+        assert!(!input.handle_key_code(KeyCode::Left));
+        assert!(!input.handle_key_code(KeyCode::Left));
+        assert!(input.handle_key_code(KeyCode::Char('é')));
+        assert_eq!(input.value, "aébc");
+        assert_eq!(input.cursor_position(), (0, 2));
+
+        assert!(input.handle_key_code(KeyCode::Delete));
+        assert_eq!(input.value, "aéc");
+        assert!(input.handle_key_code(KeyCode::Backspace));
+        assert_eq!(input.value, "ac");
+        assert_eq!(input.cursor_position(), (0, 1));
+
+        assert!(!input.handle_key_code(KeyCode::Home));
+        assert!(!input.handle_key_code(KeyCode::Backspace));
+        assert!(!input.handle_key_code(KeyCode::Left));
+        assert_eq!(input.cursor_position(), (0, 0));
+
+        assert!(!input.handle_key_code(KeyCode::End));
+        assert!(!input.handle_key_code(KeyCode::Delete));
+        assert!(!input.handle_key_code(KeyCode::Right));
+        assert_eq!(input.cursor_position(), (0, 2));
+
+        input.set_value("x".to_string());
+        assert_eq!(input.cursor, 1);
+        input.set_value("aéb".to_string());
+        input.handle_key_code(KeyCode::End);
+        input.set_value("aéb".to_string());
+        assert_eq!(input.cursor, 4);
+        input.set_value("aé".to_string());
+        assert_eq!(input.cursor, 3);
+        input.handle_key_code(KeyCode::Left);
+        input.set_value("éa".to_string());
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn test_text_input_graphemes() {
+        let mut input = TextInput::new(&TextInputProps::default());
+        input.set_value("日本語".to_string());
+        input.handle_key_code(KeyCode::End);
+        assert_eq!(input.cursor_position(), (0, 6));
+
+        // The cursor moves by whole wide characters, and its column is measured in cells.
+        assert!(!input.handle_key_code(KeyCode::Left));
+        assert_eq!(input.cursor, "日本".len());
+        assert_eq!(input.cursor_position(), (0, 4));
+        assert_eq!(input.cursor_width(), 2);
+        assert!(input.handle_key_code(KeyCode::Backspace));
+        assert_eq!(input.value, "日語");
+        assert_eq!(input.cursor_position(), (0, 2));
+
+        // A letter with a combining accent is a single cluster.
+        input.set_value("ae\u{301}b".to_string());
+        input.handle_key_code(KeyCode::Home);
+        input.handle_key_code(KeyCode::Right);
+        input.handle_key_code(KeyCode::Right);
+        assert_eq!(input.cursor, "ae\u{301}".len());
+        assert_eq!(input.cursor_position(), (0, 2));
+        assert!(input.handle_key_code(KeyCode::Backspace));
+        assert_eq!(input.value, "ab");
+        assert!(!input.handle_key_code(KeyCode::Left));
+        input.set_value("e\u{301}\u{302}b".to_string());
+        assert!(input.handle_key_code(KeyCode::Delete));
+        assert_eq!(input.value, "b");
+
+        // Setting a value moves the cursor out of any cluster it would land in.
+        input.handle_key_code(KeyCode::End);
+        input.set_value("e\u{301}".to_string());
+        assert_eq!(input.cursor, 0);
+
+        // A zero width joiner between letters stays with the letter before it, and Hangul jamo
+        // combine into a single syllable.
+        input.set_value("a\u{200d}b".to_string());
+        input.handle_key_code(KeyCode::End);
+        assert!(input.handle_key_code(KeyCode::Backspace));
+        assert_eq!(input.value, "a\u{200d}");
+        input.set_value("\u{1100}\u{1161}\u{11a8}x".to_string());
+        input.handle_key_code(KeyCode::Home);
+        input.handle_key_code(KeyCode::Right);
+        assert_eq!(input.cursor, "\u{1100}\u{1161}\u{11a8}".len());
+        assert!(input.handle_key_code(KeyCode::Backspace));
+        assert_eq!(input.value, "x");
+
+        input.mask = Some('*');
+        assert_eq!(input.display("ce\u{301}\n日"), "**\n*");
+
+        assert_eq!(
+            element! {
+                Box(width: 5, height: 1) {
+                    TextInput(value: "中文字", has_focus: true)
+                }
+            }
+            .to_string(),
+            "文字 \n"
+        );
+    }
+
+    #[test]
+    fn test_text_input_rendering() {
+        assert_eq!(
+            element! {
+                Box(width: 10, height: 1) {
+                    TextInput(placeholder: "name")
+                }
+            }
+            .to_string(),
+            "name\n"
+        );
+
+        // When the value is too long, the view scrolls to keep the cursor visible.
+        assert_eq!(
+            element! {
+                Box(width: 5, height: 1) {
+                    TextInput(value: "hello world", has_focus: true)
+                }
+            }
+            .to_string(),
+            "orld \n"
+        );
+    }
+
+    #[test]
+    fn test_text_input_mask() {
+        let mut input = TextInput::new(&TextInputProps::default());
+        input.mask = Some('*');
+        input.set_value("pässword".to_string());
+        input.handle_key_code(KeyCode::End);
+        input.handle_key_code(KeyCode::Left);
+        assert_eq!(input.cursor_position(), (0, 7));
+        assert_eq!(input.display(&input.value), "********");
+
+        assert_eq!(
+            element! {
+                Box(width: 10, height: 1) {
+                    TextInput(value: "secret", mask: '*')
+                }
+            }
+            .to_string(),
+            "******\n"
+        );
+
+        // The cursor is positioned within the masked value, which scrolls like any other.
+        assert_eq!(
+            element! {
+                Box(width: 5, height: 1) {
+                    TextInput(value: "hunter2", mask: '•', has_focus: true)
+                }
+            }
+            .to_string(),
+            "•••• \n"
+        );
+    }
 }
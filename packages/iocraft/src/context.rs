@@ -1,5 +1,11 @@
+use crate::{
+    clock::Clock,
+    components::PortalManager,
+    hooks::{FocusManager, KeymapManager, LayoutRectManager},
+    render::FrameStats,
+};
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Ref, RefCell, RefMut},
     mem,
 };
@@ -7,11 +13,17 @@ use std::{
 /// The system context, which is always available to all components.
 pub struct SystemContext {
     should_exit: bool,
+    pub(crate) frame_stats: Option<FrameStats>,
+    is_blink_enabled: bool,
 }
 
 impl SystemContext {
     pub(crate) fn new() -> Self {
-        Self { should_exit: false }
+        Self {
+            should_exit: false,
+            frame_stats: None,
+            is_blink_enabled: true,
+        }
     }
 
     /// If called from a component that is being dynamically rendered, this will cause the render
@@ -20,11 +32,46 @@ impl SystemContext {
         self.should_exit = true;
     }
 
+    /// Sets whether blinking text is rendered with the blink attribute. When disabled, such text
+    /// is rendered without blinking. This is enabled by default.
+    ///
+    /// Many users find blinking text distracting, so applications may want to offer a way to
+    /// turn it off.
+    pub fn set_blink_enabled(&mut self, enabled: bool) {
+        self.is_blink_enabled = enabled;
+    }
+
+    /// Returns whether blinking text is rendered with the blink attribute.
+    pub fn is_blink_enabled(&self) -> bool {
+        self.is_blink_enabled
+    }
+
+    /// Returns the statistics for the most recently completed render, or `None` during the first
+    /// render.
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        self.frame_stats
+    }
+
     pub(crate) fn should_exit(&self) -> bool {
         self.should_exit
     }
 }
 
+/// State which is shared by the whole tree and used to implement the built-in hooks.
+///
+/// Unlike the [`SystemContext`], this isn't stored in a `RefCell`, so hooks can always get to it,
+/// even while their component holds a mutable reference to the context.
+#[derive(Default)]
+pub(crate) struct SystemState {
+    pub focus_manager: FocusManager,
+    pub keymap_manager: KeymapManager,
+    pub layout_rect_manager: LayoutRectManager,
+    pub portal_manager: PortalManager,
+    // The statistics for the most recently completed render, mirroring the system context's.
+    pub frame_stats: Option<FrameStats>,
+    pub clock: Clock,
+}
+
 /// A context that can be passed to components.
 pub enum Context<'a> {
     /// Provides the context via a mutable reference. Children will be able to get mutable or
@@ -75,8 +122,16 @@ impl<'a> Context<'a> {
         }
     }
 
+    fn type_id(&self) -> TypeId {
+        match self {
+            Context::Mut(context) => Any::type_id(&**context),
+            Context::Ref(context) => Any::type_id(&**context),
+            Context::Owned(context) => Any::type_id(&**context),
+        }
+    }
+
     #[doc(hidden)]
-    pub fn borrow(&mut self) -> Context {
+    pub fn borrow(&mut self) -> Context<'_> {
         match self {
             Context::Mut(context) => Context::Mut(*context),
             Context::Ref(context) => Context::Ref(*context),
@@ -85,18 +140,37 @@ impl<'a> Context<'a> {
     }
 }
 
+/// Pops the last context from a stack when dropped, so that the stack is reverted even if a
+/// component panics while the context is provided.
+struct PopContextOnDrop<'a, 'b>(&'a mut ContextStack<'b>);
+
+impl Drop for PopContextOnDrop<'_, '_> {
+    fn drop(&mut self) {
+        self.0.contexts.pop();
+    }
+}
+
 #[doc(hidden)]
 pub struct ContextStack<'a> {
-    contexts: Vec<RefCell<Context<'a>>>,
+    // Each context is stored with the type id of its value so that lookups can find the nearest
+    // context of a type without borrowing the others.
+    contexts: Vec<(TypeId, RefCell<Context<'a>>)>,
+    system: &'a SystemState,
 }
 
 impl<'a> ContextStack<'a> {
-    pub(crate) fn root(root_context: &'a mut dyn Any) -> Self {
+    pub(crate) fn root(root_context: &'a mut dyn Any, system: &'a SystemState) -> Self {
+        let context = Context::Mut(root_context);
         Self {
-            contexts: vec![RefCell::new(Context::Mut(root_context))],
+            contexts: vec![(context.type_id(), RefCell::new(context))],
+            system,
         }
     }
 
+    pub(crate) fn system(&self) -> &'a SystemState {
+        self.system
+    }
+
     pub(crate) fn with_context<'b, F>(&'b mut self, context: Option<Context<'b>>, f: F)
     where
         F: FnOnce(&mut ContextStack),
@@ -106,37 +180,40 @@ impl<'a> ContextStack<'a> {
             // append a shorter-lived context, we need to transmute 'a to the shorter lifetime.
             //
             // This is only safe because we don't allow any other changes to the stack, and we
-            // revert the stack right after the call.
+            // revert the stack right after the call, even if it panics.
             let shorter_lived_self =
                 unsafe { mem::transmute::<&mut Self, &mut ContextStack<'b>>(self) };
-            shorter_lived_self.contexts.push(RefCell::new(context));
-            f(shorter_lived_self);
-            shorter_lived_self.contexts.pop();
+            shorter_lived_self
+                .contexts
+                .push((context.type_id(), RefCell::new(context)));
+            let guard = PopContextOnDrop(shorter_lived_self);
+            f(&mut *guard.0);
         } else {
             f(self);
         }
     }
 
-    pub fn get_context<T: Any>(&self) -> Option<Ref<T>> {
-        for context in self.contexts.iter().rev() {
-            if let Ok(context) = context.try_borrow() {
-                if let Ok(ret) = Ref::filter_map(context, |context| context.downcast_ref::<T>()) {
-                    return Some(ret);
-                }
-            }
-        }
-        None
+    /// Returns the nearest context of the given type. Contexts of the same type provided further
+    /// up the tree are shadowed, so if the nearest one is already mutably borrowed, this returns
+    /// `None`.
+    pub fn get_context<T: Any>(&self) -> Option<Ref<'_, T>> {
+        let context = self.nearest::<T>()?.try_borrow().ok()?;
+        Ref::filter_map(context, |context| context.downcast_ref::<T>()).ok()
     }
 
-    pub fn get_context_mut<T: Any>(&self) -> Option<RefMut<T>> {
-        for context in self.contexts.iter().rev() {
-            if let Ok(context) = context.try_borrow_mut() {
-                if let Ok(ret) = RefMut::filter_map(context, |context| context.downcast_mut::<T>())
-                {
-                    return Some(ret);
-                }
-            }
-        }
-        None
+    /// Returns the nearest context of the given type. Contexts of the same type provided further
+    /// up the tree are shadowed, so if the nearest one is already borrowed or was not provided
+    /// mutably, this returns `None`.
+    pub fn get_context_mut<T: Any>(&self) -> Option<RefMut<'_, T>> {
+        let context = self.nearest::<T>()?.try_borrow_mut().ok()?;
+        RefMut::filter_map(context, |context| context.downcast_mut::<T>()).ok()
+    }
+
+    fn nearest<T: Any>(&self) -> Option<&RefCell<Context<'a>>> {
+        self.contexts
+            .iter()
+            .rev()
+            .find(|(type_id, _)| *type_id == TypeId::of::<T>())
+            .map(|(_, context)| context)
     }
 }
@@ -1,26 +1,40 @@
 use crate::{
+    color,
     component::{Component, ComponentHelper, ComponentHelperExt},
+    components::{Fragment, FragmentProps},
     props::AnyProps,
-    render, terminal_render_loop, Canvas, Terminal,
+    render, render_loop, Canvas, RenderLoopOptions,
 };
 use any_key::AnyHash;
 use crossterm::{terminal, tty::IsTty};
 use std::{
-    fmt::Debug,
+    any::TypeId,
+    fmt::{self, Debug},
     future::Future,
-    hash::Hash,
+    hash::{Hash, Hasher},
     io::{self, stderr, stdout, Write},
     os::fd::AsRawFd,
     rc::Rc,
 };
 
-/// Used by the `element!` macro to extend a collection with elements.
-#[doc(hidden)]
-pub trait ExtendWithElements<T>: Sized {
+/// A value which can be given as a child via a `#()` block in the [`element!`](crate::element)
+/// macro, or passed to [`extend_with_elements`] directly.
+///
+/// This is implemented for:
+///
+/// - [`Element`]s, which are added as a single child.
+/// - [`AnyElement`]s, which are added as a single child.
+/// - Anything that implements [`IntoIterator`] with items that can be converted into the child
+///   type. This includes `Option<E>` for conditional children, `Vec<E>`, and iterators such as
+///   the result of [`Iterator::map`], so there's no need to collect into a vector first.
+///
+/// Here, `T` is the type of the children being extended, e.g. [`AnyElement`].
+pub trait ExtendElements<T>: Sized {
+    /// Adds the element or elements to `dest`.
     fn extend<E: Extend<T>>(self, dest: &mut E);
 }
 
-impl<'a, T, U> ExtendWithElements<T> for Element<'a, U>
+impl<'a, T, U> ExtendElements<T> for Element<'a, U>
 where
     U: ElementType + 'a,
     T: From<Element<'a, U>>,
@@ -30,13 +44,13 @@ where
     }
 }
 
-impl<'a> ExtendWithElements<AnyElement<'a>> for AnyElement<'a> {
+impl<'a> ExtendElements<AnyElement<'a>> for AnyElement<'a> {
     fn extend<E: Extend<AnyElement<'a>>>(self, dest: &mut E) {
         dest.extend([self]);
     }
 }
 
-impl<T, U, I> ExtendWithElements<T> for I
+impl<T, U, I> ExtendElements<T> for I
 where
     I: IntoIterator<Item = U>,
     U: Into<T>,
@@ -46,25 +60,157 @@ where
     }
 }
 
-/// Used by the `element!` macro to extend a collection with elements.
-#[doc(hidden)]
+/// Extends a collection of children with an element or elements. This is what the
+/// [`element!`](crate::element) macro uses to add children. See [`ExtendElements`] for the types
+/// which are accepted.
 pub fn extend_with_elements<T, U, E>(dest: &mut T, elements: U)
 where
     T: Extend<E>,
-    U: ExtendWithElements<E>,
+    U: ExtendElements<E>,
 {
     elements.extend(dest);
 }
 
 /// Used to identify an element within the scope of its parent. This is used to minimize the number
 /// of times components are destroyed and recreated from render-to-render.
-#[derive(Clone, Hash, PartialEq, Eq, Debug)]
-pub struct ElementKey(Rc<Box<dyn AnyHash>>);
+///
+/// Keys must be unique among siblings. In debug builds, a warning is printed if multiple siblings
+/// are given the same key via the `key` property, and if the `panic-on-duplicate-keys` feature is
+/// enabled, a panic occurs instead.
+///
+/// Two keys are equal if they were constructed from equal values of the same type. For example,
+/// `ElementKey::from(1u64)` and `ElementKey::from(1u32)` are different keys, but
+/// `ElementKey::from("a")` and `ElementKey::from(String::from("a"))` are the same, since string
+/// slices are converted to owned strings.
+///
+/// # Keys created by the `element!` macro
+///
+/// The [`element!`](crate::element) macro generates a unique `u128` for each element declaration
+/// in the source code. An element declared without a `key` property gets the key
+/// `ElementKey::new(decl_key)`, and an element declared with `key: value` gets the key
+/// `ElementKey::explicit(decl_key, value)`. So library code which constructs elements dynamically
+/// can produce keys which never collide with those of declared elements by using
+/// [`ElementKey::new`] or [`From`] with values of its own types, or can mimic a declaration by
+/// choosing its own `decl_key`:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// // A made-up declaration key, unique to this call site.
+/// const ROW_DECL_KEY: u128 = 0x5d1c_1b7e_8a4f_4c1e_9f0e_2b6d_0a3c_7e51;
+///
+/// fn rows(names: &[&str]) -> Vec<AnyElement<'static>> {
+///     names
+///         .iter()
+///         .map(|name| {
+///             Element::<Text> {
+///                 key: ElementKey::explicit(ROW_DECL_KEY, name.to_string()),
+///                 props: TextProps {
+///                     content: name.to_string(),
+///                     ..Default::default()
+///                 },
+///             }
+///             .into()
+///         })
+///         .collect()
+/// }
+/// # assert_eq!(rows(&["a", "b"]).len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct ElementKey {
+    key: Rc<dyn AnyHash>,
+    // If the key was given explicitly via the `key` property, this is the value that was given.
+    explicit: Option<Rc<dyn Debug>>,
+}
 
 impl ElementKey {
     /// Constructs a new key.
     pub fn new<K: Debug + Hash + Eq + 'static>(key: K) -> Self {
-        Self(Rc::new(Box::new(key)))
+        Self {
+            key: Rc::new(key),
+            explicit: None,
+        }
+    }
+
+    /// Constructs the key that the [`element!`](crate::element) macro gives to an element
+    /// declared with `key: key`, where `decl_key` identifies the declaration.
+    ///
+    /// Keys constructed this way are reported by value if they're found to be duplicated among
+    /// siblings.
+    pub fn explicit<K: Debug + Hash + Eq + 'static>(decl_key: u128, key: K) -> Self {
+        let key = Rc::new(ExplicitKey { decl_key, key });
+        Self {
+            key: key.clone(),
+            explicit: Some(key),
+        }
+    }
+
+    /// Constructs the key of an element which is spliced into the children of the element with
+    /// this key, such as a child of a [`Fragment`]. The key is unique as long as the child's key is
+    /// unique among its original siblings.
+    pub(crate) fn nested(&self, child: &ElementKey) -> Self {
+        Self {
+            key: Rc::new((self.clone(), child.clone())),
+            explicit: child.explicit.clone(),
+        }
+    }
+
+    /// Returns the value given via the `key` property, if the key was given explicitly.
+    pub(crate) fn explicit_value(&self) -> Option<&dyn Debug> {
+        self.explicit.as_deref()
+    }
+}
+
+macro_rules! impl_from_for_element_key {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for ElementKey {
+                fn from(key: $ty) -> Self {
+                    Self::new(key)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_element_key!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, bool, String
+);
+
+impl From<&str> for ElementKey {
+    fn from(key: &str) -> Self {
+        Self::new(key.to_string())
+    }
+}
+
+impl PartialEq for ElementKey {
+    fn eq(&self, other: &Self) -> bool {
+        *self.key == *other.key
+    }
+}
+
+impl Eq for ElementKey {}
+
+impl Hash for ElementKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.key).hash(state)
+    }
+}
+
+impl Debug for ElementKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ElementKey").field(&self.key).finish()
+    }
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct ExplicitKey<K> {
+    decl_key: u128,
+    key: K,
+}
+
+impl<K: Debug> Debug for ExplicitKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key.fmt(f)
     }
 }
 
@@ -88,6 +234,29 @@ pub trait ElementType {
 }
 
 /// A type-erased element that can be created from any [`Element`].
+///
+/// This makes it possible to store elements of different component types together, for example
+/// when the type of a component depends on runtime data:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # enum Field { Label(String), Input(String) }
+/// # fn my_element(fields: Vec<Field>) -> Element<'static, Box> {
+/// let children: Vec<AnyElement<'static>> = fields
+///     .into_iter()
+///     .map(|field| match field {
+///         Field::Label(text) => element!(Text(content: text)).into_any(),
+///         Field::Input(value) => element!(TextInput(value: value)).into(),
+///     })
+///     .collect();
+///
+/// element! {
+///     Box(flex_direction: FlexDirection::Column) {
+///         #(children)
+///     }
+/// }
+/// # }
+/// ```
 pub struct AnyElement<'a> {
     key: ElementKey,
     props: AnyProps<'a>,
@@ -117,6 +286,17 @@ where
     }
 }
 
+// This allows elements to be given directly to optional slot properties, such as titles or
+// headers.
+impl<'a, T> From<Element<'a, T>> for Option<AnyElement<'a>>
+where
+    T: Component + 'a,
+{
+    fn from(e: Element<'a, T>) -> Self {
+        Some(e.into())
+    }
+}
+
 impl<'a, 'b: 'a, T> From<&'a mut Element<'b, T>> for AnyElement<'a>
 where
     T: Component,
@@ -130,6 +310,47 @@ where
     }
 }
 
+impl<'a, 'b: 'a> From<&'a mut AnyElement<'b>> for AnyElement<'a> {
+    fn from(e: &'a mut AnyElement<'b>) -> Self {
+        Self {
+            key: e.key.clone(),
+            props: e.props.borrow(),
+            helper: e.helper.copy(),
+        }
+    }
+}
+
+/// Converts the given children to [`AnyElement`]s, replacing any [`Fragment`]s with their children,
+/// recursively.
+pub(crate) fn flatten_fragments<'a, T: ElementExt>(children: &'a mut [T]) -> Vec<AnyElement<'a>> {
+    fn push<'a>(element: AnyElement<'a>, dest: &mut Vec<AnyElement<'a>>) {
+        if element.helper.component_type_id() != TypeId::of::<Fragment>() {
+            dest.push(element);
+            return;
+        }
+        // SAFETY: The component is a fragment, and the props are borrowed from the caller's
+        // children for `'a`.
+        let props = unsafe { element.props.into_mut_unchecked::<FragmentProps<'a>>() };
+        for child in props.children.iter_mut() {
+            let key = element.key.nested(&child.key);
+            let mut child = AnyElement::from(child);
+            child.key = key;
+            push(child, dest);
+        }
+    }
+
+    let mut flattened = Vec::with_capacity(children.len());
+    for child in children {
+        let element = AnyElement {
+            key: child.key().clone(),
+            helper: child.helper(),
+            props: child.props_mut(),
+        };
+        push(element, &mut flattened);
+    }
+    flattened
+}
+
 mod private {
     use super::*;
 
@@ -146,7 +367,7 @@ pub trait ElementExt: private::Sealed + Sized {
     fn key(&self) -> &ElementKey;
 
     #[doc(hidden)]
-    fn props_mut(&mut self) -> AnyProps;
+    fn props_mut(&mut self) -> AnyProps<'_>;
 
     #[doc(hidden)]
     fn helper(&self) -> Box<dyn ComponentHelperExt>;
@@ -163,6 +384,109 @@ pub trait ElementExt: private::Sealed + Sized {
         self.render(None).to_string()
     }
 
+    /// Renders the element into a string with ANSI escape codes, exactly as it would be written
+    /// to a terminal by [`print`](ElementExt::print), but without touching the terminal.
+    ///
+    /// If `max_width` is given, the element is laid out as if the terminal were that wide. This is
+    /// useful for snapshot tests of styled output.
+    fn render_to_string(&mut self, max_width: Option<usize>) -> String {
+        let mut buf = Vec::new();
+        self.render(max_width)
+            .write_ansi(&mut buf)
+            .expect("writing to a buffer should never fail");
+        String::from_utf8(buf).expect("the canvas should always be valid utf-8")
+    }
+
+    /// Renders the element into a string without any styles, as it would appear in a terminal of
+    /// the given width with all colors and attributes removed.
+    ///
+    /// The layout is exactly the same as when rendering to a terminal: gaps are filled with
+    /// spaces, and borders are drawn with their box-drawing characters. This makes it useful for
+    /// writing the same output that an interactive program shows to logs or CI output, where
+    /// ANSI escape codes would just be noise.
+    fn render_to_plain_text(&mut self, max_width: Option<usize>) -> String {
+        self.render(max_width).to_string()
+    }
+
+    /// Renders the element into a string with ANSI escape codes for its styles, but no cursor
+    /// movement or clearing commands.
+    ///
+    /// Unlike [`render_to_string`](ElementExt::render_to_string), the output is a self-contained
+    /// dump which can be replayed anywhere, so it's suitable for writing colored reports to files
+    /// or piping them to programs such as `less -R`. Lines end with `\n`, and the styles are reset
+    /// at the end of each line.
+    fn render_to_ansi(&mut self, max_width: Option<usize>) -> String {
+        let mut buf = Vec::new();
+        self.render(max_width)
+            .write_ansi_styles(&mut buf)
+            .expect("writing to a buffer should never fail");
+        String::from_utf8(buf).expect("the canvas should always be valid utf-8")
+    }
+
+    /// Renders the element into a framed grid with inline style annotations, for debugging and
+    /// snapshot tests. See [`Canvas::write_debug_grid`] for the format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// let grid = element! {
+    ///     Box(width: 4) {
+    ///         Text(content: "hi", color: Color::Red)
+    ///     }
+    /// }
+    /// .render_to_debug_grid(None, true);
+    /// assert_eq!(grid, "   0123\n  ┌────┐\n0 │[red]hi[/red]  │\n  └────┘\n");
+    /// ```
+    fn render_to_debug_grid(&mut self, max_width: Option<usize>, axis_labels: bool) -> String {
+        let mut buf = Vec::new();
+        self.render(max_width)
+            .write_debug_grid(&mut buf, axis_labels)
+            .expect("writing to a buffer should never fail");
+        String::from_utf8(buf).expect("the canvas should always be valid utf-8")
+    }
+
+    /// Renders the element into a static HTML `<pre>` element with inline CSS, which approximates
+    /// how it looks in a terminal. See [`Canvas::write_html`] for details.
+    ///
+    /// This is useful for showcasing output in documentation or on the web without recording a
+    /// terminal. It requires the `html` feature.
+    #[cfg(feature = "html")]
+    fn render_to_html(&mut self, max_width: Option<usize>) -> String {
+        let mut buf = Vec::new();
+        self.render(max_width)
+            .write_html(&mut buf)
+            .expect("writing to a buffer should never fail");
+        String::from_utf8(buf).expect("the canvas should always be valid utf-8")
+    }
+
+    /// Renders the element into the given area of a [ratatui](https://ratatui.rs) buffer, so that
+    /// it can be embedded in a ratatui app. See [`Canvas::write_ratatui_buffer`] for details.
+    ///
+    /// The element is laid out as if the terminal were as wide as the area, and anything which
+    /// doesn't fit within the area is clipped. It requires the `ratatui` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    ///
+    /// let area = Rect::new(0, 0, 8, 1);
+    /// let mut buf = Buffer::empty(area);
+    /// element!(Text(content: "hello")).render_to_ratatui_buffer(area, &mut buf);
+    /// assert_eq!(buf, Buffer::with_lines(["hello   "]));
+    /// ```
+    #[cfg(feature = "ratatui")]
+    fn render_to_ratatui_buffer(
+        &mut self,
+        area: ratatui::layout::Rect,
+        buf: &mut ratatui::buffer::Buffer,
+    ) {
+        self.render(Some(area.width as usize))
+            .write_ratatui_buffer(area, buf);
+    }
+
     /// Renders the element and prints it to stdout.
     fn print(&mut self) {
         self.write_to_raw_fd(stdout()).unwrap();
@@ -184,7 +508,10 @@ pub trait ElementExt: private::Sealed + Sized {
     fn write_to_raw_fd<F: Write + AsRawFd>(&mut self, fd: F) -> io::Result<()> {
         if fd.is_tty() {
             let (width, _) = terminal::size().expect("we should be able to get the terminal size");
-            let canvas = self.render(Some(width as _));
+            let mut canvas = self.render(Some(width as _));
+            if !color::supports_truecolor() {
+                canvas.map_colors(color::to_ansi256);
+            }
             canvas.write_ansi(fd)
         } else {
             self.write(fd)
@@ -193,17 +520,78 @@ pub trait ElementExt: private::Sealed + Sized {
 
     /// Renders the element in a loop, allowing it to be dynamic and interactive.
     ///
-    /// This method should only be used if when stdio is a TTY terminal. If for example, stdout is
-    /// a file, this will probably not produce the desired result. You can determine whether stdout
-    /// is a TTY with [`stdout_is_tty`](crate::stdout_is_tty).
+    /// If stdout is not a TTY terminal, for example because it's piped to another program or
+    /// redirected to a file, there's nothing to interact with, so the element is instead rendered
+    /// once as plain text, without any escape sequences, and the method returns immediately. Use
+    /// [`stdout_is_tty`](crate::stdout_is_tty) to choose a different fallback, such as rendering
+    /// with [`render_to_ansi`](ElementExt::render_to_ansi), or
+    /// [`render_loop_with`](ElementExt::render_loop_with) to run the loop regardless.
     fn render_loop(&mut self) -> impl Future<Output = io::Result<()>>;
 
     /// Renders the element as fullscreen in a loop, allowing it to be dynamic and interactive.
     ///
-    /// This method should only be used if when stdio is a TTY terminal. If for example, stdout is
-    /// a file, this will probably not produce the desired result. You can determine whether stdout
-    /// is a TTY with [`stdout_is_tty`](crate::stdout_is_tty).
+    /// The element is rendered to the terminal's alternate screen, and raw mode is enabled while
+    /// it listens for terminal events. When the loop exits, the terminal is returned to its prior
+    /// state. This also happens if a component panics, in which case the terminal is restored
+    /// before the panic message is printed so that it remains readable.
+    ///
+    /// Like [`render_loop`](ElementExt::render_loop), if stdout is not a TTY terminal, the element
+    /// is rendered once as plain text instead.
     fn fullscreen(&mut self) -> impl Future<Output = io::Result<()>>;
+
+    /// Renders the element in a loop like [`render_loop`](ElementExt::render_loop), but to the
+    /// given output rather than stdout.
+    ///
+    /// This allows an interactive UI to be rendered to stderr, so that stdout stays clean for
+    /// output which is meant for other programs. If the output isn't a TTY terminal, the element
+    /// is rendered to it once as plain text instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use iocraft::prelude::*;
+    /// # async fn run() -> std::io::Result<()> {
+    /// element!(Text(content: "Working...")).render_loop_to(std::io::stderr()).await?;
+    /// println!("{{\"status\": \"done\"}}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn render_loop_to<F>(&mut self, output: F) -> impl Future<Output = io::Result<()>>
+    where
+        F: Write + AsRawFd + Send + 'static;
+
+    /// Renders the element as fullscreen in a loop like [`fullscreen`](ElementExt::fullscreen),
+    /// but to the given output rather than stdout.
+    ///
+    /// If the output isn't a TTY terminal, the element is rendered to it once as plain text
+    /// instead.
+    fn fullscreen_to<F>(&mut self, output: F) -> impl Future<Output = io::Result<()>>
+    where
+        F: Write + AsRawFd + Send + 'static;
+
+    /// Renders the element in a loop to the given output, with the given options.
+    ///
+    /// By default, this behaves like [`render_loop_to`](ElementExt::render_loop_to), but the
+    /// options can override whether the output is treated as a TTY terminal, e.g. to run the loop
+    /// even if the output isn't detected as one:
+    ///
+    /// ```no_run
+    /// # use iocraft::prelude::*;
+    /// # async fn run() -> std::io::Result<()> {
+    /// let options = RenderLoopOptions {
+    ///     tty: Some(true),
+    ///     ..Default::default()
+    /// };
+    /// element!(Text(content: "Working...")).render_loop_with(std::io::stdout(), options).await
+    /// # }
+    /// ```
+    fn render_loop_with<F>(
+        &mut self,
+        output: F,
+        options: RenderLoopOptions,
+    ) -> impl Future<Output = io::Result<()>>
+    where
+        F: Write + AsRawFd + Send + 'static;
 }
 
 impl<'a> ElementExt for AnyElement<'a> {
@@ -211,7 +599,7 @@ impl<'a> ElementExt for AnyElement<'a> {
         &self.key
     }
 
-    fn props_mut(&mut self) -> AnyProps {
+    fn props_mut(&mut self) -> AnyProps<'_> {
         self.props.borrow()
     }
 
@@ -225,11 +613,64 @@ impl<'a> ElementExt for AnyElement<'a> {
     }
 
     async fn render_loop(&mut self) -> io::Result<()> {
-        terminal_render_loop(self, Terminal::new()?).await
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
     }
 
     async fn fullscreen(&mut self) -> io::Result<()> {
-        terminal_render_loop(self, Terminal::fullscreen()?).await
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
+    }
+
+    async fn render_loop_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn fullscreen_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn render_loop_with<F>(&mut self, output: F, options: RenderLoopOptions) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(self, options, output).await
     }
 }
 
@@ -238,7 +679,7 @@ impl<'a> ElementExt for &mut AnyElement<'a> {
         &self.key
     }
 
-    fn props_mut(&mut self) -> AnyProps {
+    fn props_mut(&mut self) -> AnyProps<'_> {
         self.props.borrow()
     }
 
@@ -252,11 +693,64 @@ impl<'a> ElementExt for &mut AnyElement<'a> {
     }
 
     async fn render_loop(&mut self) -> io::Result<()> {
-        terminal_render_loop(&mut **self, Terminal::new()?).await
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
     }
 
     async fn fullscreen(&mut self) -> io::Result<()> {
-        terminal_render_loop(&mut **self, Terminal::fullscreen()?).await
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
+    }
+
+    async fn render_loop_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn fullscreen_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn render_loop_with<F>(&mut self, output: F, options: RenderLoopOptions) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(&mut **self, options, output).await
     }
 }
 
@@ -268,7 +762,7 @@ where
         &self.key
     }
 
-    fn props_mut(&mut self) -> AnyProps {
+    fn props_mut(&mut self) -> AnyProps<'_> {
         AnyProps::borrowed(&mut self.props)
     }
 
@@ -282,11 +776,64 @@ where
     }
 
     async fn render_loop(&mut self) -> io::Result<()> {
-        terminal_render_loop(self, Terminal::new()?).await
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
     }
 
     async fn fullscreen(&mut self) -> io::Result<()> {
-        terminal_render_loop(self, Terminal::fullscreen()?).await
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
+    }
+
+    async fn render_loop_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn fullscreen_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn render_loop_with<F>(&mut self, output: F, options: RenderLoopOptions) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(self, options, output).await
     }
 }
 
@@ -298,7 +845,7 @@ where
         &self.key
     }
 
-    fn props_mut(&mut self) -> AnyProps {
+    fn props_mut(&mut self) -> AnyProps<'_> {
         AnyProps::borrowed(&mut self.props)
     }
 
@@ -312,11 +859,64 @@ where
     }
 
     async fn render_loop(&mut self) -> io::Result<()> {
-        terminal_render_loop(&mut **self, Terminal::new()?).await
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
     }
 
     async fn fullscreen(&mut self) -> io::Result<()> {
-        terminal_render_loop(&mut **self, Terminal::fullscreen()?).await
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            stdout(),
+        )
+        .await
+    }
+
+    async fn render_loop_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: false,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn fullscreen_to<F>(&mut self, output: F) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(
+            &mut **self,
+            RenderLoopOptions {
+                fullscreen: true,
+                ..Default::default()
+            },
+            output,
+        )
+        .await
+    }
+
+    async fn render_loop_with<F>(&mut self, output: F, options: RenderLoopOptions) -> io::Result<()>
+    where
+        F: Write + AsRawFd + Send + 'static,
+    {
+        render_loop(&mut **self, options, output).await
     }
 }
 
@@ -325,6 +925,7 @@ mod tests {
     use crate::prelude::*;
 
     #[test]
+    #[allow(clippy::needless_borrow, clippy::unnecessary_mut_passed)]
     fn test_element() {
         let mut box_element = element!(Box);
         box_element.key();
@@ -347,4 +948,81 @@ mod tests {
         any_element_ref.print();
         any_element_ref.eprint();
     }
+
+    #[test]
+    fn test_element_key() {
+        use std::collections::HashSet;
+
+        assert_eq!(ElementKey::from(1u32), ElementKey::new(1u32));
+        assert_ne!(ElementKey::from(1u32), ElementKey::from(1u64));
+        assert_eq!(ElementKey::from("a"), ElementKey::from("a".to_string()));
+        assert_ne!(ElementKey::from("a"), ElementKey::from("b"));
+        assert_eq!(ElementKey::explicit(1, "a"), ElementKey::explicit(1, "a"));
+        assert_ne!(ElementKey::explicit(1, "a"), ElementKey::explicit(2, "a"));
+        assert_ne!(
+            ElementKey::explicit(1, 2u128),
+            ElementKey::new((1u128, 2u128))
+        );
+
+        let keys: HashSet<_> = [
+            ElementKey::from(1),
+            ElementKey::from(1),
+            ElementKey::from('a'),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(keys.len(), 2);
+
+        // The macro combines the key with the declaration, so the same declaration always gives
+        // the same key, which is distinct from the bare value.
+        let keys: Vec<_> = (0..2).map(|_| element!(Text(key: "x")).key).collect();
+        assert_eq!(keys[0], keys[1]);
+        assert_ne!(keys[0], ElementKey::from("x"));
+    }
+
+    #[test]
+    fn test_render_to_string() {
+        let mut e = element! {
+            Text(content: "foo bar", color: Color::Red, weight: Weight::Bold)
+        };
+        assert_eq!(
+            e.render_to_string(Some(4)),
+            "\x1b[0m\x1b[38;5;9m\x1b[1mfoo\x1b[K\r\nbar\x1b[K\r\n\x1b[0m"
+        );
+        assert_eq!(
+            e.render_to_string(None),
+            "\x1b[0m\x1b[38;5;9m\x1b[1mfoo bar\x1b[K\r\n\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_to_plain_text() {
+        let mut e = element! {
+            Box(border_style: BorderStyle::Single, border_color: Color::Blue, padding_left: 1) {
+                Text(content: "foo bar", color: Color::Red, weight: Weight::Bold)
+            }
+        };
+        assert_eq!(
+            e.render_to_plain_text(Some(8)),
+            "┌──────┐\n│ foo  │\n│ bar  │\n└──────┘\n"
+        );
+        assert_eq!(
+            e.render_to_plain_text(None),
+            "┌────────┐\n│ foo bar│\n└────────┘\n"
+        );
+    }
+
+    #[test]
+    fn test_render_to_ansi() {
+        let mut e = element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: "foo bar", color: Color::Red, weight: Weight::Bold)
+                Text(content: "baz")
+            }
+        };
+        assert_eq!(
+            e.render_to_ansi(Some(4)),
+            "\x1b[0m\x1b[38;5;9m\x1b[1mfoo\x1b[0m\n\x1b[38;5;9m\x1b[1mbar\x1b[0m\nbaz\n\x1b[0m"
+        );
+    }
 }
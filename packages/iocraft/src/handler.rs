@@ -37,9 +37,206 @@ impl<'a, T> Handler<'a, T> {
     }
 }
 
+impl<'a, T: 'a> Handler<'a, T> {
+    /// Creates a handler which forwards each value it's invoked with to a channel.
+    ///
+    /// This works with any [`HandlerSender`], which includes the senders of the standard library's
+    /// [`mpsc`](std::sync::mpsc) channels and the [`futures`] crate's
+    /// [`mpsc`](futures::channel::mpsc) channels. Because handlers are invoked from the UI, they
+    /// never block: if a bounded channel is full, the value is dropped. Values sent after the
+    /// receiver is dropped are dropped as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// # use std::sync::mpsc;
+    /// let (tx, rx) = mpsc::channel();
+    /// let mut handler = Handler::from_sender(tx.clone());
+    /// handler.invoke(42);
+    /// assert_eq!(rx.try_recv(), Ok(42));
+    /// ```
+    pub fn from_sender<S>(mut sender: S) -> Self
+    where
+        S: HandlerSender<T> + Send + 'a,
+    {
+        Self::from(move |value| sender.send_value(value))
+    }
+}
+
+/// A channel sender which a [`Handler`] can forward values to, via [`Handler::from_sender`].
+///
+/// Implementations must not block. If the value can't be sent immediately, for example because the
+/// channel is full or disconnected, it should be dropped.
+pub trait HandlerSender<T> {
+    /// Sends the value to the channel, or drops it if it can't be sent immediately.
+    fn send_value(&mut self, value: T);
+}
+
+impl<T> HandlerSender<T> for std::sync::mpsc::Sender<T> {
+    fn send_value(&mut self, value: T) {
+        let _ = self.send(value);
+    }
+}
+
+impl<T> HandlerSender<T> for std::sync::mpsc::SyncSender<T> {
+    fn send_value(&mut self, value: T) {
+        let _ = self.try_send(value);
+    }
+}
+
+impl<T> HandlerSender<T> for futures::channel::mpsc::Sender<T> {
+    fn send_value(&mut self, value: T) {
+        let _ = self.try_send(value);
+    }
+}
+
+impl<T> HandlerSender<T> for futures::channel::mpsc::UnboundedSender<T> {
+    fn send_value(&mut self, value: T) {
+        let _ = self.unbounded_send(value);
+    }
+}
+
+impl<'a, T: Clone + 'a> Handler<'a, T> {
+    /// Combines this handler with another, returning a handler which invokes both in order.
+    ///
+    /// Because both handlers take ownership of the value, the first handler is invoked with a
+    /// clone of it. If either handler is not set, the other is returned as-is.
+    pub fn chain(self, other: Handler<'a, T>) -> Handler<'a, T> {
+        match (self, other) {
+            (Self::None, other) => other,
+            (this, Self::None) => this,
+            (Self::Function(mut a), Self::Function(mut b)) => Self::from(move |value: T| {
+                a(value.clone());
+                b(value);
+            }),
+        }
+    }
+}
+
+/// `RefHandler` is a type representing an optional event handler which is invoked with a reference
+/// to a value, rather than taking ownership of it.
+///
+/// This is useful when the value is expensive to clone, or when the same value needs to be passed
+/// to several handlers. The handler's lifetime, `'a`, only constrains what the handler itself can
+/// capture. The value only needs to be borrowed for the duration of each call, so a handler stored
+/// for `'static` can still be invoked with a reference to a temporary:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// let mut handler: RefHandler<'static, KeyEvent> =
+///     RefHandler::from(|event: &KeyEvent| println!("{:?}", event.code));
+/// handler.invoke(&KeyEvent {
+///     code: KeyCode::Enter,
+///     modifiers: KeyModifiers::empty(),
+///     kind: KeyEventKind::Press,
+/// });
+/// ```
+///
+/// When passing a closure as a prop, the type of its argument may need to be annotated, since it
+/// can't always be inferred.
+#[derive(Default)]
+pub enum RefHandler<'a, T: ?Sized> {
+    /// No handler is set.
+    #[default]
+    None,
+    /// A function handler.
+    Function(Box<dyn FnMut(&T) + Send + 'a>),
+}
+
+impl<'a, T: ?Sized, F> From<F> for RefHandler<'a, T>
+where
+    F: FnMut(&T) + Send + 'a,
+{
+    fn from(f: F) -> Self {
+        Self::Function(Box::new(f))
+    }
+}
+
+impl<'a, T: ?Sized> RefHandler<'a, T> {
+    /// Returns `true` if the handler is not set.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Takes the handler, leaving `None` in its place.
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    /// Invokes the handler with a reference to the given value.
+    pub fn invoke(&mut self, value: &T) {
+        match self {
+            Self::Function(f) => f(value),
+            Self::None => {}
+        }
+    }
+}
+
+impl<'a, T: ?Sized + 'a> RefHandler<'a, T> {
+    /// Combines this handler with another, returning a handler which invokes both in order with
+    /// the same reference. If either handler is not set, the other is returned as-is.
+    pub fn chain(self, other: RefHandler<'a, T>) -> RefHandler<'a, T> {
+        match (self, other) {
+            (Self::None, other) => other,
+            (this, Self::None) => this,
+            (Self::Function(mut a), Self::Function(mut b)) => Self::from(move |value: &T| {
+                a(value);
+                b(value);
+            }),
+        }
+    }
+}
+
+/// `TryHandler` is a type representing an optional event handler which may fail.
+///
+/// This is the fallible counterpart to [`Handler`]. Rather than swallowing errors, the handler's
+/// result is returned to the caller of [`invoke`](TryHandler::invoke), which can decide how to
+/// deal with it.
+#[derive(Default)]
+pub enum TryHandler<'a, T, E> {
+    /// No handler is set.
+    #[default]
+    None,
+    /// A function handler.
+    Function(Box<dyn FnMut(T) -> Result<(), E> + Send + 'a>),
+}
+
+impl<'a, T, E, F> From<F> for TryHandler<'a, T, E>
+where
+    F: FnMut(T) -> Result<(), E> + Send + 'a,
+{
+    fn from(f: F) -> Self {
+        Self::Function(Box::new(f))
+    }
+}
+
+impl<'a, T, E> TryHandler<'a, T, E> {
+    /// Returns `true` if the handler is not set.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Takes the handler, leaving `None` in its place.
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    /// Invokes the handler with the given value, returning the handler's result. If the handler
+    /// is not set, this returns `Ok(())`.
+    pub fn invoke(&mut self, value: T) -> Result<(), E> {
+        match self {
+            Self::Function(f) => f(value),
+            Self::None => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::{executor::block_on, StreamExt};
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_handler() {
@@ -55,4 +252,113 @@ mod tests {
         handler.invoke(42);
         handler.take().invoke(42);
     }
+
+    #[test]
+    fn test_handler_chain() {
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+
+        let mut handler = Handler::from({
+            let first = first.clone();
+            move |value: i32| first.lock().unwrap().push(value)
+        })
+        .chain(Handler::from({
+            let second = second.clone();
+            move |value: i32| second.lock().unwrap().push(value)
+        }));
+        handler.invoke(42);
+        assert_eq!(*first.lock().unwrap(), vec![42]);
+        assert_eq!(*second.lock().unwrap(), vec![42]);
+
+        let mut handler = Handler::<i32>::None.chain(Handler::None);
+        assert!(handler.is_none());
+        handler.invoke(0);
+
+        let mut handler = Handler::None.chain(Handler::from({
+            let second = second.clone();
+            move |value: i32| second.lock().unwrap().push(value)
+        }));
+        handler.invoke(1);
+        assert_eq!(*second.lock().unwrap(), vec![42, 1]);
+    }
+
+    #[test]
+    fn test_handler_from_sender() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut handler = Handler::from_sender(tx);
+        handler.invoke(1);
+        handler.invoke(2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        // Values are dropped rather than blocking when a bounded channel is full.
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let mut handler = Handler::from_sender(tx);
+        handler.invoke(1);
+        handler.invoke(2);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1]);
+        drop(rx);
+        handler.invoke(3);
+
+        let (tx, rx) = futures::channel::mpsc::channel(0);
+        let mut handler = Handler::from_sender(tx);
+        handler.invoke(1);
+        handler.invoke(2);
+        drop(handler);
+        assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1]);
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut handler = Handler::from_sender(tx);
+        handler.invoke(1);
+        handler.invoke(2);
+        drop(handler);
+        assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_ref_handler() {
+        let mut handler = RefHandler::<str>::None;
+        assert!(handler.is_none());
+        handler.invoke("foo");
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let mut handler: RefHandler<'static, str> = RefHandler::from({
+            let values = values.clone();
+            move |value: &str| values.lock().unwrap().push(value.to_string())
+        })
+        .chain(RefHandler::from({
+            let values = values.clone();
+            move |value: &str| values.lock().unwrap().push(value.to_uppercase())
+        }));
+        assert!(!handler.is_none());
+
+        // The handler outlives the values it's invoked with.
+        for value in ["foo", "bar"] {
+            let value = value.to_string();
+            handler.invoke(&value);
+        }
+        assert_eq!(*values.lock().unwrap(), vec!["foo", "FOO", "bar", "BAR"]);
+        handler.take().invoke("baz");
+        assert!(handler.is_none());
+    }
+
+    #[test]
+    fn test_try_handler() {
+        let mut handler = TryHandler::<i32, String>::None;
+        assert!(handler.is_none());
+        assert_eq!(handler.take().invoke(0), Ok(()));
+        assert_eq!(handler.invoke(0), Ok(()));
+
+        let mut handler = TryHandler::from(|value| {
+            if value == 42 {
+                Ok(())
+            } else {
+                Err(format!("unexpected value: {}", value))
+            }
+        });
+        assert!(!handler.is_none());
+        assert_eq!(handler.invoke(42), Ok(()));
+        assert_eq!(handler.invoke(0), Err("unexpected value: 0".to_string()));
+        assert_eq!(handler.take().invoke(42), Ok(()));
+        assert!(handler.is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{ComponentDrawer, ComponentUpdater, ContextStack};
+use crate::{context::SystemState, ComponentDrawer, ComponentUpdater, ContextStack};
 use std::{
     any::Any,
     pin::Pin,
@@ -31,13 +31,23 @@ pub trait Hook: Unpin {
 }
 
 pub(crate) trait AnyHook: Hook {
+    fn any_self(&self) -> &dyn Any;
     fn any_self_mut(&mut self) -> &mut dyn Any;
+    fn type_name(&self) -> &'static str;
 }
 
 impl<T: Hook + 'static> AnyHook for T {
+    fn any_self(&self) -> &dyn Any {
+        self
+    }
+
     fn any_self_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 impl Hook for Vec<Box<dyn AnyHook>> {
@@ -88,15 +98,21 @@ impl Hook for Vec<Box<dyn AnyHook>> {
 pub struct Hooks<'a, 'b: 'a> {
     hooks: &'a mut Vec<Box<dyn AnyHook>>,
     first_update: bool,
+    has_changes: bool,
     hook_index: usize,
     pub(crate) context_stack: Option<&'a ContextStack<'b>>,
 }
 
 impl<'a, 'b> Hooks<'a, 'b> {
-    pub(crate) fn new(hooks: &'a mut Vec<Box<dyn AnyHook>>, first_update: bool) -> Self {
+    pub(crate) fn new(
+        hooks: &'a mut Vec<Box<dyn AnyHook>>,
+        first_update: bool,
+        has_changes: bool,
+    ) -> Self {
         Self {
             hooks,
             first_update,
+            has_changes,
             hook_index: 0,
             context_stack: None,
         }
@@ -110,11 +126,30 @@ impl<'a, 'b> Hooks<'a, 'b> {
         Hooks {
             hooks: self.hooks,
             first_update: self.first_update,
+            has_changes: self.has_changes,
             hook_index: self.hook_index,
             context_stack: Some(context_stack),
         }
     }
 
+    /// Returns `true` if this is the component's first render, or if any of its hooks or
+    /// descendants have signaled a change since the previous render. This is used by memoized
+    /// components to determine whether they can skip re-rendering, since their children can only
+    /// be updated by re-rendering them.
+    #[doc(hidden)]
+    pub fn has_changes(&self) -> bool {
+        self.first_update || self.has_changes
+    }
+
+    /// Returns the state shared by the whole tree, which the built-in hooks are implemented with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hooks weren't given a context stack.
+    pub(crate) fn system(&self) -> &'a SystemState {
+        self.context_stack.expect("context not available").system()
+    }
+
     /// If this is the component's first render, this function adds a new hook to the component and
     /// returns it.
     ///
@@ -131,6 +166,120 @@ impl<'a, 'b> Hooks<'a, 'b> {
 
         let idx = self.hook_index;
         self.hook_index += 1;
-        self.hooks.get_mut(idx).and_then(|hook| hook.any_self_mut().downcast_mut::<H>()).expect("Unexpected hook type! Most likely you've violated the rules of hooks and called this hook in a different order than the previous render.")
+
+        // Like the check for fewer hooks below, the descriptive checks for the rules of hooks only
+        // run in debug builds. Release builds still can't hand out a hook which doesn't exist or
+        // has the wrong type, so they panic tersely instead.
+        #[cfg(debug_assertions)]
+        match self.hooks.get(idx) {
+            None => panic!(
+                "hook #{} (`{}`) was called, but only {} hooks were called on the first render. most likely you've violated the rules of hooks and called a hook conditionally.",
+                idx + 1,
+                std::any::type_name::<H>(),
+                self.hooks.len(),
+            ),
+            Some(hook) if !hook.any_self().is::<H>() => panic!(
+                "hook #{} was `{}` on the first render, but is now `{}`. most likely you've violated the rules of hooks and called hooks in a different order than on the first render.",
+                idx + 1,
+                hook.type_name(),
+                std::any::type_name::<H>(),
+            ),
+            Some(_) => {}
+        }
+
+        self.hooks
+            .get_mut(idx)
+            .and_then(|hook| hook.any_self_mut().downcast_mut::<H>())
+            .expect("hooks must be called in the same order on every render")
+    }
+}
+
+// In debug builds, a render which calls fewer hooks than the first one is caught once the
+// component is done with its hooks. A render which calls none at all is allowed, since memoized
+// components skip their hooks entirely when they re-use their previous output.
+#[cfg(debug_assertions)]
+impl Drop for Hooks<'_, '_> {
+    fn drop(&mut self) {
+        if !self.first_update
+            && self.hook_index > 0
+            && self.hook_index < self.hooks.len()
+            && !std::thread::panicking()
+        {
+            panic!(
+                "only {} of the {} hooks called on the first render were called. most likely you've violated the rules of hooks and called a hook conditionally or returned early.",
+                self.hook_index,
+                self.hooks.len(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Clone, Copy, Default, PartialEq)]
+    enum Violation {
+        #[default]
+        Reorder,
+        Add,
+        Remove,
+    }
+
+    #[derive(Default, Props)]
+    struct RuleBreakerProps {
+        violation: Violation,
+    }
+
+    #[component]
+    fn RuleBreaker(mut hooks: Hooks, props: &RuleBreakerProps) -> impl Into<AnyElement<'static>> {
+        let broken = hooks.use_state(|| false);
+        hooks.use_terminal_events(move |_| broken.set(true));
+        match (props.violation, broken.get()) {
+            (_, false) => {
+                hooks.use_state(|| 0);
+                hooks.use_ref(|| 0);
+            }
+            (Violation::Reorder, true) => {
+                hooks.use_ref(|| 0);
+                hooks.use_state(|| 0);
+            }
+            (Violation::Add, true) => {
+                hooks.use_state(|| 0);
+                hooks.use_ref(|| 0);
+                hooks.use_ref(|| 0);
+            }
+            (Violation::Remove, true) => {
+                hooks.use_state(|| 0);
+            }
+        }
+        element!(Text(content: "rules"))
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hook #3 was `iocraft::hooks::use_state::UseStateImpl<i32>`")]
+    fn test_hooks_reordered() {
+        let mut element = element!(RuleBreaker(violation: Violation::Reorder));
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Enter);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hook #5 (`iocraft::hooks::use_ref::UseRefImpl<i32>`) was called")]
+    fn test_hooks_added() {
+        let mut element = element!(RuleBreaker(violation: Violation::Add));
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Enter);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "only 3 of the 4 hooks called on the first render were called")]
+    fn test_hooks_removed() {
+        let mut element = element!(RuleBreaker(violation: Violation::Remove));
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send_key(KeyCode::Enter);
     }
 }
@@ -34,16 +34,51 @@
 //!
 //! They must be called in the same order every time, so calling them in any sort of conditional or
 //! loop is not allowed. If you break the rules of hooks, you can expect a panic.
+//!
+//! In debug builds, each render's hook calls are checked against those of the first render, and
+//! calling more hooks, fewer hooks, or a different kind of hook in some position panics with a
+//! message identifying the hook. Release builds skip these checks, but still panic if a hook is
+//! called which doesn't match the first render's, since there's no hook to return.
 
+mod use_animation_frame;
+pub use use_animation_frame::*;
+mod use_async_handler;
+pub use use_async_handler::*;
 mod use_context;
 pub use use_context::*;
+mod use_context_selector;
+pub use use_context_selector::*;
+mod use_element_size;
+pub use use_element_size::*;
+mod use_focus;
+pub use use_focus::*;
+mod use_frame_stats;
+pub use use_frame_stats::*;
 mod use_future;
 pub use use_future::*;
+mod use_interval;
+pub use use_interval::*;
+mod use_keymap;
+pub use use_keymap::*;
+mod use_layout_rect;
+pub use use_layout_rect::*;
+mod use_mount;
+pub use use_mount::*;
 mod use_output;
 pub use use_output::*;
+mod use_rate_limited_handler;
+pub use use_rate_limited_handler::*;
+mod use_render_handle;
+pub use use_render_handle::*;
+mod use_ref;
+pub use use_ref::*;
+mod use_resource;
+pub use use_resource::*;
 mod use_state;
 pub use use_state::*;
 mod use_terminal_events;
 pub use use_terminal_events::*;
 mod use_terminal_size;
 pub use use_terminal_size::*;
+mod use_theme;
+pub use use_theme::*;
@@ -0,0 +1,121 @@
+use crate::{
+    clock::{Clock, Interval},
+    Hook, Hooks,
+};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The interval at which components using
+/// [`use_animation_frame`](UseAnimationFrame::use_animation_frame) are re-rendered, which is about
+/// 60 frames per second.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// `UseAnimationFrame` is a hook that allows a component to animate smoothly, by re-rendering on
+/// every frame.
+///
+/// Rather than counting frames, which can be dropped when the terminal is slow, animations should
+/// be computed from the elapsed time that the hook returns. When rendered by a `TestRenderer`, the
+/// time follows the renderer's virtual clock instead of real time.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::time::Duration;
+/// #[component]
+/// fn FillingBar(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let elapsed = hooks.use_animation_frame();
+///     // Fills up over two seconds.
+///     let value = elapsed.as_secs_f32() / 2.0;
+///     element! {
+///         Box(width: 40) {
+///             ProgressBar(value, color: Color::Green)
+///         }
+///     }
+/// }
+/// ```
+pub trait UseAnimationFrame {
+    /// Returns the time that has passed since the component was mounted, and schedules the
+    /// component to be re-rendered on the next frame.
+    ///
+    /// Frames are requested for as long as the component keeps calling this hook. They stop when
+    /// the component is unmounted.
+    fn use_animation_frame(&mut self) -> Duration;
+}
+
+impl UseAnimationFrame for Hooks<'_, '_> {
+    fn use_animation_frame(&mut self) -> Duration {
+        let clock = self.system().clock.clone();
+        let hook = self.use_hook(move || UseAnimationFrameImpl {
+            start: clock.now(),
+            frames: clock.interval(FRAME_INTERVAL),
+            clock,
+        });
+        hook.elapsed()
+    }
+}
+
+struct UseAnimationFrameImpl {
+    clock: Clock,
+    start: Duration,
+    frames: Interval,
+}
+
+impl UseAnimationFrameImpl {
+    fn elapsed(&self) -> Duration {
+        self.clock.now() - self.start
+    }
+}
+
+impl Hook for UseAnimationFrameImpl {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Any frames that were missed are skipped.
+        if self.frames.poll_tick(cx) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::time::Duration;
+
+    #[derive(Default, Props)]
+    struct AnimatedProgressProps {
+        duration: Option<Duration>,
+    }
+
+    #[component]
+    fn AnimatedProgress(
+        mut hooks: Hooks,
+        props: &AnimatedProgressProps,
+    ) -> impl Into<AnyElement<'static>> {
+        let elapsed = hooks.use_animation_frame();
+        let value = elapsed.as_secs_f32() / props.duration.unwrap_or_default().as_secs_f32();
+        element! {
+            Box(width: 10) {
+                ProgressBar(value)
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_animation_frame() {
+        let mut element = element!(AnimatedProgress(duration: Duration::from_secs(1)));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "\n");
+
+        renderer.advance_time(Duration::from_millis(250));
+        assert_eq!(renderer.frame(), "██▌\n");
+        renderer.advance_time(Duration::from_millis(250));
+        assert_eq!(renderer.frame(), "█████\n");
+        renderer.advance_time(Duration::from_secs(10));
+        assert_eq!(renderer.frame(), "██████████\n");
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{Handler, Hook, Hooks};
+use futures::{
+    future::BoxFuture,
+    stream::{FuturesUnordered, Stream},
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// `UseAsyncHandler` is a hook that allows you to create a [`Handler`] which spawns an async task
+/// each time it's invoked.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # async fn save(_: &str) {}
+/// #[component]
+/// fn SaveButton(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let saves = hooks.use_state(|| 0);
+///     let save = hooks.use_async_handler(move |_| async move {
+///         save("document").await;
+///         saves.set(saves + 1);
+///     });
+///
+///     element! {
+///         Box(flex_direction: FlexDirection::Column) {
+///             Button(label: "Save", is_focused: true, on_press: save.clone())
+///             Text(content: if save.is_running() {
+///                 "saving...".to_string()
+///             } else {
+///                 format!("saved {} times", saves)
+///             })
+///         }
+///     }
+/// }
+/// ```
+pub trait UseAsyncHandler {
+    /// Creates a handler which, when invoked, calls `f` with the payload and runs the returned
+    /// future as a task bound to the lifetime of the component.
+    ///
+    /// Like futures spawned via [`UseFuture::use_future`](crate::hooks::UseFuture::use_future),
+    /// the tasks are polled by the render loop, and any which are still running when the
+    /// component is unmounted are dropped. Tasks started by multiple invocations run
+    /// concurrently. The component is re-rendered whenever a task starts or completes, so
+    /// [`AsyncHandler::is_running`] can be used to display progress.
+    ///
+    /// The function given on the most recent render is the one that gets called, so it can
+    /// capture the component's latest values.
+    fn use_async_handler<T, F, Fut>(&mut self, f: F) -> AsyncHandler<T>
+    where
+        T: 'static,
+        F: Fn(T) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static;
+}
+
+impl UseAsyncHandler for Hooks<'_, '_> {
+    fn use_async_handler<T, F, Fut>(&mut self, f: F) -> AsyncHandler<T>
+    where
+        T: 'static,
+        F: Fn(T) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let hook = self.use_hook(|| UseAsyncHandlerImpl {
+            handler: AsyncHandler {
+                inner: Default::default(),
+            },
+            tasks: FuturesUnordered::new(),
+        });
+        hook.handler.inner.lock().unwrap().f = Some(Box::new(move |value| Box::pin(f(value))));
+        hook.handler.clone()
+    }
+}
+
+type SpawnFn<T> = Box<dyn Fn(T) -> BoxFuture<'static, ()> + Send>;
+
+struct AsyncHandlerInner<T> {
+    f: Option<SpawnFn<T>>,
+    // Tasks which have been started but not yet picked up by the hook.
+    new_tasks: Vec<BoxFuture<'static, ()>>,
+    running: usize,
+    waker: Option<Waker>,
+    is_unmounted: bool,
+}
+
+impl<T> Default for AsyncHandlerInner<T> {
+    fn default() -> Self {
+        Self {
+            f: None,
+            new_tasks: Vec::new(),
+            running: 0,
+            waker: None,
+            is_unmounted: false,
+        }
+    }
+}
+
+/// A handle to the handler created by [`UseAsyncHandler::use_async_handler`].
+///
+/// It can be converted into a [`Handler`] to be passed as a prop, or invoked directly.
+pub struct AsyncHandler<T> {
+    inner: Arc<Mutex<AsyncHandlerInner<T>>>,
+}
+
+impl<T> Clone for AsyncHandler<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> AsyncHandler<T> {
+    /// Starts a new task with the given payload. If the component has been unmounted, this does
+    /// nothing.
+    pub fn invoke(&self, value: T) {
+        // The function is called without holding the lock so that it can use the handler itself.
+        let f = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.is_unmounted {
+                return;
+            }
+            match inner.f.take() {
+                Some(f) => f,
+                None => return,
+            }
+        };
+        let task = f(value);
+
+        let mut inner = self.inner.lock().unwrap();
+        // A render may have replaced the function in the meantime, in which case that one is kept.
+        if inner.f.is_none() {
+            inner.f = Some(f);
+        }
+        if inner.is_unmounted {
+            return;
+        }
+        inner.new_tasks.push(task);
+        inner.running += 1;
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Returns the number of tasks which have been started and haven't completed yet.
+    pub fn running_tasks(&self) -> usize {
+        self.inner.lock().unwrap().running
+    }
+
+    /// Returns `true` if any tasks are still running.
+    pub fn is_running(&self) -> bool {
+        self.running_tasks() > 0
+    }
+}
+
+impl<T: 'static> From<AsyncHandler<T>> for Handler<'static, T> {
+    fn from(handler: AsyncHandler<T>) -> Self {
+        Handler::from(move |value| handler.invoke(value))
+    }
+}
+
+struct UseAsyncHandlerImpl<T> {
+    handler: AsyncHandler<T>,
+    tasks: FuturesUnordered<BoxFuture<'static, ()>>,
+}
+
+impl<T> Unpin for UseAsyncHandlerImpl<T> {}
+
+impl<T> Hook for UseAsyncHandlerImpl<T> {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut changed = false;
+        {
+            let mut inner = self.handler.inner.lock().unwrap();
+            inner.waker = Some(cx.waker().clone());
+            if !inner.new_tasks.is_empty() {
+                changed = true;
+                let new_tasks = std::mem::take(&mut inner.new_tasks);
+                drop(inner);
+                self.tasks.extend(new_tasks);
+            }
+        }
+        while let Poll::Ready(Some(())) = Pin::new(&mut self.tasks).poll_next(cx) {
+            changed = true;
+            self.handler.inner.lock().unwrap().running -= 1;
+        }
+        if changed {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for UseAsyncHandlerImpl<T> {
+    fn drop(&mut self) {
+        let mut inner = self.handler.inner.lock().unwrap();
+        inner.is_unmounted = true;
+        inner.new_tasks.clear();
+        inner.running = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use futures::channel::oneshot;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[derive(Default, Props)]
+    struct SaverProps {
+        // Each save waits for the next of these to complete.
+        gates: Arc<Mutex<Vec<oneshot::Receiver<()>>>>,
+    }
+
+    #[component]
+    fn Saver(mut hooks: Hooks, props: &SaverProps) -> impl Into<AnyElement<'static>> {
+        let saved = hooks.use_state(|| 0);
+        let gates = props.gates.clone();
+        let save = hooks.use_async_handler(move |amount: i32| {
+            let gate = gates.lock().unwrap().remove(0);
+            async move {
+                let _ = gate.await;
+                saved.set(saved + amount);
+            }
+        });
+        hooks.use_terminal_events({
+            let save = save.clone();
+            move |event| {
+                if let TerminalEvent::Key(KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                }) = event
+                {
+                    save.invoke(c.to_digit(10).unwrap_or(0) as _);
+                }
+            }
+        });
+        element! {
+            Text(content: format!("saved: {}, saving: {}", saved, save.running_tasks()))
+        }
+    }
+
+    #[test]
+    fn test_use_async_handler() {
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..3).map(|_| oneshot::channel()).unzip();
+        let mut element = element!(Saver(gates: Arc::new(Mutex::new(receivers))));
+        let mut renderer = TestRenderer::new(&mut element);
+        let mut senders = senders.into_iter();
+        assert_eq!(renderer.frame(), "saved: 0, saving: 0\n");
+
+        renderer.send_key(KeyCode::Char('1'));
+        renderer.send_key(KeyCode::Char('2'));
+        assert_eq!(renderer.frame(), "saved: 0, saving: 2\n");
+
+        senders.next().unwrap().send(()).unwrap();
+        renderer.advance_time(Duration::ZERO);
+        assert_eq!(renderer.frame(), "saved: 1, saving: 1\n");
+
+        senders.next().unwrap().send(()).unwrap();
+        renderer.advance_time(Duration::ZERO);
+        assert_eq!(renderer.frame(), "saved: 3, saving: 0\n");
+    }
+
+    #[test]
+    fn test_async_handler_used_by_its_function() {
+        let handler = AsyncHandler::<i32> {
+            inner: Default::default(),
+        };
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        handler.inner.lock().unwrap().f = Some(std::boxed::Box::new({
+            let handler = handler.clone();
+            let observed = observed.clone();
+            move |value| {
+                observed
+                    .lock()
+                    .unwrap()
+                    .push((value, handler.running_tasks()));
+                std::boxed::Box::pin(async {})
+            }
+        }));
+        handler.invoke(1);
+        handler.invoke(2);
+        assert_eq!(*observed.lock().unwrap(), vec![(1, 0), (2, 1)]);
+        assert_eq!(handler.running_tasks(), 2);
+    }
+}
@@ -10,6 +10,11 @@ use std::{
 /// contexts are provided via the [`ContextProvider`](crate::components::ContextProvider)
 /// component.
 ///
+/// Contexts are looked up by type, starting from the nearest provider and walking up the tree, so
+/// a provider shadows any providers of the same type above it. If a context might not be
+/// provided, use [`try_use_context`](UseContext::try_use_context), which returns `None` rather
+/// than panicking.
+///
 /// # Example
 ///
 /// ```
@@ -30,10 +35,12 @@ pub trait UseContext<'a> {
     /// Panics if the context is not available or is not mutable.
     fn use_context_mut<T: Any>(&self) -> RefMut<'a, T>;
 
-    /// Returns a reference to the context of the given type, if it is available.
+    /// Returns a reference to the context of the given type, or `None` if no such context has
+    /// been provided.
     fn try_use_context<T: Any>(&self) -> Option<Ref<'a, T>>;
 
-    /// Returns a mutable reference to the context of the given type, if it is available and mutable.
+    /// Returns a mutable reference to the context of the given type, or `None` if no such context
+    /// has been provided or the nearest one was not provided mutably.
     fn try_use_context_mut<T: Any>(&self) -> Option<RefMut<'a, T>>;
 }
 
@@ -0,0 +1,158 @@
+use crate::{hooks::UseContext, ComponentUpdater, Hook, Hooks};
+use std::{any::Any, marker::PhantomData};
+
+/// `UseContextSelector` is a hook that allows a component to depend on only part of a context.
+///
+/// This is primarily useful for [memoized](macro@crate::component#memoization) components, which
+/// otherwise aren't re-rendered when a context they read changes. With a selector, such a
+/// component is re-rendered when the selected part of the context changes, and only then, which
+/// keeps apps with a large shared context from re-rendering every component that reads from it.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// struct Session {
+///     user_name: String,
+///     unread_messages: usize,
+/// }
+///
+/// #[component(memo)]
+/// fn UserName(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     // Not re-rendered when only the number of unread messages changes.
+///     let user_name = hooks.use_context_selector(|session: &Session| session.user_name.clone());
+///     element! {
+///         Text(content: user_name)
+///     }
+/// }
+/// ```
+pub trait UseContextSelector {
+    /// Returns the result of applying the selector to the context of the given type.
+    ///
+    /// The result is kept, and before each subsequent update of the component, the selector given
+    /// on the most recent render is applied to the context again. If the result differs, the
+    /// component is marked as changed, so it's re-rendered even if it's memoized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the context is not available.
+    fn use_context_selector<T, R, F>(&mut self, selector: F) -> R
+    where
+        T: Any,
+        R: Clone + PartialEq + Send + 'static,
+        F: Fn(&T) -> R + Send + 'static;
+}
+
+impl UseContextSelector for Hooks<'_, '_> {
+    fn use_context_selector<T, R, F>(&mut self, selector: F) -> R
+    where
+        T: Any,
+        R: Clone + PartialEq + Send + 'static,
+        F: Fn(&T) -> R + Send + 'static,
+    {
+        let value = selector(&*self.use_context::<T>());
+        let hook = self.use_hook(|| UseContextSelectorImpl::<T, R> {
+            selector: None,
+            value: None,
+            _marker: PhantomData,
+        });
+        hook.selector = Some(Box::new(selector));
+        hook.value = Some(value.clone());
+        value
+    }
+}
+
+type Selector<T, R> = Box<dyn Fn(&T) -> R + Send>;
+
+struct UseContextSelectorImpl<T, R> {
+    selector: Option<Selector<T, R>>,
+    value: Option<R>,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T, R> Unpin for UseContextSelectorImpl<T, R> {}
+
+impl<T: Any, R: PartialEq + Send + 'static> Hook for UseContextSelectorImpl<T, R> {
+    fn pre_component_update(&mut self, updater: &mut ComponentUpdater) {
+        let (Some(selector), Some(value)) = (&self.selector, &self.value) else {
+            return;
+        };
+        // If the context has gone away, the component is re-rendered so that it panics.
+        let is_changed = updater
+            .get_context::<T>()
+            .map_or(true, |context| selector(&context) != *value);
+        if is_changed {
+            updater.mark_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Session {
+        user_name: &'static str,
+        unread_messages: i32,
+    }
+
+    static USER_NAME_RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    #[component(memo)]
+    fn UserName(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        USER_NAME_RENDERS.fetch_add(1, Ordering::SeqCst);
+        let user_name = hooks.use_context_selector(|session: &Session| session.user_name);
+        element!(Text(content: user_name))
+    }
+
+    #[component]
+    fn UnreadMessages(hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let session = hooks.use_context::<Session>();
+        element!(Text(content: session.unread_messages.to_string()))
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let user_name = hooks.use_state(|| "alice");
+        let unread_messages = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent { code, .. }) = event {
+                match code {
+                    KeyCode::Char('m') => unread_messages.set(unread_messages + 1),
+                    KeyCode::Char('u') => user_name.set("bob"),
+                    _ => {}
+                }
+            }
+        });
+        element! {
+            ContextProvider(value: Context::owned(Session {
+                user_name: user_name.get(),
+                unread_messages: unread_messages.get(),
+            })) {
+                Box(flex_direction: FlexDirection::Column) {
+                    UserName
+                    UnreadMessages
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_context_selector() {
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "alice\n0\n");
+        assert_eq!(USER_NAME_RENDERS.load(Ordering::SeqCst), 1);
+
+        // Changes to other parts of the context don't re-render the component.
+        renderer.send_key(KeyCode::Char('m'));
+        renderer.send_key(KeyCode::Char('m'));
+        assert_eq!(renderer.frame(), "alice\n2\n");
+        assert_eq!(USER_NAME_RENDERS.load(Ordering::SeqCst), 1);
+
+        renderer.send_key(KeyCode::Char('u'));
+        assert_eq!(renderer.frame(), "bob\n2\n");
+        assert_eq!(USER_NAME_RENDERS.load(Ordering::SeqCst), 2);
+    }
+}
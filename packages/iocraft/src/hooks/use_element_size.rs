@@ -0,0 +1,120 @@
+use crate::{ComponentDrawer, Hook, Hooks};
+use std::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// `UseElementSize` is a hook that returns the size of the component as determined by the most
+/// recent layout.
+///
+/// This allows components to make decisions based on how much space they've been given, such as
+/// how many rows of a list fit.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[derive(Default, Props)]
+/// struct LogProps {
+///     lines: Vec<String>,
+/// }
+///
+/// #[component]
+/// fn Log(mut hooks: Hooks, props: &LogProps) -> impl Into<AnyElement<'static>> {
+///     let height = hooks.use_element_size().map(|(_, height)| height).unwrap_or(0);
+///     let visible = props.lines.iter().rev().take(height as _).rev();
+///
+///     element! {
+///         Box(flex_direction: FlexDirection::Column, height: 100pct) {
+///             #(visible.map(|line| element!(Text(content: line.clone()))))
+///         }
+///     }
+/// }
+/// ```
+pub trait UseElementSize {
+    /// Returns the width and height of the component as of the most recent layout, or `None` if
+    /// the component hasn't been laid out yet.
+    ///
+    /// The component is re-rendered whenever its size changes. Care should be taken that the
+    /// rendered output doesn't in turn change the component's size, as that could cause it to be
+    /// re-rendered endlessly.
+    fn use_element_size(&mut self) -> Option<(u16, u16)>;
+}
+
+impl UseElementSize for Hooks<'_, '_> {
+    fn use_element_size(&mut self) -> Option<(u16, u16)> {
+        let hook = self.use_hook(UseElementSizeImpl::default);
+        hook.reported_size = hook.size;
+        hook.reported_size
+    }
+}
+
+#[derive(Default)]
+struct UseElementSizeImpl {
+    size: Option<(u16, u16)>,
+    reported_size: Option<(u16, u16)>,
+    waker: Option<Waker>,
+}
+
+impl Hook for UseElementSizeImpl {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.size != self.reported_size {
+            Poll::Ready(())
+        } else {
+            self.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    fn pre_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        let size = drawer.size();
+        self.size = Some((size.width, size.height));
+        if self.size != self.reported_size {
+            if let Some(waker) = self.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[component]
+    fn SizeDisplay(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let size = hooks.use_element_size();
+        element! {
+            Text(content: match size {
+                Some((width, height)) => format!("{}x{}", width, height),
+                None => "?".to_string(),
+            })
+        }
+    }
+
+    #[component]
+    fn Resizer(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let width = hooks.use_state(|| 12);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(_) = event {
+                width.set(width + 4);
+            }
+        });
+        element! {
+            Box(flex_direction: FlexDirection::Column, width: width.get(), height: 3) {
+                SizeDisplay
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_element_size() {
+        let mut element = element!(Resizer);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "12x1\n\n\n");
+        assert_eq!(renderer.frames(), vec!["?\n\n\n", "12x1\n\n\n"]);
+
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "16x1\n\n\n");
+    }
+}
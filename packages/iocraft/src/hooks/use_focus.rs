@@ -0,0 +1,396 @@
+use crate::{
+    ComponentUpdater, Hook, Hooks, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, TerminalEvent,
+    TerminalEvents,
+};
+use futures::stream::Stream;
+use std::{
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// `UseFocus` is a hook that allows components to participate in focus management.
+///
+/// At most one component is focused at a time. The user can cycle focus through the focusable
+/// components with the Tab and Shift+Tab keys, in the order that the components were mounted, and
+/// components can request focus programmatically via [`FocusHandle::focus`]. If the focused
+/// component is unmounted or becomes unfocusable, focus moves to the next focusable component.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[derive(Default, Props)]
+/// struct FieldProps {
+///     label: String,
+///     disabled: bool,
+/// }
+///
+/// #[component]
+/// fn Field(mut hooks: Hooks, props: &FieldProps) -> impl Into<AnyElement<'static>> {
+///     let (is_focused, _) = hooks.use_focus_if(!props.disabled);
+///     element! {
+///         Text(
+///             content: &props.label,
+///             decoration: if is_focused { TextDecoration::Underline } else { TextDecoration::None },
+///         )
+///     }
+/// }
+/// ```
+pub trait UseFocus {
+    /// Registers the component as focusable, returning whether it is currently focused and a
+    /// handle which can be used to control its focus.
+    fn use_focus(&mut self) -> (bool, FocusHandle);
+
+    /// Like [`use_focus`](UseFocus::use_focus), but allows the component to opt out of focus. If
+    /// `focusable` is `false`, the component will be skipped when cycling focus and will lose
+    /// focus if it has it.
+    fn use_focus_if(&mut self, focusable: bool) -> (bool, FocusHandle);
+}
+
+impl UseFocus for Hooks<'_, '_> {
+    fn use_focus(&mut self) -> (bool, FocusHandle) {
+        self.use_focus_if(true)
+    }
+
+    fn use_focus_if(&mut self, focusable: bool) -> (bool, FocusHandle) {
+        let manager = self.system().focus_manager.clone();
+        let hook = self.use_hook(move || UseFocusImpl::new(manager, focusable));
+        let mut inner = hook.manager.inner.lock().unwrap();
+        inner.set_focusable(hook.id, focusable);
+        hook.is_focused = inner.focused == Some(hook.id);
+        drop(inner);
+        (
+            hook.is_focused,
+            FocusHandle {
+                manager: hook.manager.clone(),
+                id: hook.id,
+            },
+        )
+    }
+}
+
+/// A handle which can be used to control the focus of a component, obtained from
+/// [`UseFocus::use_focus`].
+#[derive(Clone)]
+pub struct FocusHandle {
+    manager: FocusManager,
+    id: u64,
+}
+
+impl FocusHandle {
+    /// Requests focus for the component. This has no effect if the component is not focusable or
+    /// has been unmounted.
+    pub fn focus(&self) {
+        self.manager.inner.lock().unwrap().focus(self.id);
+    }
+
+    /// Returns whether the component is currently focused.
+    pub fn is_focused(&self) -> bool {
+        self.manager.inner.lock().unwrap().focused == Some(self.id)
+    }
+}
+
+struct FocusEntry {
+    id: u64,
+    focusable: bool,
+    waker: Option<Waker>,
+}
+
+#[derive(Default)]
+struct FocusManagerInner {
+    next_id: u64,
+    entries: Vec<FocusEntry>,
+    focused: Option<u64>,
+    events: Option<TerminalEvents>,
+}
+
+impl FocusManagerInner {
+    fn register(&mut self, focusable: bool) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(FocusEntry {
+            id,
+            focusable,
+            waker: None,
+        });
+        id
+    }
+
+    fn unregister(&mut self, id: u64) {
+        if self.focused == Some(id) {
+            self.focus_next();
+        }
+        if self.focused == Some(id) {
+            self.set_focused(None);
+        }
+        self.entries.retain(|entry| entry.id != id);
+    }
+
+    fn set_focusable(&mut self, id: u64, focusable: bool) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.focusable = focusable;
+        }
+        if !focusable && self.focused == Some(id) {
+            self.focus_next();
+            if self.focused == Some(id) {
+                self.set_focused(None);
+            }
+        }
+    }
+
+    fn focus(&mut self, id: u64) {
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.id == id && entry.focusable)
+        {
+            self.set_focused(Some(id));
+        }
+    }
+
+    fn focus_next(&mut self) {
+        self.cycle_focus(false);
+    }
+
+    fn focus_prev(&mut self) {
+        self.cycle_focus(true);
+    }
+
+    fn cycle_focus(&mut self, reverse: bool) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let current = self
+            .focused
+            .and_then(|id| self.entries.iter().position(|entry| entry.id == id));
+        let next = (1..=len)
+            .map(|offset| match (current, reverse) {
+                (Some(i), false) => (i + offset) % len,
+                (Some(i), true) => (i + len - offset) % len,
+                (None, false) => offset - 1,
+                (None, true) => len - offset,
+            })
+            .find(|&i| self.entries[i].focusable)
+            .map(|i| self.entries[i].id);
+        if next.is_some() {
+            self.set_focused(next);
+        }
+    }
+
+    fn set_focused(&mut self, focused: Option<u64>) {
+        if focused == self.focused {
+            return;
+        }
+        let prev = self.focused;
+        self.focused = focused;
+        for entry in self.entries.iter_mut() {
+            if Some(entry.id) == prev || Some(entry.id) == focused {
+                if let Some(waker) = entry.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
+    fn poll_events(&mut self, cx: &mut Context<'_>) {
+        loop {
+            let Some(events) = self.events.as_mut() else {
+                return;
+            };
+            let Poll::Ready(Some(event)) = pin!(events).poll_next(cx) else {
+                return;
+            };
+            if let TerminalEvent::Key(KeyEvent {
+                code,
+                modifiers,
+                kind,
+            }) = event
+            {
+                if kind == KeyEventKind::Release {
+                    continue;
+                }
+                match code {
+                    KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => self.focus_prev(),
+                    KeyCode::Tab => self.focus_next(),
+                    KeyCode::BackTab => self.focus_prev(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Tracks the focusable components within a render tree. There's exactly one per tree, which is
+/// owned by its [`SystemState`](crate::context::SystemState).
+#[derive(Clone, Default)]
+pub(crate) struct FocusManager {
+    inner: Arc<Mutex<FocusManagerInner>>,
+}
+
+struct UseFocusImpl {
+    manager: FocusManager,
+    id: u64,
+    is_focused: bool,
+}
+
+impl UseFocusImpl {
+    fn new(manager: FocusManager, focusable: bool) -> Self {
+        let id = manager.inner.lock().unwrap().register(focusable);
+        Self {
+            manager,
+            id,
+            is_focused: false,
+        }
+    }
+}
+
+impl Hook for UseFocusImpl {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.manager.inner.lock().unwrap();
+        inner.poll_events(cx);
+        let is_focused = inner.focused == Some(self.id);
+        if let Some(entry) = inner.entries.iter_mut().find(|entry| entry.id == self.id) {
+            entry.waker = Some(cx.waker().clone());
+        }
+        drop(inner);
+        if is_focused != self.is_focused {
+            self.is_focused = is_focused;
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn post_component_update(&mut self, updater: &mut ComponentUpdater) {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.events.is_none() {
+            inner.events = updater.terminal_events();
+        }
+    }
+}
+
+impl Drop for UseFocusImpl {
+    fn drop(&mut self) {
+        self.manager.inner.lock().unwrap().unregister(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use macro_rules_attribute::apply;
+    use smol_macros::test;
+
+    #[test]
+    fn test_focus_manager() {
+        let mut manager = FocusManagerInner::default();
+        let a = manager.register(true);
+        let b = manager.register(true);
+        let c = manager.register(true);
+        assert_eq!(manager.focused, None);
+
+        manager.focus_next();
+        assert_eq!(manager.focused, Some(a));
+        manager.focus_next();
+        assert_eq!(manager.focused, Some(b));
+        manager.focus_prev();
+        manager.focus_prev();
+        assert_eq!(manager.focused, Some(c));
+
+        // Unfocusable components are skipped, and lose focus if they have it.
+        manager.set_focusable(a, false);
+        manager.focus_next();
+        assert_eq!(manager.focused, Some(b));
+        manager.set_focusable(b, false);
+        assert_eq!(manager.focused, Some(c));
+        manager.focus(a);
+        assert_eq!(manager.focused, Some(c));
+
+        // Removing the focused component moves focus to the next one.
+        manager.set_focusable(a, true);
+        manager.unregister(c);
+        assert_eq!(manager.focused, Some(a));
+        manager.unregister(a);
+        assert_eq!(manager.focused, None);
+
+        manager.focus_next();
+        assert_eq!(manager.focused, None);
+        manager.set_focusable(b, true);
+        manager.focus(b);
+        assert_eq!(manager.focused, Some(b));
+    }
+
+    #[derive(Default, Props)]
+    struct FocusableProps {
+        auto_focus: bool,
+    }
+
+    #[component]
+    fn Focusable(mut hooks: Hooks, props: &FocusableProps) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let (is_focused, handle) = hooks.use_focus();
+
+        if props.auto_focus {
+            hooks.use_future(async move {
+                handle.focus();
+            });
+        }
+
+        if is_focused {
+            system.exit();
+        }
+
+        element! {
+            Text(content: if is_focused { "*" } else { "-" })
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_use_focus() {
+        let canvases = mock_terminal_render_loop(element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Focusable
+                Focusable(auto_focus: true)
+            }
+        })
+        .await
+        .unwrap();
+        let actual = canvases.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let expected = vec!["-\n-\n", "-\n*\n"];
+        assert_eq!(actual, expected);
+    }
+
+    #[derive(Default, Props)]
+    struct LabeledProps {
+        label: String,
+    }
+
+    #[component]
+    fn Labeled(mut hooks: Hooks, props: &LabeledProps) -> impl Into<AnyElement<'static>> {
+        // Holding the system context must not cut the component off from the tree's focus.
+        let _system = hooks.use_context_mut::<SystemContext>();
+        let (is_focused, _) = hooks.use_focus();
+        element! {
+            Text(content: format!("{}{}", props.label, if is_focused { "*" } else { "" }))
+        }
+    }
+
+    #[test]
+    fn test_use_focus_with_system_context() {
+        let mut element = element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Labeled(label: "a")
+                Labeled(label: "b")
+            }
+        };
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "a\nb\n");
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(renderer.frame(), "a*\nb\n");
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(renderer.frame(), "a\nb*\n");
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{FrameStats, Hooks};
+
+/// `UseFrameStats` is a hook that allows a component to inspect how long rendering is taking,
+/// which is useful for profiling.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn FrameTime(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let stats = hooks.use_frame_stats();
+///     element! {
+///         Text(content: match stats {
+///             Some(stats) => format!(
+///                 "{:?} ({} nodes)",
+///                 stats.total_duration(),
+///                 stats.node_count,
+///             ),
+///             None => "-".to_string(),
+///         })
+///     }
+/// }
+/// ```
+pub trait UseFrameStats {
+    /// Returns the statistics for the most recently completed render, or `None` during the first
+    /// render.
+    ///
+    /// Rendering a frame doesn't cause the component to be re-rendered, so the stats are only
+    /// updated when the component is re-rendered for some other reason.
+    fn use_frame_stats(&mut self) -> Option<FrameStats>;
+}
+
+impl UseFrameStats for Hooks<'_, '_> {
+    fn use_frame_stats(&mut self) -> Option<FrameStats> {
+        self.system().frame_stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[component]
+    fn StatsDisplay(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let renders = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |_| renders.set(renders + 1));
+        let stats = hooks.use_frame_stats();
+        element! {
+            Text(content: match stats {
+                Some(stats) => format!(
+                    "nodes: {}, components: {}",
+                    stats.node_count, stats.updated_component_count,
+                ),
+                None => "no stats".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_use_frame_stats() {
+        let mut element = element!(StatsDisplay);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "no stats\n");
+
+        // The tree consists of the wrapper node, the root, and the text.
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "nodes: 3, components: 2\n");
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{Hook, Hooks};
 use futures::future::BoxFuture;
+use generational_box::{AnyStorage, GenerationalBox, Owner, SyncStorage};
 use std::{
     future::Future,
+    ops,
     pin::Pin,
     task::{Context, Poll},
 };
@@ -19,43 +21,237 @@ pub trait UseFuture {
     /// dropped, the future will also be dropped.
     ///
     /// The given future will only be spawned once. After that, calling this function has no
-    /// effect.
-    fn use_future<F>(&mut self, f: F)
+    /// effect other than returning the handle.
+    ///
+    /// The future is polled by the render loop rather than spawned as a detached task, so
+    /// dropping the component reliably cancels it. When the future completes, the component is
+    /// re-rendered, and its output can be accessed via the returned [`FutureHandle`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// # async fn fetch_greeting() -> String { "Hello!".to_string() }
+    /// #[component]
+    /// fn Greeting(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+    ///     let greeting = hooks.use_future(fetch_greeting());
+    ///     element! {
+    ///         Text(content: match greeting.state() {
+    ///             FutureState::Pending => "Loading...".to_string(),
+    ///             FutureState::Ready(greeting) => greeting,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    fn use_future<F>(&mut self, f: F) -> FutureHandle<F::Output>
     where
-        F: Future<Output = ()> + Send + 'static;
+        F: Future + Send + 'static,
+        F::Output: Send + Sync + 'static;
 }
 
 impl UseFuture for Hooks<'_, '_> {
-    fn use_future<F>(&mut self, f: F)
+    fn use_future<F>(&mut self, f: F) -> FutureHandle<F::Output>
     where
-        F: Future<Output = ()> + Send + 'static,
+        F: Future + Send + 'static,
+        F::Output: Send + Sync + 'static,
     {
-        self.use_hook(move || UseFutureImpl::new(f));
+        self.use_hook(move || UseFutureImpl::new(f)).handle
+    }
+}
+
+/// The state of a future spawned via [`UseFuture::use_future`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FutureState<T> {
+    /// The future has not completed yet.
+    Pending,
+    /// The future has completed with the given output.
+    Ready(T),
+}
+
+/// A reference to the output of a completed future.
+pub struct FutureOutputRef<T: 'static> {
+    inner: <SyncStorage as AnyStorage>::Ref<'static, T>,
+}
+
+impl<T: 'static> ops::Deref for FutureOutputRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// `FutureHandle` is a copyable handle to the output of a future spawned via
+/// [`UseFuture::use_future`].
+pub struct FutureHandle<T: Send + Sync + 'static> {
+    output: GenerationalBox<Option<T>, SyncStorage>,
+}
+
+impl<T: Send + Sync + 'static> Clone for FutureHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + 'static> Copy for FutureHandle<T> {}
+
+impl<T: Send + Sync + 'static> FutureHandle<T> {
+    /// Returns `true` if the future has completed.
+    pub fn is_ready(&self) -> bool {
+        self.output.read().is_some()
+    }
+
+    /// Returns a reference to the future's output, or `None` if it hasn't completed yet.
+    pub fn read(&self) -> Option<FutureOutputRef<T>> {
+        SyncStorage::try_map(self.output.read(), |output| output.as_ref())
+            .map(|inner| FutureOutputRef { inner })
     }
 }
 
-struct UseFutureImpl {
-    f: Option<BoxFuture<'static, ()>>,
+impl<T: Clone + Send + Sync + 'static> FutureHandle<T> {
+    /// Returns the state of the future, cloning its output if it has completed.
+    pub fn state(&self) -> FutureState<T> {
+        match self.output.read().as_ref() {
+            Some(output) => FutureState::Ready(output.clone()),
+            None => FutureState::Pending,
+        }
+    }
 }
 
-impl Hook for UseFutureImpl {
+struct UseFutureImpl<T: Send + Sync + 'static> {
+    _storage: Owner<SyncStorage>,
+    f: Option<BoxFuture<'static, T>>,
+    handle: FutureHandle<T>,
+}
+
+// The future is boxed and the output is stored behind the generational box, so nothing is
+// structurally pinned.
+impl<T: Send + Sync + 'static> Unpin for UseFutureImpl<T> {}
+
+impl<T: Send + Sync + 'static> Hook for UseFutureImpl<T> {
     fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
         if let Some(f) = self.f.as_mut() {
-            if let Poll::Ready(()) = f.as_mut().poll(cx) {
+            if let Poll::Ready(output) = f.as_mut().poll(cx) {
                 self.f = None;
+                *self.handle.output.write() = Some(output);
+                return Poll::Ready(());
             }
         }
         Poll::Pending
     }
 }
 
-impl UseFutureImpl {
+impl<T: Send + Sync + 'static> UseFutureImpl<T> {
     pub fn new<F>(f: F) -> Self
     where
-        F: Future<Output = ()> + Send + 'static,
+        F: Future<Output = T> + Send + 'static,
     {
+        let storage = Owner::default();
         Self {
+            handle: FutureHandle {
+                output: storage.insert(None),
+            },
+            _storage: storage,
             f: Some(Box::pin(f)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use futures::future;
+    use macro_rules_attribute::apply;
+    use smol_macros::test;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[component]
+    fn MyComponent(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let output = hooks.use_future(async { 42 });
+
+        match output.state() {
+            FutureState::Pending => element!(Text(content: "pending")),
+            FutureState::Ready(output) => {
+                system.exit();
+                element!(Text(content: format!("ready: {}", output)))
+            }
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_use_future() {
+        let canvases = mock_terminal_render_loop(element!(MyComponent))
+            .await
+            .unwrap();
+        let actual = canvases.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let expected = vec!["pending\n", "ready: 42\n"];
+        assert_eq!(actual, expected);
+    }
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct NeverCompletesProps {
+        dropped: Option<Arc<AtomicBool>>,
+    }
+
+    #[component]
+    fn NeverCompletes(
+        mut hooks: Hooks,
+        props: &NeverCompletesProps,
+    ) -> impl Into<AnyElement<'static>> {
+        let guard = SetOnDrop(props.dropped.clone().unwrap());
+        let output = hooks.use_future(async move {
+            let _guard = guard;
+            future::pending::<()>().await;
+        });
+        element!(Text(content: if output.is_ready() { "ready" } else { "pending" }))
+    }
+
+    #[derive(Default, Props)]
+    struct CancellationProps {
+        dropped: Option<Arc<AtomicBool>>,
+    }
+
+    #[component]
+    fn Cancellation(mut hooks: Hooks, props: &CancellationProps) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let show = hooks.use_state(|| true);
+        hooks.use_future(async move {
+            show.set(false);
+        });
+
+        if !show.get() {
+            system.exit();
+        }
+
+        element! {
+            Box {
+                #(if show.get() {
+                    Some(element!(NeverCompletes(dropped: props.dropped.clone())))
+                } else {
+                    None
+                })
+            }
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_use_future_cancellation() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        mock_terminal_render_loop(element!(Cancellation(dropped: dropped.clone())))
+            .await
+            .unwrap();
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{clock::Interval, Hook, Hooks};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// `UseInterval` is a hook that allows you to invoke a callback periodically.
+///
+/// The timer is driven by the render loop, so waiting for the next tick never blocks the handling
+/// of input or other updates. When rendered by a `TestRenderer`, the timer follows the renderer's
+/// virtual clock instead of real time.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::time::Duration;
+/// #[component]
+/// fn Stopwatch(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let seconds = hooks.use_state(|| 0);
+///     hooks.use_interval(Duration::from_secs(1), move || seconds.set(seconds.get() + 1));
+///     element! {
+///         Text(content: format!("{} seconds have passed", seconds))
+///     }
+/// }
+/// ```
+pub trait UseInterval {
+    /// Defines a callback to be invoked once every `period`, starting one `period` after the
+    /// component is mounted.
+    ///
+    /// The callback is only captured on the first call. If the period changes, the timer is
+    /// restarted with the new period. The timer is dropped when the component is unmounted.
+    ///
+    /// The callback is invoked at most once per poll of the render loop. If several ticks come due
+    /// in the meantime, e.g. because the loop was busy, they're coalesced into a single invocation.
+    /// Periods shorter than a millisecond, including zero, are treated as one millisecond.
+    fn use_interval<F>(&mut self, period: Duration, f: F)
+    where
+        F: FnMut() + Send + 'static;
+}
+
+impl UseInterval for Hooks<'_, '_> {
+    fn use_interval<F>(&mut self, period: Duration, f: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let clock = self.system().clock.clone();
+        let hook = self.use_hook(move || UseIntervalImpl {
+            period,
+            timer: clock.interval(period),
+            f: Box::new(f),
+        });
+        if hook.period != period {
+            hook.period = period;
+            hook.timer.restart(period);
+        }
+    }
+}
+
+struct UseIntervalImpl {
+    period: Duration,
+    timer: Interval,
+    f: Box<dyn FnMut() + Send + 'static>,
+}
+
+impl Hook for UseIntervalImpl {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.timer.poll_tick(cx) {
+            (self.f)();
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use macro_rules_attribute::apply;
+    use smol_macros::test;
+    use std::time::Duration;
+
+    #[component]
+    fn Ticker(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let ticks = hooks.use_state(|| 0);
+        hooks.use_interval(Duration::from_millis(1), move || ticks.set(ticks.get() + 1));
+
+        if ticks.get() >= 3 {
+            system.exit();
+        }
+
+        element! {
+            Text(content: ticks.to_string())
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_use_interval() {
+        let canvases = mock_terminal_render_loop(element!(Ticker)).await.unwrap();
+        let actual = canvases.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(actual.first().unwrap(), "0\n");
+        assert!(actual.last().unwrap().trim().parse::<i32>().unwrap() >= 3);
+    }
+
+    #[component]
+    fn VirtualTicker(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        // Holding the system context must not cut the component off from the tree's clock.
+        let _system = hooks.use_context_mut::<SystemContext>();
+        let ticks = hooks.use_state(|| 0);
+        hooks.use_interval(Duration::from_secs(1), move || ticks.set(ticks.get() + 1));
+        element! {
+            Text(content: ticks.to_string())
+        }
+    }
+
+    #[test]
+    fn test_use_interval_with_system_context() {
+        let mut element = element!(VirtualTicker);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "0\n");
+        renderer.advance_time(Duration::from_secs(1));
+        assert_eq!(renderer.frame(), "1\n");
+        renderer.advance_time(Duration::from_secs(1));
+        assert_eq!(renderer.frame(), "2\n");
+    }
+
+    #[test]
+    fn test_use_interval_coalesces_ticks() {
+        let mut element = element!(VirtualTicker);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.advance_time(Duration::from_millis(3500));
+        assert_eq!(renderer.frame(), "1\n");
+
+        // The interval keeps to its original schedule.
+        renderer.advance_time(Duration::from_millis(499));
+        assert_eq!(renderer.frame(), "1\n");
+        renderer.advance_time(Duration::from_millis(1));
+        assert_eq!(renderer.frame(), "2\n");
+    }
+
+    #[component]
+    fn ZeroTicker(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let ticks = hooks.use_state(|| 0);
+        hooks.use_interval(Duration::ZERO, move || ticks.set(ticks.get() + 1));
+        element! {
+            Text(content: ticks.to_string())
+        }
+    }
+
+    #[test]
+    fn test_use_interval_zero_period() {
+        let mut element = element!(ZeroTicker);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "0\n");
+        renderer.advance_time(Duration::from_micros(500));
+        assert_eq!(renderer.frame(), "0\n");
+        renderer.advance_time(Duration::from_micros(500));
+        assert_eq!(renderer.frame(), "1\n");
+    }
+}
@@ -0,0 +1,872 @@
+use crate::{
+    hooks::FocusHandle, ComponentUpdater, Hook, Hooks, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, TerminalEvent, TerminalEvents,
+};
+use futures::stream::Stream;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    pin::{pin, Pin},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// A key along with the modifiers which must be held for it, such as `Ctrl+C`.
+///
+/// Key combos can be parsed from strings of `+`-separated modifiers followed by a key. The
+/// modifiers are `Ctrl`, `Alt`, `Shift`, and `Super`. The key is either a single character, a
+/// function key such as `F5`, or one of `Enter`, `Esc`, `Tab`, `Backspace`, `Delete`, `Insert`,
+/// `Home`, `End`, `PageUp`, `PageDown`, `Up`, `Down`, `Left`, `Right`, or `Space`. Parsing is
+/// case-insensitive, except for character keys without modifiers, since `Q` and `q` are different
+/// keys.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// let combo: KeyCombo = "Ctrl+C".parse().unwrap();
+/// assert_eq!(combo, KeyCombo::new(KeyCode::Char('c')).ctrl());
+/// assert_eq!(combo.to_string(), "Ctrl+C");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+    /// Creates a combo for the given key with no modifiers.
+    pub fn new(code: KeyCode) -> Self {
+        Self::with_modifiers(code, KeyModifiers::empty())
+    }
+
+    /// Creates a combo for the given key and modifiers.
+    pub fn with_modifiers(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is folded into character keys and Shift+Tab is reported as a key of its own, so
+        // these are normalized to make equivalent combos compare equal.
+        let modifiers = modifiers
+            & (KeyModifiers::CONTROL
+                | KeyModifiers::ALT
+                | KeyModifiers::SHIFT
+                | KeyModifiers::SUPER);
+        match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::Char(c.to_uppercase().next().unwrap_or(c)),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::BackTab => Self {
+                code: KeyCode::Tab,
+                modifiers: modifiers | KeyModifiers::SHIFT,
+            },
+            code => Self { code, modifiers },
+        }
+    }
+
+    /// Adds the Ctrl modifier to the combo.
+    pub fn ctrl(self) -> Self {
+        Self::with_modifiers(self.code, self.modifiers | KeyModifiers::CONTROL)
+    }
+
+    /// Adds the Alt modifier to the combo.
+    pub fn alt(self) -> Self {
+        Self::with_modifiers(self.code, self.modifiers | KeyModifiers::ALT)
+    }
+
+    /// Adds the Shift modifier to the combo.
+    pub fn shift(self) -> Self {
+        Self::with_modifiers(self.code, self.modifiers | KeyModifiers::SHIFT)
+    }
+
+    /// Returns the key of the combo.
+    pub fn code(&self) -> KeyCode {
+        self.code
+    }
+
+    /// Returns the modifiers of the combo. For character keys, Shift is reflected in the character
+    /// instead.
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Returns `true` if the given key event is a press or repeat of this combo.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        event.kind != KeyEventKind::Release
+            && Self::with_modifiers(event.code, event.modifiers) == *self
+    }
+}
+
+impl From<KeyCode> for KeyCombo {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code)
+    }
+}
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Space", KeyCode::Char(' ')),
+];
+
+/// An error which can be returned when parsing a [`KeyCombo`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseKeyComboError {
+    /// The string didn't contain a key.
+    MissingKey,
+    /// The string contained a modifier which isn't recognized.
+    UnknownModifier(String),
+    /// The string contained a key which isn't recognized.
+    UnknownKey(String),
+}
+
+impl Display for ParseKeyComboError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingKey => write!(f, "key combos must end with a key"),
+            Self::UnknownModifier(name) => write!(f, "unknown modifier: {:?}", name),
+            Self::UnknownKey(name) => write!(f, "unknown key: {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for ParseKeyComboError {}
+
+impl FromStr for KeyCombo {
+    type Err = ParseKeyComboError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A trailing "+" is the plus key rather than a separator.
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (Some(modifiers), "+"),
+            None if s == "+" => (None, "+"),
+            None => match s.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, s),
+            },
+        };
+
+        let mut combo_modifiers = KeyModifiers::empty();
+        for name in modifiers.into_iter().flat_map(|m| m.split('+')) {
+            combo_modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" | "cmd" => KeyModifiers::SUPER,
+                _ => return Err(ParseKeyComboError::UnknownModifier(name.to_string())),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (None, _) => return Err(ParseKeyComboError::MissingKey),
+            // Letters are conventionally written in uppercase, as in "Ctrl+C", but only mean the
+            // uppercase letter when no other modifiers are given.
+            (Some(c), None) if combo_modifiers.is_empty() => KeyCode::Char(c),
+            (Some(c), None) => KeyCode::Char(c.to_lowercase().next().unwrap_or(c)),
+            _ => KEY_NAMES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, code)| *code)
+                .or_else(|| {
+                    let n = key.strip_prefix(['F', 'f'])?.parse().ok()?;
+                    (1..=24).contains(&n).then_some(KeyCode::F(n))
+                })
+                .ok_or_else(|| ParseKeyComboError::UnknownKey(key.to_string()))?,
+        };
+        Ok(Self::with_modifiers(code, combo_modifiers))
+    }
+}
+
+impl TryFrom<&str> for KeyCombo {
+    type Error = ParseKeyComboError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let has_modifiers = self
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+        let mut modifiers = self.modifiers;
+        let key = match self.code {
+            KeyCode::Char(c) if c.is_uppercase() && has_modifiers => {
+                modifiers |= KeyModifiers::SHIFT;
+                c.to_string()
+            }
+            KeyCode::Char(c) if has_modifiers => c.to_uppercase().to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            code => KEY_NAMES
+                .iter()
+                .find(|(_, c)| *c == code)
+                .map(|(name, _)| name.to_string())
+                .unwrap_or_else(|| match code {
+                    KeyCode::Char(c) => c.to_string(),
+                    code => format!("{:?}", code),
+                }),
+        };
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+            (KeyModifiers::SUPER, "Super"),
+        ] {
+            if modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", key)
+    }
+}
+
+type KeyHandler = Arc<Mutex<dyn FnMut() + Send>>;
+
+struct KeyBinding {
+    combo: KeyCombo,
+    description: Option<String>,
+    handler: KeyHandler,
+}
+
+/// A set of key bindings, which can be registered with [`UseKeymap::use_keymap`].
+///
+/// Bindings are global by default, but can be scoped to a focusable component via
+/// [`when_focused`](KeyBindings::when_focused), in which case they're only active while the
+/// component has focus.
+#[derive(Default)]
+pub struct KeyBindings {
+    focus: Option<FocusHandle>,
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyBindings {
+    /// Creates an empty set of global key bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scopes the bindings to the component with the given focus handle, so that they're only
+    /// active while it has focus.
+    pub fn when_focused(mut self, focus: &FocusHandle) -> Self {
+        self.focus = Some(focus.clone());
+        self
+    }
+
+    /// Binds a key combo, given as a [`KeyCombo`], [`KeyCode`], or string such as `"Ctrl+S"`, to
+    /// a handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combo is a string which can't be parsed.
+    pub fn bind<K, F>(self, combo: K, f: F) -> Self
+    where
+        K: TryInto<KeyCombo>,
+        K::Error: Display,
+        F: FnMut() + Send + 'static,
+    {
+        self.push(combo, None, f)
+    }
+
+    /// Like [`bind`](KeyBindings::bind), but also gives the binding a description, such as
+    /// `"Save"`. Described bindings are returned by
+    /// [`use_active_key_bindings`](UseKeymap::use_active_key_bindings), so that they can be listed
+    /// in a help screen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combo is a string which can't be parsed.
+    pub fn bind_with_description<K, D, F>(self, combo: K, description: D, f: F) -> Self
+    where
+        K: TryInto<KeyCombo>,
+        K::Error: Display,
+        D: Into<String>,
+        F: FnMut() + Send + 'static,
+    {
+        self.push(combo, Some(description.into()), f)
+    }
+
+    fn push<K, F>(mut self, combo: K, description: Option<String>, f: F) -> Self
+    where
+        K: TryInto<KeyCombo>,
+        K::Error: Display,
+        F: FnMut() + Send + 'static,
+    {
+        let combo = match combo.try_into() {
+            Ok(combo) => combo,
+            Err(err) => panic!("invalid key combo: {}", err),
+        };
+        self.bindings.push(KeyBinding {
+            combo,
+            description,
+            handler: Arc::new(Mutex::new(f)),
+        });
+        self
+    }
+
+    fn is_active(&self) -> bool {
+        self.focus.as_ref().map_or(true, |focus| focus.is_focused())
+    }
+}
+
+/// `UseKeymap` is a hook that allows a component to declare key bindings, rather than matching
+/// terminal events itself.
+///
+/// When a key is pressed, the bindings of all mounted components are searched for one that
+/// matches, and only its handler is invoked. Bindings scoped to the focused component take
+/// precedence over global ones. Within each of those groups, components which registered their
+/// bindings first take precedence, as do bindings which were bound first within a component.
+/// Keys which match no binding are ignored. [Terminal event](super::UseTerminalEvents) handlers
+/// still receive every key.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn Editor(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let (is_focused, focus) = hooks.use_focus();
+///     let saves = hooks.use_state(|| 0);
+///     hooks.use_keymap(
+///         KeyBindings::new()
+///             .when_focused(&focus)
+///             .bind("Ctrl+S", move || saves.set(saves.get() + 1))
+///             .bind(KeyCode::Esc, move || saves.set(0)),
+///     );
+///     element! {
+///         Text(
+///             content: format!("saved {} times", saves),
+///             weight: if is_focused { Weight::Bold } else { Weight::Normal },
+///         )
+///     }
+/// }
+/// ```
+pub trait UseKeymap {
+    /// Registers the given key bindings for the component, replacing those given on the previous
+    /// render. The bindings are removed when the component is unmounted.
+    fn use_keymap(&mut self, bindings: KeyBindings);
+
+    /// Returns the combos and descriptions of the described bindings which are currently active,
+    /// for rendering a help screen. The component is re-rendered whenever they change, such as
+    /// when focus moves.
+    ///
+    /// The bindings are listed in the order that they take precedence in. If several bindings
+    /// share a combo, only the one which would handle it is listed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// #[component]
+    /// fn Help(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+    ///     let bindings = hooks.use_active_key_bindings();
+    ///     element! {
+    ///         Box(flex_direction: FlexDirection::Column) {
+    ///             #for (combo, description) in bindings {
+    ///                 Text(content: format!("{:>10}  {}", combo, description))
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn use_active_key_bindings(&mut self) -> Vec<(KeyCombo, String)>;
+}
+
+impl UseKeymap for Hooks<'_, '_> {
+    fn use_keymap(&mut self, bindings: KeyBindings) {
+        let manager = self.system().keymap_manager.clone();
+        let hook = self.use_hook(move || UseKeymapImpl::new(manager));
+        let mut inner = hook.manager.inner.lock().unwrap();
+        if let Some(entry) = inner.entries.iter_mut().find(|entry| entry.0 == hook.id) {
+            entry.1 = bindings;
+            inner.wake_watchers();
+        }
+    }
+
+    fn use_active_key_bindings(&mut self) -> Vec<(KeyCombo, String)> {
+        let manager = self.system().keymap_manager.clone();
+        let hook = self.use_hook(move || {
+            let watcher_id = manager.inner.lock().unwrap().add_watcher();
+            UseActiveKeyBindingsImpl {
+                manager,
+                watcher_id,
+                bindings: Vec::new(),
+            }
+        });
+        hook.bindings = hook.manager.inner.lock().unwrap().active_bindings();
+        hook.bindings.clone()
+    }
+}
+
+#[derive(Default)]
+struct KeymapManagerInner {
+    next_id: u64,
+    entries: Vec<(u64, KeyBindings)>,
+    events: Option<TerminalEvents>,
+    // The wakers of the components which list the active bindings, by watcher id.
+    watchers: HashMap<u64, Option<Waker>>,
+}
+
+impl KeymapManagerInner {
+    fn add_watcher(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.watchers.insert(id, None);
+        id
+    }
+
+    fn wake_watchers(&mut self) {
+        for waker in self.watchers.values_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Returns the active bindings, in order of precedence.
+    fn bindings(&self) -> impl Iterator<Item = &KeyBinding> {
+        let scoped = self
+            .entries
+            .iter()
+            .filter(|(_, bindings)| bindings.focus.is_some());
+        let global = self
+            .entries
+            .iter()
+            .filter(|(_, bindings)| bindings.focus.is_none());
+        scoped
+            .chain(global)
+            .filter(|(_, bindings)| bindings.is_active())
+            .flat_map(|(_, bindings)| bindings.bindings.iter())
+    }
+
+    /// Returns the handler of the binding which should handle the given key event, if any.
+    fn resolve(&self, event: &KeyEvent) -> Option<KeyHandler> {
+        self.bindings()
+            .find(|binding| binding.combo.matches(event))
+            .map(|binding| binding.handler.clone())
+    }
+
+    /// Returns the combos and descriptions of the bindings which would handle their combos.
+    fn active_bindings(&self) -> Vec<(KeyCombo, String)> {
+        let mut seen = HashSet::new();
+        self.bindings()
+            .filter(|binding| seen.insert(binding.combo))
+            .filter_map(|binding| Some((binding.combo, binding.description.clone()?)))
+            .collect()
+    }
+}
+
+/// Tracks the key bindings within a render tree. There's exactly one per tree, which is owned by
+/// its [`SystemState`](crate::context::SystemState).
+#[derive(Clone, Default)]
+pub(crate) struct KeymapManager {
+    inner: Arc<Mutex<KeymapManagerInner>>,
+}
+
+impl KeymapManager {
+    fn poll_events(&self, cx: &mut Context<'_>) {
+        loop {
+            let mut inner = self.inner.lock().unwrap();
+            let Some(events) = inner.events.as_mut() else {
+                return;
+            };
+            let Poll::Ready(Some(event)) = pin!(events).poll_next(cx) else {
+                return;
+            };
+            let TerminalEvent::Key(event) = event else {
+                continue;
+            };
+            let handler = inner.resolve(&event);
+            // The handler is invoked without the lock held, so that it's free to use the keymap.
+            drop(inner);
+            if let Some(handler) = handler {
+                (handler.lock().unwrap())();
+            }
+        }
+    }
+}
+
+struct UseKeymapImpl {
+    manager: KeymapManager,
+    id: u64,
+}
+
+impl UseKeymapImpl {
+    fn new(manager: KeymapManager) -> Self {
+        let mut inner = manager.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.entries.push((id, KeyBindings::default()));
+        inner.wake_watchers();
+        drop(inner);
+        Self { manager, id }
+    }
+}
+
+impl Hook for UseKeymapImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.manager.poll_events(cx);
+        Poll::Pending
+    }
+
+    fn post_component_update(&mut self, updater: &mut ComponentUpdater) {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.events.is_none() {
+            inner.events = updater.terminal_events();
+        }
+    }
+}
+
+struct UseActiveKeyBindingsImpl {
+    manager: KeymapManager,
+    watcher_id: u64,
+    bindings: Vec<(KeyCombo, String)>,
+}
+
+impl Hook for UseActiveKeyBindingsImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.active_bindings() != self.bindings {
+            Poll::Ready(())
+        } else {
+            inner
+                .watchers
+                .insert(self.watcher_id, Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for UseActiveKeyBindingsImpl {
+    fn drop(&mut self) {
+        self.manager
+            .inner
+            .lock()
+            .unwrap()
+            .watchers
+            .remove(&self.watcher_id);
+    }
+}
+
+impl Drop for UseKeymapImpl {
+    fn drop(&mut self) {
+        let mut inner = self.manager.inner.lock().unwrap();
+        inner.entries.retain(|entry| entry.0 != self.id);
+        inner.wake_watchers();
+        // Without any bindings, there's no need to keep listening. The next keymap to be
+        // registered subscribes again.
+        if inner.entries.is_empty() {
+            inner.events = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use futures::task::ArcWake;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_key_combo() {
+        let combo = |s: &str| s.parse::<KeyCombo>();
+        assert_eq!(
+            combo("Ctrl+C"),
+            Ok(KeyCombo::new(KeyCode::Char('c')).ctrl())
+        );
+        assert_eq!(combo("ctrl+c"), combo("Ctrl+C"));
+        assert_eq!(combo("Shift+a"), Ok(KeyCombo::new(KeyCode::Char('A'))));
+        assert_eq!(
+            combo("Ctrl+Shift+C"),
+            Ok(KeyCombo::new(KeyCode::Char('C')).ctrl())
+        );
+        assert_eq!(combo("Shift+Tab"), Ok(KeyCombo::new(KeyCode::BackTab)));
+        assert_eq!(combo("Alt+F5"), Ok(KeyCombo::new(KeyCode::F(5)).alt()));
+        assert_eq!(
+            combo("Ctrl++"),
+            Ok(KeyCombo::new(KeyCode::Char('+')).ctrl())
+        );
+        assert_eq!(combo("space"), Ok(KeyCombo::new(KeyCode::Char(' '))));
+        assert_eq!(combo("q"), Ok(KeyCombo::new(KeyCode::Char('q'))));
+        assert_eq!(combo(""), Err(ParseKeyComboError::MissingKey));
+        assert_eq!(
+            combo("Hyper+x"),
+            Err(ParseKeyComboError::UnknownModifier("Hyper".to_string()))
+        );
+        assert_eq!(
+            combo("Ctrl+Foo"),
+            Err(ParseKeyComboError::UnknownKey("Foo".to_string()))
+        );
+
+        for s in [
+            "Ctrl+C",
+            "Ctrl+Shift+C",
+            "Alt+Enter",
+            "Shift+Tab",
+            "F12",
+            "Q",
+            "Space",
+        ] {
+            assert_eq!(combo(s).unwrap().to_string(), s);
+        }
+
+        let ctrl_c = combo("Ctrl+C").unwrap();
+        let event = |code, modifiers, kind| KeyEvent {
+            code,
+            modifiers,
+            kind,
+        };
+        assert!(ctrl_c.matches(&event(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+            KeyEventKind::Press
+        )));
+        assert!(!ctrl_c.matches(&event(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+            KeyEventKind::Release
+        )));
+        assert!(!ctrl_c.matches(&event(
+            KeyCode::Char('c'),
+            KeyModifiers::empty(),
+            KeyEventKind::Press
+        )));
+        assert!(combo("Shift+Tab").unwrap().matches(&event(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+            KeyEventKind::Press
+        )));
+    }
+
+    #[derive(Default, Props)]
+    struct FieldProps {
+        name: &'static str,
+        log: Option<State<Vec<String>>>,
+    }
+
+    #[component]
+    fn Field(mut hooks: Hooks, props: &FieldProps) -> impl Into<AnyElement<'static>> {
+        let (is_focused, focus) = hooks.use_focus();
+        let (name, log) = (props.name, props.log.unwrap());
+        hooks.use_keymap(
+            KeyBindings::new()
+                .when_focused(&focus)
+                .bind("Ctrl+S", move || log.write_log(format!("saved {}", name))),
+        );
+        element!(Text(content: format!("{}{}", name, if is_focused { "*" } else { "" })))
+    }
+
+    trait WriteLog {
+        fn write_log(&self, entry: String);
+    }
+
+    impl WriteLog for State<Vec<String>> {
+        fn write_log(&self, entry: String) {
+            let mut entries = self.read().clone();
+            entries.push(entry);
+            self.set(entries);
+        }
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let log = hooks.use_state(Vec::new);
+        hooks.use_keymap(
+            KeyBindings::new()
+                .bind("Ctrl+S", move || log.write_log("saved all".to_string()))
+                .bind(KeyCode::Esc, move || log.write_log("cancelled".to_string())),
+        );
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Field(name: "a", log: Some(log))
+                Field(name: "b", log: Some(log))
+                Text(content: log.read().join(", "))
+            }
+        }
+    }
+
+    fn ctrl_s() -> TerminalEvent {
+        TerminalEvent::Key(KeyEvent {
+            code: KeyCode::Char('s'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+        })
+    }
+
+    #[test]
+    fn test_use_keymap() {
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "a\nb\n\n");
+
+        // With nothing focused, the global binding handles the key.
+        renderer.send(ctrl_s());
+        assert_eq!(renderer.frame(), "a\nb\nsaved all\n");
+
+        // The focused component's binding takes precedence.
+        renderer.send_key(KeyCode::Tab);
+        renderer.send_key(KeyCode::Tab);
+        renderer.send(ctrl_s());
+        assert_eq!(renderer.frame(), "a\nb*\nsaved all, saved b\n");
+
+        // Keys which aren't bound by the focused component fall through to global bindings.
+        renderer.send_key(KeyCode::Esc);
+        renderer.send_key(KeyCode::Char('s'));
+        assert_eq!(renderer.frame(), "a\nb*\nsaved all, saved b, cancelled\n");
+    }
+
+    #[derive(Default, Props)]
+    struct DocumentProps {
+        name: &'static str,
+    }
+
+    #[component]
+    fn Document(mut hooks: Hooks, props: &DocumentProps) -> impl Into<AnyElement<'static>> {
+        let (_, focus) = hooks.use_focus();
+        hooks.use_keymap(
+            KeyBindings::new()
+                .when_focused(&focus)
+                .bind_with_description("Ctrl+S", format!("Save {}", props.name), || {})
+                .bind("Ctrl+W", || {}),
+        );
+        element!(Text(content: props.name))
+    }
+
+    #[component]
+    fn Help(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let bindings = hooks.use_active_key_bindings();
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                #for (combo, description) in bindings {
+                    Text(content: format!("{}: {}", combo, description))
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn Editor(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        hooks.use_keymap(
+            KeyBindings::new()
+                .bind_with_description("Ctrl+Q", "Quit", || {})
+                .bind_with_description("Ctrl+S", "Save all", || {})
+                .bind_with_description("Ctrl+W", "Close all", || {}),
+        );
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Document(name: "a")
+                Document(name: "b")
+                Help
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_active_key_bindings() {
+        let mut element = element!(Editor);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(
+            renderer.frame(),
+            "a\nb\nCtrl+Q: Quit\nCtrl+S: Save all\nCtrl+W: Close all\n"
+        );
+
+        // The focused component's bindings come first and shadow the global ones, even if they
+        // have no description.
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(renderer.frame(), "a\nb\nCtrl+S: Save a\nCtrl+Q: Quit\n");
+        renderer.send_key(KeyCode::Tab);
+        assert_eq!(renderer.frame(), "a\nb\nCtrl+S: Save b\nCtrl+Q: Quit\n");
+    }
+
+    #[component]
+    fn Quitter(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        hooks.use_keymap(KeyBindings::new().bind("q", || {}));
+        element!(Text(content: "q"))
+    }
+
+    #[derive(Default, Props)]
+    struct QuitterToggleProps {
+        manager: Arc<Mutex<Option<KeymapManager>>>,
+    }
+
+    #[component]
+    fn QuitterToggle(
+        mut hooks: Hooks,
+        props: &QuitterToggleProps,
+    ) -> impl Into<AnyElement<'static>> {
+        *props.manager.lock().unwrap() = Some(hooks.system().keymap_manager.clone());
+        let shown = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) = event
+            {
+                shown.set(false);
+            }
+        });
+        element! {
+            Box {
+                #(shown.get().then(|| element!(Quitter)))
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_keymap_unsubscribes() {
+        let manager = Arc::new(Mutex::new(None));
+        let mut element = element!(QuitterToggle(manager: manager.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "q\n");
+        let manager = manager.lock().unwrap().clone().unwrap();
+        assert!(manager.inner.lock().unwrap().events.is_some());
+
+        renderer.send_key(KeyCode::Esc);
+        assert_eq!(renderer.frame(), "");
+        assert!(manager.inner.lock().unwrap().events.is_none());
+    }
+
+    struct WakeFlag(AtomicBool);
+
+    impl ArcWake for WakeFlag {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_active_key_bindings_waker() {
+        let manager = KeymapManager::default();
+        let watcher_id = manager.inner.lock().unwrap().add_watcher();
+        let mut hook = UseActiveKeyBindingsImpl {
+            manager: manager.clone(),
+            watcher_id,
+            bindings: Vec::new(),
+        };
+        let flag = Arc::new(WakeFlag(AtomicBool::new(false)));
+        let waker = futures::task::waker(flag.clone());
+        let mut cx = std::task::Context::from_waker(&waker);
+
+        // Registering bindings wakes the hook.
+        assert!(Pin::new(&mut hook).poll_change(&mut cx).is_pending());
+        let keymap = UseKeymapImpl::new(manager.clone());
+        assert!(flag.0.swap(false, Ordering::SeqCst));
+
+        // So does removing them.
+        assert!(Pin::new(&mut hook).poll_change(&mut cx).is_pending());
+        drop(keymap);
+        assert!(flag.0.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{ComponentDrawer, Hook, Hooks};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// The position and size of an element as of the most recent layout, as returned by
+/// [`use_layout_rect`](UseLayoutRect::use_layout_rect).
+///
+/// The position is relative to the top left corner of the rendered output. For fullscreen
+/// applications, that's the top left corner of the terminal, so it can be compared directly with
+/// the coordinates of mouse events.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LayoutRect {
+    /// The column of the left edge of the element.
+    pub x: u16,
+    /// The row of the top edge of the element.
+    pub y: u16,
+    /// The width of the element.
+    pub width: u16,
+    /// The height of the element.
+    pub height: u16,
+}
+
+impl LayoutRect {
+    /// Returns whether the given cell is within the rect.
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        column >= self.x
+            && row >= self.y
+            && column - self.x < self.width
+            && row - self.y < self.height
+    }
+}
+
+/// `UseLayoutRect` is a hook that returns the position and size of another element, identified
+/// by its `id` prop, such as [`Box`](crate::components::Box)'s.
+///
+/// This is the foundation for interactions which depend on where things ended up on screen, such
+/// as showing tooltips for the element under the mouse or detecting drag targets. Unlike
+/// [`use_element_size`](crate::hooks::UseElementSize::use_element_size), the element can be
+/// anywhere in the tree.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn Toolbar(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let save_rect = hooks.use_layout_rect("save");
+///     let hovered = hooks.use_state(|| false);
+///     hooks.use_terminal_events(move |event| {
+///         if let TerminalEvent::Mouse(MouseEvent { column, row, .. }) = event {
+///             hovered.set(save_rect.is_some_and(|rect| rect.contains(column, row)));
+///         }
+///     });
+///
+///     element! {
+///         Box(flex_direction: FlexDirection::Column) {
+///             Box(id: "save".to_string(), border_style: BorderStyle::Round) {
+///                 Text(content: "Save")
+///             }
+///             #(hovered.get().then(|| element!(Text(content: "Saves the file"))))
+///         }
+///     }
+/// }
+/// ```
+pub trait UseLayoutRect {
+    /// Returns the position and size of the element with the given id as of the most recent
+    /// layout, or `None` if there's no such element or it hasn't been laid out yet.
+    ///
+    /// The component is re-rendered whenever the rect changes. If multiple elements are given the
+    /// same id, the one drawn last wins, so ids should be unique within the whole tree.
+    fn use_layout_rect(&mut self, id: &str) -> Option<LayoutRect>;
+}
+
+impl UseLayoutRect for Hooks<'_, '_> {
+    fn use_layout_rect(&mut self, id: &str) -> Option<LayoutRect> {
+        let manager = self.system().layout_rect_manager.clone();
+        let hook = self.use_hook(move || {
+            let watcher_id = manager.inner.lock().unwrap().add_watcher();
+            UseLayoutRectImpl {
+                manager,
+                watcher_id,
+                id: String::new(),
+                rect: None,
+            }
+        });
+        if hook.id != id {
+            hook.id = id.to_string();
+        }
+        hook.rect = hook.manager.get(&hook.id);
+        hook.rect
+    }
+}
+
+#[derive(Default)]
+struct LayoutRectManagerInner {
+    next_id: u64,
+    // The rect of each id, along with the id of the element which set it.
+    rects: HashMap<String, (u64, LayoutRect)>,
+    watchers: HashMap<u64, Option<Waker>>,
+}
+
+impl LayoutRectManagerInner {
+    fn allocate_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn add_watcher(&mut self) -> u64 {
+        let id = self.allocate_id();
+        self.watchers.insert(id, None);
+        id
+    }
+
+    fn wake_watchers(&mut self) {
+        for waker in self.watchers.values_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Keeps track of the rects of the elements which have been given ids.
+#[derive(Clone, Default)]
+pub(crate) struct LayoutRectManager {
+    inner: Arc<Mutex<LayoutRectManagerInner>>,
+}
+
+impl LayoutRectManager {
+    fn get(&self, id: &str) -> Option<LayoutRect> {
+        let inner = self.inner.lock().unwrap();
+        inner.rects.get(id).map(|(_, rect)| *rect)
+    }
+}
+
+struct UseLayoutRectImpl {
+    manager: LayoutRectManager,
+    watcher_id: u64,
+    id: String,
+    rect: Option<LayoutRect>,
+}
+
+impl Hook for UseLayoutRectImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.rects.get(&self.id).map(|(_, rect)| *rect) != self.rect {
+            Poll::Ready(())
+        } else {
+            inner
+                .watchers
+                .insert(self.watcher_id, Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for UseLayoutRectImpl {
+    fn drop(&mut self) {
+        self.manager
+            .inner
+            .lock()
+            .unwrap()
+            .watchers
+            .remove(&self.watcher_id);
+    }
+}
+
+/// Records the rect of a component under an id each time it's drawn, until it's dropped. This is
+/// used to implement the `id` prop of built-in components, which only create one when the prop is
+/// set.
+pub(crate) struct LayoutId {
+    manager: LayoutRectManager,
+    owner: u64,
+    id: String,
+}
+
+impl LayoutId {
+    pub fn new(manager: &LayoutRectManager, id: &str) -> Self {
+        let owner = manager.inner.lock().unwrap().allocate_id();
+        Self {
+            manager: manager.clone(),
+            owner,
+            id: id.to_string(),
+        }
+    }
+
+    /// Changes the id that the rect is recorded under.
+    pub fn set_id(&mut self, id: &str) {
+        if self.id != id {
+            self.remove();
+            self.id = id.to_string();
+        }
+    }
+
+    /// Records the rect of the component being drawn.
+    pub fn record(&self, drawer: &ComponentDrawer) {
+        let position = drawer.canvas_position();
+        let size = drawer.size();
+        let rect = LayoutRect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        };
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner.rects.get(&self.id) != Some(&(self.owner, rect)) {
+            inner.rects.insert(self.id.clone(), (self.owner, rect));
+            inner.wake_watchers();
+        }
+    }
+
+    fn remove(&self) {
+        let mut inner = self.manager.inner.lock().unwrap();
+        if inner
+            .rects
+            .get(&self.id)
+            .is_some_and(|(owner, _)| *owner == self.owner)
+        {
+            inner.rects.remove(&self.id);
+            inner.wake_watchers();
+        }
+    }
+}
+
+impl Drop for LayoutId {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[component]
+    fn Tooltip(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let rect = hooks.use_layout_rect("target");
+        element!(Text(content: format!("{:?}", rect.map(|r| (r.x, r.y, r.width, r.height)))))
+    }
+
+    #[component]
+    fn Layout(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let shown = hooks.use_state(|| true);
+        let indent = hooks.use_state(|| 2);
+        hooks.use_terminal_events(move |event| match event {
+            TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('h'),
+                ..
+            }) => shown.set(false),
+            TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Right,
+                ..
+            }) => indent.set(indent + 1),
+            _ => {}
+        });
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Tooltip
+                Box(padding_left: indent.get()) {
+                    #(shown.get().then(|| element! {
+                        Box(id: "target".to_string(), width: 3, height: 2)
+                    }))
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout_rect() {
+        let rect = LayoutRect {
+            x: 2,
+            y: 1,
+            width: 3,
+            height: 2,
+        };
+        assert!(rect.contains(2, 1));
+        assert!(rect.contains(4, 2));
+        assert!(!rect.contains(5, 2));
+        assert!(!rect.contains(4, 3));
+        assert!(!rect.contains(1, 1));
+        assert!(!LayoutRect::default().contains(0, 0));
+    }
+
+    #[test]
+    fn test_use_layout_rect() {
+        let mut element = element!(Layout);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "Some((2, 1, 3, 2))\n\n\n");
+
+        renderer.send_key(KeyCode::Right);
+        assert_eq!(renderer.frame(), "Some((3, 1, 3, 2))\n\n\n");
+
+        // Once the element is unmounted, its rect is gone.
+        renderer.send_key(KeyCode::Char('h'));
+        assert_eq!(renderer.frame(), "None\n");
+    }
+}
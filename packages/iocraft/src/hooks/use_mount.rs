@@ -0,0 +1,164 @@
+use crate::{ComponentUpdater, Hook, Hooks};
+
+/// `UseMount` is a hook that allows you to run code when a component is mounted or unmounted.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # fn log(_: &str) {}
+/// #[component]
+/// fn Connection(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     hooks.use_mount(|| log("connecting"));
+///     hooks.use_unmount(|| log("disconnecting"));
+///     element! {
+///         Text(content: "connected")
+///     }
+/// }
+/// ```
+pub trait UseMount {
+    /// Defines a callback to be invoked once, when the component is first mounted.
+    ///
+    /// The callback is invoked after the component's first update, once its children have been
+    /// created, but before it's drawn and before any futures it spawns are first polled. On
+    /// subsequent renders, calling this function has no effect.
+    fn use_mount<F>(&mut self, f: F)
+    where
+        F: FnOnce() + Send + 'static;
+
+    /// Defines a callback to be invoked once, when the component is unmounted.
+    ///
+    /// The callback given on the most recent render is the one that gets invoked, so it can
+    /// capture the component's latest values.
+    fn use_unmount<F>(&mut self, f: F)
+    where
+        F: FnOnce() + Send + 'static;
+}
+
+impl UseMount for Hooks<'_, '_> {
+    fn use_mount<F>(&mut self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.use_hook(move || UseMountImpl {
+            f: Some(Box::new(f)),
+        });
+    }
+
+    fn use_unmount<F>(&mut self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let hook = self.use_hook(|| UseUnmountImpl { f: None });
+        hook.f = Some(Box::new(f));
+    }
+}
+
+struct UseMountImpl {
+    f: Option<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+impl Hook for UseMountImpl {
+    fn post_component_update(&mut self, _updater: &mut ComponentUpdater) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
+
+struct UseUnmountImpl {
+    f: Option<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+impl Hook for UseUnmountImpl {}
+
+impl Drop for UseUnmountImpl {
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Default, Props)]
+    struct LifecycleProps {
+        mounts: Arc<AtomicUsize>,
+        unmounts: Arc<AtomicUsize>,
+    }
+
+    #[component]
+    fn Lifecycle(mut hooks: Hooks, props: &LifecycleProps) -> impl Into<AnyElement<'static>> {
+        let renders = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |_| renders.set(renders + 1));
+        hooks.use_mount({
+            let mounts = props.mounts.clone();
+            move || {
+                mounts.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        hooks.use_unmount({
+            let unmounts = props.unmounts.clone();
+            move || {
+                unmounts.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        element! {
+            Text(content: format!("renders: {}", renders))
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct ParentProps {
+        mounts: Arc<AtomicUsize>,
+        unmounts: Arc<AtomicUsize>,
+    }
+
+    #[component]
+    fn Parent(mut hooks: Hooks, props: &ParentProps) -> impl Into<AnyElement<'static>> {
+        let show = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) = event
+            {
+                show.set(false);
+            }
+        });
+        element! {
+            Box {
+                #(show.get().then(|| element! {
+                    Lifecycle(mounts: props.mounts.clone(), unmounts: props.unmounts.clone())
+                }))
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_mount() {
+        let mounts = Arc::new(AtomicUsize::new(0));
+        let unmounts = Arc::new(AtomicUsize::new(0));
+        let mut element = element!(Parent(mounts: mounts.clone(), unmounts: unmounts.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "renders: 0\n");
+        assert_eq!(mounts.load(Ordering::SeqCst), 1);
+
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.send_key(KeyCode::Char('b'));
+        assert_eq!(renderer.frame(), "renders: 2\n");
+        assert_eq!(mounts.load(Ordering::SeqCst), 1);
+        assert_eq!(unmounts.load(Ordering::SeqCst), 0);
+
+        renderer.send_key(KeyCode::Esc);
+        assert_eq!(renderer.frame(), "");
+        assert_eq!(mounts.load(Ordering::SeqCst), 1);
+        assert_eq!(unmounts.load(Ordering::SeqCst), 1);
+    }
+}
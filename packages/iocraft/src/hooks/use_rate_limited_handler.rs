@@ -0,0 +1,342 @@
+use crate::{
+    clock::{Clock, ClockTimer},
+    Handler, Hook, Hooks,
+};
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// `UseRateLimitedHandler` is a hook that allows you to limit how often a handler is invoked.
+///
+/// This is useful for handlers bound to rapidly firing events, such as resizes, scrolling, or
+/// typing, which trigger expensive work. Like [`UseInterval`](crate::hooks::UseInterval), the
+/// timing is driven by the render loop, and follows the virtual clock of a `TestRenderer`.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::time::Duration;
+/// # fn search(_: &str) -> Vec<String> { vec![] }
+/// #[component]
+/// fn Search(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let query = hooks.use_state(|| String::new());
+///     let results = hooks.use_state(Vec::new);
+///     let mut search = hooks.use_debounced_handler(
+///         Duration::from_millis(300),
+///         move |query: String| results.set(search(&query)),
+///     );
+///
+///     element! {
+///         Box(flex_direction: FlexDirection::Column) {
+///             TextInput(
+///                 has_focus: true,
+///                 value: query.to_string(),
+///                 on_change: move |value: String| {
+///                     query.set(value.clone());
+///                     search.invoke(value);
+///                 },
+///             )
+///             #(results.read().iter().map(|result| element!(Text(content: result.clone()))))
+///         }
+///     }
+/// }
+/// ```
+pub trait UseRateLimitedHandler {
+    /// Returns a handler which invokes the given one only once its invocations stop for `period`.
+    ///
+    /// Each invocation restarts the period, and when it finally elapses, the given handler is
+    /// invoked with the most recent value. Values from the earlier invocations are dropped.
+    ///
+    /// The handler given on the most recent render is the one that gets invoked. Any pending
+    /// invocation is dropped when the component is unmounted.
+    fn use_debounced_handler<T, H>(&mut self, period: Duration, handler: H) -> Handler<'static, T>
+    where
+        T: Send + 'static,
+        H: Into<Handler<'static, T>>;
+
+    /// Returns a handler which invokes the given one at most once per `period`.
+    ///
+    /// The first invocation is passed through immediately. Invocations within the following
+    /// period are held back, and when the period elapses, the given handler is invoked with the
+    /// most recent of them, starting a new period. This means that the last value is never
+    /// dropped.
+    ///
+    /// The handler given on the most recent render is the one that gets invoked. Any pending
+    /// invocation is dropped when the component is unmounted.
+    fn use_throttled_handler<T, H>(&mut self, period: Duration, handler: H) -> Handler<'static, T>
+    where
+        T: Send + 'static,
+        H: Into<Handler<'static, T>>;
+}
+
+impl UseRateLimitedHandler for Hooks<'_, '_> {
+    fn use_debounced_handler<T, H>(&mut self, period: Duration, handler: H) -> Handler<'static, T>
+    where
+        T: Send + 'static,
+        H: Into<Handler<'static, T>>,
+    {
+        self.use_rate_limited_handler(RateLimit::Debounce, period, handler.into())
+    }
+
+    fn use_throttled_handler<T, H>(&mut self, period: Duration, handler: H) -> Handler<'static, T>
+    where
+        T: Send + 'static,
+        H: Into<Handler<'static, T>>,
+    {
+        self.use_rate_limited_handler(RateLimit::Throttle, period, handler.into())
+    }
+}
+
+trait UseRateLimitedHandlerPrivate {
+    fn use_rate_limited_handler<T: Send + 'static>(
+        &mut self,
+        rate_limit: RateLimit,
+        period: Duration,
+        handler: Handler<'static, T>,
+    ) -> Handler<'static, T>;
+}
+
+impl UseRateLimitedHandlerPrivate for Hooks<'_, '_> {
+    fn use_rate_limited_handler<T: Send + 'static>(
+        &mut self,
+        rate_limit: RateLimit,
+        period: Duration,
+        handler: Handler<'static, T>,
+    ) -> Handler<'static, T> {
+        let clock = self.system().clock.clone();
+        let hook = self.use_hook(move || UseRateLimitedHandlerImpl {
+            state: Arc::new(Mutex::new(RateLimitState {
+                rate_limit,
+                clock: clock.clone(),
+                period,
+                handler: Handler::None,
+                pending: None,
+                deadline: None,
+                waker: None,
+            })),
+            clock,
+            timer: None,
+        });
+        {
+            let mut state = hook.state.lock().unwrap();
+            state.period = period;
+            state.handler = handler;
+        }
+        let state = hook.state.clone();
+        Handler::from(move |value| {
+            let value = state.lock().unwrap().invoke(value);
+            if let Some(value) = value {
+                pass_on(&state, value);
+            }
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RateLimit {
+    Debounce,
+    Throttle,
+}
+
+struct RateLimitState<T> {
+    rate_limit: RateLimit,
+    clock: Clock,
+    period: Duration,
+    handler: Handler<'static, T>,
+    pending: Option<T>,
+    // For debouncing, this is when the pending value should be passed on. For throttling, this is
+    // when the current period ends.
+    deadline: Option<Duration>,
+    waker: Option<Waker>,
+}
+
+impl<T> RateLimitState<T> {
+    /// Records an invocation. Returns the value if it should be passed on right away.
+    fn invoke(&mut self, value: T) -> Option<T> {
+        let now = self.clock.now();
+        let value = match self.rate_limit {
+            RateLimit::Debounce => {
+                self.pending = Some(value);
+                self.deadline = Some(now + self.period);
+                None
+            }
+            RateLimit::Throttle => {
+                if self.deadline.is_some_and(|deadline| now < deadline) {
+                    self.pending = Some(value);
+                    None
+                } else {
+                    self.deadline = Some(now + self.period);
+                    Some(value)
+                }
+            }
+        };
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+        value
+    }
+
+    /// Takes the pending value if its time has come. Returns it, along with the deadline that the
+    /// hook needs to wait for, if any.
+    fn flush(&mut self) -> (Option<T>, Option<Duration>) {
+        let Some(deadline) = self.deadline else {
+            return (None, None);
+        };
+        if self.clock.now() < deadline {
+            return (None, self.pending.is_some().then_some(deadline));
+        }
+        let value = self.pending.take();
+        self.deadline = match (&value, self.rate_limit) {
+            (Some(_), RateLimit::Throttle) => Some(deadline + self.period),
+            _ => None,
+        };
+        (value, None)
+    }
+}
+
+/// Invokes the handler with the given value. The lock isn't held while doing so, so that the
+/// handler can use the rate-limited handler itself.
+fn pass_on<T>(state: &Mutex<RateLimitState<T>>, value: T) {
+    let mut handler = state.lock().unwrap().handler.take();
+    handler.invoke(value);
+    let mut state = state.lock().unwrap();
+    // A render may have replaced the handler in the meantime, in which case that one is kept.
+    if state.handler.is_none() {
+        state.handler = handler;
+    }
+}
+
+struct UseRateLimitedHandlerImpl<T> {
+    state: Arc<Mutex<RateLimitState<T>>>,
+    clock: Clock,
+    timer: Option<ClockTimer>,
+}
+
+impl<T> Unpin for UseRateLimitedHandlerImpl<T> {}
+
+impl<T> Hook for UseRateLimitedHandlerImpl<T> {
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let (value, deadline) = {
+            let mut state = self.state.lock().unwrap();
+            state.waker = Some(cx.waker().clone());
+            state.flush()
+        };
+        if let Some(value) = value {
+            pass_on(&self.state, value);
+        }
+        match deadline {
+            Some(deadline) => {
+                let this = &mut *self;
+                let timer = this
+                    .timer
+                    .get_or_insert_with(|| this.clock.timer_at(deadline));
+                timer.set_deadline(deadline);
+                if timer.poll(cx).is_ready() {
+                    self.timer = None;
+                    cx.waker().wake_by_ref();
+                }
+            }
+            None => self.timer = None,
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[derive(Default, Props)]
+    struct RecorderProps {
+        throttle: bool,
+        calls: Arc<Mutex<Vec<char>>>,
+    }
+
+    #[component]
+    fn Recorder(mut hooks: Hooks, props: &RecorderProps) -> impl Into<AnyElement<'static>> {
+        let period = Duration::from_millis(100);
+        let record = {
+            let calls = props.calls.clone();
+            move |c| calls.lock().unwrap().push(c)
+        };
+        let mut handler = if props.throttle {
+            hooks.use_throttled_handler(period, record)
+        } else {
+            hooks.use_debounced_handler(period, record)
+        };
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            }) = event
+            {
+                handler.invoke(c);
+            }
+        });
+        element!(Text)
+    }
+
+    #[test]
+    fn test_use_debounced_handler() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut element = element!(Recorder(calls: calls.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.advance_time(Duration::from_millis(60));
+        renderer.send_key(KeyCode::Char('b'));
+        renderer.advance_time(Duration::from_millis(60));
+        renderer.send_key(KeyCode::Char('c'));
+        renderer.advance_time(Duration::from_millis(60));
+        assert!(calls.lock().unwrap().is_empty());
+
+        // Only the last value is passed on, once things settle down.
+        renderer.advance_time(Duration::from_millis(40));
+        assert_eq!(*calls.lock().unwrap(), vec!['c']);
+
+        renderer.send_key(KeyCode::Char('d'));
+        renderer.advance_time(Duration::from_millis(100));
+        assert_eq!(*calls.lock().unwrap(), vec!['c', 'd']);
+    }
+
+    #[test]
+    fn test_use_throttled_handler() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut element = element!(Recorder(throttle: true, calls: calls.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+
+        // The first value goes through immediately.
+        renderer.send_key(KeyCode::Char('a'));
+        assert_eq!(*calls.lock().unwrap(), vec!['a']);
+
+        renderer.advance_time(Duration::from_millis(30));
+        renderer.send_key(KeyCode::Char('b'));
+        renderer.advance_time(Duration::from_millis(30));
+        renderer.send_key(KeyCode::Char('c'));
+        assert_eq!(*calls.lock().unwrap(), vec!['a']);
+
+        // The most recent value goes through at the end of the period.
+        renderer.advance_time(Duration::from_millis(40));
+        assert_eq!(*calls.lock().unwrap(), vec!['a', 'c']);
+
+        // Which starts a new period.
+        renderer.send_key(KeyCode::Char('d'));
+        renderer.advance_time(Duration::from_millis(50));
+        assert_eq!(*calls.lock().unwrap(), vec!['a', 'c']);
+        renderer.advance_time(Duration::from_millis(50));
+        assert_eq!(*calls.lock().unwrap(), vec!['a', 'c', 'd']);
+
+        // Once things are quiet, values go through immediately again.
+        renderer.advance_time(Duration::from_millis(200));
+        renderer.send_key(KeyCode::Char('e'));
+        assert_eq!(*calls.lock().unwrap(), vec!['a', 'c', 'd', 'e']);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{Hook, Hooks};
+use generational_box::{AnyStorage, GenerationalBox, Owner, SyncStorage};
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops,
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+/// `UseRef` is a hook that allows you to store a mutable value in a component without causing it
+/// to re-render.
+///
+/// This is useful for values that the component needs to remember but which don't affect its
+/// output, such as caches, accumulators, or the value of a prop from the previous render.
+pub trait UseRef {
+    /// Creates a new ref with its initial value computed by the given function. The value lives
+    /// for as long as the component does, and is dropped when the component is unmounted.
+    ///
+    /// Unlike [`UseState::use_state`](crate::hooks::UseState::use_state), modifying the value
+    /// never causes the component to be re-rendered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// #[derive(Default, Props)]
+    /// struct ScoreProps {
+    ///     score: i32,
+    /// }
+    ///
+    /// #[component]
+    /// fn Score(mut hooks: Hooks, props: &ScoreProps) -> impl Into<AnyElement<'static>> {
+    ///     let previous_score = hooks.use_ref(|| props.score);
+    ///     let change = props.score - previous_score.get();
+    ///     previous_score.set(props.score);
+    ///
+    ///     element! {
+    ///         Text(content: format!("{} ({:+})", props.score, change))
+    ///     }
+    /// }
+    /// ```
+    fn use_ref<T, F>(&mut self, initial_value: F) -> Ref<T>
+    where
+        T: Sync + Send + 'static,
+        F: FnOnce() -> T;
+}
+
+impl UseRef for Hooks<'_, '_> {
+    fn use_ref<T, F>(&mut self, initial_value: F) -> Ref<T>
+    where
+        T: Sync + Send + 'static,
+        F: FnOnce() -> T,
+    {
+        self.use_hook(move || UseRefImpl::new(initial_value()))
+            .r#ref
+    }
+}
+
+struct UseRefImpl<T: Send + Sync + 'static> {
+    _storage: Owner<SyncStorage>,
+    r#ref: Ref<T>,
+}
+
+impl<T: Send + Sync + 'static> UseRefImpl<T> {
+    pub fn new(initial_value: T) -> Self {
+        let storage = Owner::default();
+        UseRefImpl {
+            r#ref: Ref {
+                borrow: storage.insert(AtomicIsize::new(0)),
+                value: storage.insert(initial_value),
+            },
+            _storage: storage,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Hook for UseRefImpl<T> {}
+
+/// A reference to the value of a [`Ref`], returned by [`Ref::borrow`].
+pub struct RefBorrow<T: 'static> {
+    borrow: GenerationalBox<AtomicIsize, SyncStorage>,
+    inner: Option<<SyncStorage as AnyStorage>::Ref<'static, T>>,
+}
+
+impl<T: 'static> ops::Deref for RefBorrow<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_ref().expect("borrow is live until dropped")
+    }
+}
+
+impl<T: 'static> Drop for RefBorrow<T> {
+    fn drop(&mut self) {
+        self.inner = None;
+        self.borrow.read().fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// A mutable reference to the value of a [`Ref`], returned by [`Ref::borrow_mut`].
+pub struct RefBorrowMut<T: 'static> {
+    borrow: GenerationalBox<AtomicIsize, SyncStorage>,
+    inner: Option<<SyncStorage as AnyStorage>::Mut<'static, T>>,
+}
+
+impl<T: 'static> ops::Deref for RefBorrowMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.as_ref().expect("borrow is live until dropped")
+    }
+}
+
+impl<T: 'static> ops::DerefMut for RefBorrowMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.as_mut().expect("borrow is live until dropped")
+    }
+}
+
+impl<T: 'static> Drop for RefBorrowMut<T> {
+    fn drop(&mut self) {
+        self.inner = None;
+        self.borrow.read().store(0, Ordering::Release);
+    }
+}
+
+/// `Ref` is a copyable handle to a mutable value which persists across renders. Unlike a
+/// [`State`](crate::hooks::State), modifying it never causes the component to be re-rendered.
+///
+/// Borrows are checked at runtime, following the same rules as a
+/// [`RefCell`](std::cell::RefCell): the value can either be borrowed immutably any number of
+/// times, or mutably once. Borrowing the value in a way that breaks these rules panics, or
+/// returns `None` when using the `try_` methods.
+pub struct Ref<T: Send + Sync + 'static> {
+    // The storage's locks block rather than fail when the value is already borrowed, so borrows
+    // are tracked separately: the count is positive while the value is borrowed immutably, and -1
+    // while it's borrowed mutably. Locking the value only ever happens once this allows it.
+    borrow: GenerationalBox<AtomicIsize, SyncStorage>,
+    value: GenerationalBox<T, SyncStorage>,
+}
+
+impl<T: Send + Sync + 'static> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + 'static> Copy for Ref<T> {}
+
+// The value lives in the storage rather than in the handle, so moving the handle never moves it.
+impl<T: Send + Sync + 'static> Unpin for Ref<T> {}
+
+impl<T: Copy + Send + Sync + 'static> Ref<T> {
+    /// Gets a copy of the current value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    pub fn get(&self) -> T {
+        *self.borrow()
+    }
+}
+
+impl<T: Send + Sync + 'static> Ref<T> {
+    /// Sets the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed.
+    pub fn set(&self, value: T) {
+        *self.borrow_mut() = value;
+    }
+
+    /// Immutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn borrow(&self) -> RefBorrow<T> {
+        self.try_borrow()
+            .expect("value is already mutably borrowed")
+    }
+
+    /// Mutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed.
+    #[track_caller]
+    pub fn borrow_mut(&self) -> RefBorrowMut<T> {
+        self.try_borrow_mut().expect("value is already borrowed")
+    }
+
+    /// Immutably borrows the value, returning `None` if it's currently mutably borrowed.
+    pub fn try_borrow(&self) -> Option<RefBorrow<T>> {
+        self.borrow
+            .read()
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |borrow| {
+                (borrow >= 0).then_some(borrow + 1)
+            })
+            .ok()?;
+        Some(RefBorrow {
+            borrow: self.borrow,
+            inner: Some(self.value.read()),
+        })
+    }
+
+    /// Mutably borrows the value, returning `None` if it's currently borrowed.
+    pub fn try_borrow_mut(&self) -> Option<RefBorrowMut<T>> {
+        self.borrow
+            .read()
+            .compare_exchange(0, -1, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(RefBorrowMut {
+            borrow: self.borrow,
+            inner: Some(self.value.write()),
+        })
+    }
+}
+
+impl<T: Debug + Send + Sync + 'static> Debug for Ref<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.try_borrow() {
+            Some(value) => value.fmt(f),
+            None => f.write_str("<borrowed>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_ref() {
+        let hook = UseRefImpl::new(vec![1]);
+        let r = hook.r#ref;
+
+        r.borrow_mut().push(2);
+        assert_eq!(*r.borrow(), vec![1, 2]);
+
+        {
+            let a = r.borrow();
+            let b = r.borrow();
+            assert_eq!(a.len() + b.len(), 4);
+            assert!(r.try_borrow_mut().is_none());
+        }
+
+        {
+            let _a = r.borrow_mut();
+            assert!(r.try_borrow().is_none());
+            assert!(r.try_borrow_mut().is_none());
+        }
+
+        r.set(vec![3]);
+        assert_eq!(format!("{:?}", r), "[3]");
+    }
+
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct KeyCounterProps {
+        drops: Arc<AtomicUsize>,
+    }
+
+    #[component]
+    fn KeyCounter(mut hooks: Hooks, props: &KeyCounterProps) -> impl Into<AnyElement<'static>> {
+        let drops = props.drops.clone();
+        let presses = hooks.use_ref(move || (0, DropCounter(drops)));
+        let shown_presses = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent { code, .. }) = event {
+                presses.borrow_mut().0 += 1;
+                if code == KeyCode::Enter {
+                    shown_presses.set(presses.borrow().0);
+                }
+            }
+        });
+
+        element! {
+            Text(content: format!("presses: {} ({})", shown_presses, presses.borrow().0))
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct ParentProps {
+        drops: Arc<AtomicUsize>,
+    }
+
+    #[component]
+    fn Parent(mut hooks: Hooks, props: &ParentProps) -> impl Into<AnyElement<'static>> {
+        let show = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) = event
+            {
+                show.set(false);
+            }
+        });
+        element! {
+            Box {
+                #(show.get().then(|| element!(KeyCounter(drops: props.drops.clone()))))
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_ref() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut element = element!(Parent(drops: drops.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "presses: 0 (0)\n");
+
+        // The value is kept across renders.
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.send_key(KeyCode::Char('b'));
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "presses: 3 (3)\n");
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        // The value is dropped when the component is unmounted.
+        renderer.send_key(KeyCode::Esc);
+        assert_eq!(renderer.frame(), "");
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{ComponentUpdater, Hook, Hooks};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// `UseRenderHandle` is a hook that allows a component to be re-rendered on demand from outside
+/// of the render loop.
+///
+/// This is useful for bridging push-style sources, such as background threads, file watchers, or
+/// callbacks from other libraries, into the UI. For pull-style sources, see
+/// [`UseFuture`](crate::hooks::UseFuture).
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+/// #[component]
+/// fn Downloads(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let render = hooks.use_render_handle();
+///     let completed = hooks.use_ref(|| Arc::new(AtomicUsize::new(0)));
+///     hooks.use_mount({
+///         let completed = completed.borrow().clone();
+///         move || {
+///             std::thread::spawn(move || {
+///                 for _ in 0..3 {
+///                     // ... download something ...
+///                     completed.fetch_add(1, Ordering::SeqCst);
+///                     render.request_render();
+///                 }
+///             });
+///         }
+///     });
+///
+///     element! {
+///         Text(content: format!("completed: {}", completed.borrow().load(Ordering::SeqCst)))
+///     }
+/// }
+/// ```
+pub trait UseRenderHandle {
+    /// Returns a handle which can be used to request that the component be re-rendered.
+    ///
+    /// The same handle is returned on every render.
+    fn use_render_handle(&mut self) -> RenderHandle;
+}
+
+impl UseRenderHandle for Hooks<'_, '_> {
+    fn use_render_handle(&mut self) -> RenderHandle {
+        self.use_hook(|| UseRenderHandleImpl {
+            handle: RenderHandle {
+                inner: Default::default(),
+            },
+        })
+        .handle
+        .clone()
+    }
+}
+
+#[derive(Default)]
+struct RenderHandleInner {
+    is_requested: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// A handle to a component which can be used to request that it be re-rendered, returned by
+/// [`UseRenderHandle::use_render_handle`].
+///
+/// It can be cloned and sent to other threads. If the component has been unmounted, requests have
+/// no effect.
+#[derive(Clone)]
+pub struct RenderHandle {
+    inner: Arc<RenderHandleInner>,
+}
+
+impl RenderHandle {
+    /// Requests that the component be re-rendered.
+    ///
+    /// This doesn't block. Like changes to [`State`](crate::hooks::State), requests are batched:
+    /// any number of requests and state changes made before the next frame are rendered in a
+    /// single pass.
+    pub fn request_render(&self) {
+        self.inner.is_requested.store(true, Ordering::SeqCst);
+        if let Some(waker) = self.inner.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+struct UseRenderHandleImpl {
+    handle: RenderHandle,
+}
+
+impl Hook for UseRenderHandleImpl {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &self.handle.inner;
+        *inner.waker.lock().unwrap() = Some(cx.waker().clone());
+        if inner.is_requested.swap(false, Ordering::SeqCst) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn pre_component_update(&mut self, updater: &mut ComponentUpdater) {
+        // A request which hasn't been polled yet is satisfied by this render, which mustn't be
+        // skipped, even if the component is memoized.
+        if self.handle.inner.is_requested.swap(false, Ordering::SeqCst) {
+            updater.mark_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use futures::task::noop_waker;
+    use std::{
+        sync::{atomic::AtomicUsize, Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn test_render_handle() {
+        let mut hook = UseRenderHandleImpl {
+            handle: RenderHandle {
+                inner: Default::default(),
+            },
+        };
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        assert!(Pin::new(&mut hook).poll_change(&mut cx).is_pending());
+
+        // Multiple requests are coalesced.
+        hook.handle.request_render();
+        hook.handle.clone().request_render();
+        assert!(Pin::new(&mut hook).poll_change(&mut cx).is_ready());
+        assert!(Pin::new(&mut hook).poll_change(&mut cx).is_pending());
+    }
+
+    #[derive(Default, Props)]
+    struct CounterProps {
+        count: Arc<AtomicUsize>,
+        handle: Arc<Mutex<Option<RenderHandle>>>,
+    }
+
+    #[component]
+    fn Counter(mut hooks: Hooks, props: &CounterProps) -> impl Into<AnyElement<'static>> {
+        *props.handle.lock().unwrap() = Some(hooks.use_render_handle());
+        element! {
+            Text(content: format!("count: {}", props.count.load(Ordering::SeqCst)))
+        }
+    }
+
+    #[test]
+    fn test_use_render_handle() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let handle = Arc::new(Mutex::new(None));
+        let mut element = element!(Counter(count: count.clone(), handle: handle.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "count: 0\n");
+
+        let handle = handle.lock().unwrap().clone().unwrap();
+        std::thread::spawn(move || {
+            count.store(2, Ordering::SeqCst);
+            handle.request_render();
+            handle.request_render();
+        })
+        .join()
+        .unwrap();
+        renderer.advance_time(Duration::ZERO);
+        assert_eq!(renderer.frame(), "count: 2\n");
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{Hook, Hooks};
+use std::sync::{Arc, Mutex};
+
+/// `UseResource` is a hook that allows a component to own a resource, such as a file, socket, or
+/// child process, which is released when the component is unmounted.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::process::{Child, Command};
+/// struct Process(Child);
+///
+/// impl Drop for Process {
+///     fn drop(&mut self) {
+///         let _ = self.0.kill();
+///     }
+/// }
+///
+/// #[component]
+/// fn Tail(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let process = hooks.use_resource(|| {
+///         Command::new("tail").args(["-f", "app.log"]).spawn().ok().map(Process)
+///     });
+///     let pid = process.with(|process| process.as_ref().map(|p| p.0.id()));
+///
+///     element! {
+///         Text(content: match pid.flatten() {
+///             Some(pid) => format!("tailing in process {}", pid),
+///             None => "failed to start tail".to_string(),
+///         })
+///     }
+/// }
+/// ```
+pub trait UseResource {
+    /// Creates a resource with the given function when the component is first rendered, and
+    /// returns a handle to it on every render.
+    ///
+    /// The resource is dropped when the component is unmounted. This happens after all of the
+    /// component's children have been unmounted, so they may still use the resource while being
+    /// torn down. Resources of the same component are dropped in the order that their hooks were
+    /// called.
+    fn use_resource<T, F>(&mut self, f: F) -> Resource<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T;
+}
+
+impl UseResource for Hooks<'_, '_> {
+    fn use_resource<T, F>(&mut self, f: F) -> Resource<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T,
+    {
+        self.use_hook(move || UseResourceImpl {
+            resource: Resource {
+                inner: Arc::new(Mutex::new(Some(f()))),
+            },
+        })
+        .resource
+        .clone()
+    }
+}
+
+/// A handle to a resource created by [`UseResource::use_resource`].
+///
+/// Handles can be cloned and moved into handlers and futures. Once the owning component is
+/// unmounted, the resource is released and the handles no longer provide access to it.
+pub struct Resource<T> {
+    inner: Arc<Mutex<Option<T>>>,
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Resource<T> {
+    /// Calls the given function with a mutable reference to the resource, returning its result.
+    /// If the resource has already been released, the function isn't called and `None` is
+    /// returned.
+    ///
+    /// The resource is locked while the function runs, so calling this again from within the
+    /// function will deadlock.
+    pub fn with<R, F>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.inner.lock().unwrap().as_mut().map(f)
+    }
+
+    /// Returns `true` if the owning component has been unmounted and the resource released.
+    pub fn is_released(&self) -> bool {
+        self.inner.lock().unwrap().is_none()
+    }
+}
+
+struct UseResourceImpl<T> {
+    resource: Resource<T>,
+}
+
+impl<T> Unpin for UseResourceImpl<T> {}
+
+impl<T: Send + 'static> Hook for UseResourceImpl<T> {}
+
+impl<T> Drop for UseResourceImpl<T> {
+    fn drop(&mut self) {
+        // The resource is dropped outside of the lock, in case its destructor blocks.
+        let resource = self.resource.inner.lock().unwrap().take();
+        drop(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    struct Connection {
+        events: Arc<Mutex<Vec<String>>>,
+        sent: usize,
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("closed after {} sends", self.sent));
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct ChildProps {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[component]
+    fn Child(mut hooks: Hooks, props: &ChildProps) -> impl Into<AnyElement<'static>> {
+        let events = props.events.clone();
+        hooks.use_unmount(move || events.lock().unwrap().push("child unmounted".to_string()));
+        element!(Text(content: "child"))
+    }
+
+    #[derive(Default, Props)]
+    struct ClientProps {
+        events: Arc<Mutex<Vec<String>>>,
+        released: Option<Arc<Mutex<Option<Resource<Connection>>>>>,
+    }
+
+    #[component]
+    fn Client(mut hooks: Hooks, props: &ClientProps) -> impl Into<AnyElement<'static>> {
+        let events = props.events.clone();
+        let connection = hooks.use_resource(move || Connection { events, sent: 0 });
+        if let Some(released) = &props.released {
+            *released.lock().unwrap() = Some(connection.clone());
+        }
+        hooks.use_terminal_events({
+            let connection = connection.clone();
+            move |event| {
+                if let TerminalEvent::Key(KeyEvent {
+                    code: KeyCode::Char(_),
+                    ..
+                }) = event
+                {
+                    connection.with(|connection| connection.sent += 1);
+                }
+            }
+        });
+        let sent = connection.with(|connection| connection.sent).unwrap_or(0);
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Text(content: format!("sent: {}", sent))
+                Child(events: props.events.clone())
+            }
+        }
+    }
+
+    #[derive(Default, Props)]
+    struct ParentProps {
+        events: Arc<Mutex<Vec<String>>>,
+        released: Option<Arc<Mutex<Option<Resource<Connection>>>>>,
+    }
+
+    #[component]
+    fn Parent(mut hooks: Hooks, props: &ParentProps) -> impl Into<AnyElement<'static>> {
+        let show = hooks.use_state(|| true);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) = event
+            {
+                show.set(false);
+            }
+        });
+        element! {
+            Box {
+                #(show.get().then(|| element! {
+                    Client(events: props.events.clone(), released: props.released.clone())
+                }))
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_resource() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let released = Arc::new(Mutex::new(None));
+        let mut element = element!(Parent(events: events.clone(), released: released.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "sent: 0\nchild\n");
+
+        renderer.send_key(KeyCode::Char('a'));
+        renderer.send_key(KeyCode::Char('b'));
+        renderer.advance_time(std::time::Duration::ZERO);
+        assert_eq!(renderer.frame(), "sent: 2\nchild\n");
+        assert!(events.lock().unwrap().is_empty());
+
+        // The resource is released after the children are unmounted.
+        renderer.send_key(KeyCode::Esc);
+        assert_eq!(renderer.frame(), "");
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["child unmounted", "closed after 2 sends"]
+        );
+
+        let resource = released.lock().unwrap().take().unwrap();
+        assert!(resource.is_released());
+        assert_eq!(resource.with(|connection| connection.sent), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Hook, Hooks};
+use crate::{ComponentUpdater, Hook, Hooks};
 use generational_box::{AnyStorage, GenerationalBox, Owner, SyncStorage};
 use std::{
     cmp,
@@ -10,7 +10,10 @@ use std::{
 
 /// `UseState` is a hook that allows you to store state in a component.
 ///
-/// When the state changes, the component will be re-rendered.
+/// When the state changes, the component will be re-rendered. Changes are batched: everything
+/// which happens before the next frame, such as a handler setting several states or the handling
+/// of a burst of terminal events, is rendered in a single pass, so a handler's updates are never
+/// rendered partially applied.
 ///
 /// # Example
 ///
@@ -73,6 +76,18 @@ impl<T: Unpin + Send + Sync + 'static> Hook for UseStateImpl<T> {
             Poll::Pending
         }
     }
+
+    fn pre_component_update(&mut self, updater: &mut ComponentUpdater) {
+        // The component is about to render the latest value, so any change which hasn't been
+        // polled yet doesn't need a render of its own. It does need this one though, even if the
+        // component is memoized.
+        if let Ok(mut value) = self.state.inner.try_write() {
+            if value.did_change {
+                value.did_change = false;
+                updater.mark_changed();
+            }
+        }
+    }
 }
 
 struct StateValue<T> {
@@ -241,10 +256,19 @@ impl<T: cmp::Eq + Sync + Send + 'static> cmp::Eq for State<T> {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prelude::*;
     use futures::task::noop_waker;
-    use std::pin::Pin;
+    use std::{
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        task::Context,
+    };
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_state() {
         let mut hook = UseStateImpl::new(42);
         let mut state = hook.state;
@@ -283,4 +307,80 @@ mod tests {
         assert!(state >= 43);
         assert!(state < 44);
     }
+
+    #[derive(Default, Props)]
+    struct PairProps {
+        renders: Arc<AtomicUsize>,
+    }
+
+    #[component]
+    fn Pair(mut hooks: Hooks, props: &PairProps) -> impl Into<AnyElement<'static>> {
+        props.renders.fetch_add(1, Ordering::SeqCst);
+        // The handler is deliberately placed between the states, so that one of them has already
+        // been polled by the time it's invoked.
+        let a = hooks.use_state(|| 0);
+        let later_state = hooks.use_ref(|| None::<State<i32>>);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(_) = event {
+                a.set(a + 1);
+                if let Some(b) = later_state.get() {
+                    b.set(b + 1);
+                }
+            }
+        });
+        let b = hooks.use_state(|| 0);
+        later_state.set(Some(b));
+        element! {
+            Text(content: format!("{} {}", a, b))
+        }
+    }
+
+    #[test]
+    fn test_state_batching() {
+        let renders = Arc::new(AtomicUsize::new(0));
+        let mut element = element!(Pair(renders: renders.clone()));
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        // The test renderer renders once before delivering the event, and then all of the changes
+        // caused by the event are rendered at once.
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frames(), vec!["0 0\n", "1 1\n"]);
+        assert_eq!(renders.load(Ordering::SeqCst), 3);
+    }
+
+    // Lets the test set the label's state from outside of the tree.
+    static EXCLAIM: Mutex<Option<State<bool>>> = Mutex::new(None);
+
+    #[component(memo)]
+    fn Label(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let exclaim = hooks.use_state(|| false);
+        *EXCLAIM.lock().unwrap() = Some(exclaim);
+        element!(Text(content: if exclaim.get() { "a!" } else { "a" }))
+    }
+
+    #[component]
+    fn LabelParent(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let presses = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |_| presses.set(presses + 1));
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                Label
+                Text(content: presses.to_string())
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_change_in_memoized_component() {
+        let mut element = element!(LabelParent);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "a\n0\n");
+
+        // The label's change hasn't been polled by the time the parent re-renders, so the label
+        // must pick it up during the parent's render rather than skipping its own.
+        EXCLAIM.lock().unwrap().unwrap().set(true);
+        renderer.send_key(KeyCode::Enter);
+        assert_eq!(renderer.frame(), "a!\n1\n");
+    }
 }
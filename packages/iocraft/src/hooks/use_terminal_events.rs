@@ -1,9 +1,12 @@
-use crate::{ComponentUpdater, Hook, Hooks, TerminalEvent, TerminalEvents};
+use crate::{
+    ComponentDrawer, ComponentUpdater, Hook, Hooks, MouseEvent, TerminalEvent, TerminalEvents,
+};
 use futures::stream::Stream;
 use std::{
     pin::{pin, Pin},
     task::{Context, Poll},
 };
+use taffy::{Point, Size};
 
 /// `UseTerminalEvents` is a hook that allows you to listen for user input such as key strokes.
 ///
@@ -14,9 +17,48 @@ use std::{
 /// ```
 pub trait UseTerminalEvents {
     /// Defines a callback to be invoked whenever a terminal event occurs.
+    ///
+    /// Any number of components may listen for events simultaneously, and each of them will
+    /// receive every event in the order that it occurred. The callback is dropped and stops
+    /// receiving events when the component is unmounted.
     fn use_terminal_events<F>(&mut self, f: F)
     where
         F: FnMut(TerminalEvent) + Send + 'static;
+
+    /// Defines a callback to be invoked whenever a terminal event occurs within the component.
+    ///
+    /// Unlike [`use_terminal_events`](UseTerminalEvents::use_terminal_events), mouse events which
+    /// occur outside of the area the component was last drawn to are not delivered, and the
+    /// coordinates of mouse events are made relative to the top left corner of the component.
+    /// This makes it easy to implement things like clickable regions. Events which don't have a
+    /// position, such as key events, are delivered as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// #[component]
+    /// fn ClickCounter(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+    ///     let clicks = hooks.use_state(|| 0);
+    ///     hooks.use_local_terminal_events(move |event| {
+    ///         if let TerminalEvent::Mouse(MouseEvent {
+    ///             kind: MouseEventKind::Down(MouseButton::Left),
+    ///             ..
+    ///         }) = event
+    ///         {
+    ///             clicks.set(clicks.get() + 1);
+    ///         }
+    ///     });
+    ///     element! {
+    ///         Box(border_style: BorderStyle::Round) {
+    ///             Text(content: format!("clicked {} times", clicks))
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn use_local_terminal_events<F>(&mut self, f: F)
+    where
+        F: FnMut(TerminalEvent) + Send + 'static;
 }
 
 impl UseTerminalEvents for Hooks<'_, '_> {
@@ -26,6 +68,20 @@ impl UseTerminalEvents for Hooks<'_, '_> {
     {
         self.use_hook(move || UseTerminalEventsImpl {
             events: None,
+            local: false,
+            rect: None,
+            f: Box::new(f),
+        });
+    }
+
+    fn use_local_terminal_events<F>(&mut self, f: F)
+    where
+        F: FnMut(TerminalEvent) + Send + 'static,
+    {
+        self.use_hook(move || UseTerminalEventsImpl {
+            events: None,
+            local: true,
+            rect: None,
             f: Box::new(f),
         });
     }
@@ -33,9 +89,38 @@ impl UseTerminalEvents for Hooks<'_, '_> {
 
 struct UseTerminalEventsImpl {
     events: Option<TerminalEvents>,
+    local: bool,
+    // The position and size of the component as of its last draw.
+    rect: Option<(Point<u16>, Size<u16>)>,
     f: Box<dyn FnMut(TerminalEvent) + Send + 'static>,
 }
 
+impl UseTerminalEventsImpl {
+    /// Converts the event to the component's local coordinate space, returning `None` if the
+    /// component shouldn't receive it.
+    fn localize(&self, event: TerminalEvent) -> Option<TerminalEvent> {
+        if !self.local {
+            return Some(event);
+        }
+        match event {
+            TerminalEvent::Mouse(event) => {
+                let (position, size) = self.rect?;
+                let column = event.column.checked_sub(position.x)?;
+                let row = event.row.checked_sub(position.y)?;
+                if column >= size.width || row >= size.height {
+                    return None;
+                }
+                Some(TerminalEvent::Mouse(MouseEvent {
+                    column,
+                    row,
+                    ..event
+                }))
+            }
+            event => Some(event),
+        }
+    }
+}
+
 impl Hook for UseTerminalEventsImpl {
     fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
         while let Some(Poll::Ready(Some(event))) = self
@@ -43,11 +128,17 @@ impl Hook for UseTerminalEventsImpl {
             .as_mut()
             .map(|events| pin!(events).poll_next(cx))
         {
-            (self.f)(event);
+            if let Some(event) = self.localize(event) {
+                (self.f)(event);
+            }
         }
         Poll::Pending
     }
 
+    fn pre_component_draw(&mut self, drawer: &mut ComponentDrawer) {
+        self.rect = Some((drawer.canvas_position(), drawer.size()));
+    }
+
     fn post_component_update(&mut self, updater: &mut ComponentUpdater) {
         if self.events.is_none() {
             self.events = updater.terminal_events();
@@ -77,6 +168,58 @@ mod tests {
         }
     }
 
+    #[component]
+    fn EventRecorder(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let events = hooks.use_state(String::new);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                kind,
+                ..
+            }) = event
+            {
+                let mut s = events.read().clone();
+                s.push(c);
+                s.push(match kind {
+                    KeyEventKind::Press => '+',
+                    KeyEventKind::Repeat => '*',
+                    KeyEventKind::Release => '-',
+                });
+                events.set(s);
+            }
+        });
+        element!(Text(content: events.to_string()))
+    }
+
+    #[component]
+    fn MultipleSubscribers(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let mut system = hooks.use_context_mut::<SystemContext>();
+        let count = hooks.use_state(|| 0);
+        hooks.use_terminal_events(move |_event| {
+            count.set(count.get() + 1);
+        });
+
+        if count.get() == 5 {
+            system.exit();
+        }
+
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                EventRecorder
+                EventRecorder
+            }
+        }
+    }
+
+    #[apply(test!)]
+    async fn test_use_terminal_events_multiple_subscribers() {
+        let canvases = mock_terminal_render_loop(element!(MultipleSubscribers))
+            .await
+            .unwrap();
+        let actual = canvases.last().unwrap().to_string();
+        assert_eq!(actual, "f+f-o+o*o-\nf+f-o+o*o-\n");
+    }
+
     #[apply(test!)]
     async fn test_use_terminal_events() {
         let canvases = mock_terminal_render_loop(element!(MyComponent))
@@ -86,4 +229,53 @@ mod tests {
         let expected = vec!["", "received event\n"];
         assert_eq!(actual, expected);
     }
+
+    #[component]
+    fn ClickTarget(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let clicks = hooks.use_state(Vec::new);
+        hooks.use_local_terminal_events(move |event| {
+            if let TerminalEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) = event
+            {
+                let mut new_clicks = clicks.read().clone();
+                new_clicks.push(format!("{},{}", column, row));
+                clicks.set(new_clicks);
+            }
+        });
+        element! {
+            Box(width: 10, height: 2) {
+                Text(content: clicks.read().join(" "))
+            }
+        }
+    }
+
+    fn click(column: u16, row: u16) -> TerminalEvent {
+        TerminalEvent::Mouse(MouseEvent {
+            column,
+            row,
+            kind: MouseEventKind::Down(MouseButton::Left),
+            modifiers: KeyModifiers::empty(),
+        })
+    }
+
+    #[test]
+    fn test_use_local_terminal_events() {
+        let mut element = element! {
+            Box(flex_direction: FlexDirection::Column, padding_left: 2) {
+                ClickTarget
+                ClickTarget
+            }
+        };
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send(click(3, 0));
+        renderer.send(click(11, 3));
+        renderer.send(click(12, 3));
+        renderer.send(click(1, 1));
+        renderer.send(click(4, 4));
+        assert_eq!(renderer.frame(), "  1,0\n\n  9,1\n\n");
+    }
 }
@@ -5,6 +5,23 @@ use crate::{
 use crossterm::terminal;
 
 /// `UseTerminalSize` is a hook that returns the current terminal size.
+///
+/// The component is re-rendered whenever the terminal is resized. Bursts of resizes, such as those
+/// caused by dragging the edge of the window, are coalesced so that only the final size causes an
+/// update.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn SizeDisplay(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let (width, height) = hooks.use_terminal_size();
+///     element! {
+///         Text(content: format!("{}x{}", width, height))
+///     }
+/// }
+/// ```
 pub trait UseTerminalSize {
     /// Returns the current terminal size as a tuple of `(width, height)`.
     fn use_terminal_size(&mut self) -> (u16, u16);
@@ -21,3 +38,26 @@ impl UseTerminalSize for Hooks<'_, '_> {
         size.get()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[component]
+    fn SizeDisplay(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let (width, height) = hooks.use_terminal_size();
+        element! {
+            Text(content: format!("{}x{}", width, height))
+        }
+    }
+
+    #[test]
+    fn test_use_terminal_size() {
+        let mut element = element!(SizeDisplay);
+        let mut renderer = TestRenderer::new(&mut element);
+        renderer.send(TerminalEvent::Resize(80, 24));
+        assert_eq!(renderer.frame(), "80x24\n");
+        renderer.send(TerminalEvent::Resize(100, 30));
+        assert_eq!(renderer.frame(), "100x30\n");
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{hooks::UseContext, ComponentUpdater, Hook, Hooks, Theme};
+
+/// `UseTheme` is a hook that returns the current [`Theme`].
+///
+/// The theme is provided via a [`ContextProvider`](crate::components::ContextProvider). If no
+/// theme is provided, the default one is returned. When the theme changes, the component is
+/// re-rendered, even if it's [memoized](macro@crate::component#memoization).
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn Alert(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let theme = hooks.use_theme();
+///     element! {
+///         Box(border_style: BorderStyle::Round, border_color: theme.danger) {
+///             Text(content: "Something went wrong")
+///         }
+///     }
+/// }
+/// ```
+pub trait UseTheme {
+    /// Returns the current theme.
+    fn use_theme(&mut self) -> Theme;
+}
+
+impl UseTheme for Hooks<'_, '_> {
+    fn use_theme(&mut self) -> Theme {
+        let theme = self
+            .try_use_context::<Theme>()
+            .map(|theme| *theme)
+            .unwrap_or_default();
+        let hook = self.use_hook(|| UseThemeImpl { theme });
+        hook.theme = theme;
+        theme
+    }
+}
+
+struct UseThemeImpl {
+    theme: Theme,
+}
+
+impl Hook for UseThemeImpl {
+    fn pre_component_update(&mut self, updater: &mut ComponentUpdater) {
+        let theme = updater
+            .get_context::<Theme>()
+            .map(|theme| *theme)
+            .unwrap_or_default();
+        if theme != self.theme {
+            updater.mark_changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[component(memo)]
+    fn Title(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let theme = hooks.use_theme();
+        element!(Text(content: format!("{:?}", theme.primary)))
+    }
+
+    #[component]
+    fn App(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let primary = hooks.use_state(|| Color::Cyan);
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char('t'),
+                ..
+            }) = event
+            {
+                primary.set(Color::Magenta);
+            }
+        });
+        element! {
+            ContextProvider(value: Context::owned(Theme {
+                primary: primary.get(),
+                ..Default::default()
+            })) {
+                Title
+            }
+        }
+    }
+
+    #[test]
+    fn test_use_theme() {
+        assert_eq!(element!(Title).to_string(), "Blue\n");
+
+        let mut element = element!(App);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "Cyan\n");
+
+        // Memoized components are re-rendered when the theme changes.
+        renderer.send_key(KeyCode::Char('t'));
+        assert_eq!(renderer.frame(), "Magenta\n");
+    }
+}
@@ -62,6 +62,7 @@
 // Those types will remain in their modules for the public API.
 
 mod canvas;
+mod clock;
 mod component;
 mod context;
 mod element;
@@ -71,6 +72,9 @@ mod props;
 mod render;
 mod style;
 mod terminal;
+#[cfg(any(test, feature = "testing"))]
+mod testing;
+mod theme;
 
 mod flattened_exports {
     pub use crate::canvas::*;
@@ -83,6 +87,9 @@ mod flattened_exports {
     pub use crate::render::*;
     pub use crate::style::*;
     pub use crate::terminal::*;
+    #[cfg(any(test, feature = "testing"))]
+    pub use crate::testing::*;
+    pub use crate::theme::*;
 
     /// Used to declare an element and its properties.
     ///
@@ -122,8 +129,10 @@ mod flattened_exports {
     /// # }
     /// ```
     ///
-    /// Lastly, you can use Rust to conditionally add child elements via `#()` blocks that evaluate
-    /// to any iterator type:
+    /// Lastly, you can use Rust to add child elements via `#()` blocks. A block can evaluate to a
+    /// single [`Element`] or [`AnyElement`], an `Option` of one for conditional children, or
+    /// anything else that can be iterated over to get elements, such as a `Vec` or the result of
+    /// [`Iterator::map`]. See [`ExtendElements`] for details.
     ///
     /// ```
     /// # use iocraft::prelude::*;
@@ -162,8 +171,141 @@ mod flattened_exports {
     /// }
     /// # }
     /// ```
+    ///
+    /// Elements can also be passed as properties, which is useful for components with "slots" for
+    /// things like titles. Just use a nested `element!` as the value. Properties of type
+    /// [`AnyElement`] or `Option<AnyElement>` accept an element of any type:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// #[derive(Default, Props)]
+    /// struct PanelProps {
+    ///     title: Option<AnyElement<'static>>,
+    ///     children: Vec<AnyElement<'static>>,
+    /// }
+    ///
+    /// #[component]
+    /// fn Panel(props: &mut PanelProps) -> impl Into<AnyElement<'static>> {
+    ///     element! {
+    ///         Box(border_style: BorderStyle::Round, flex_direction: FlexDirection::Column) {
+    ///             #(props.title.take())
+    ///             #(std::mem::take(&mut props.children))
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # fn my_element() -> Element<'static, Panel> {
+    /// element! {
+    ///     Panel(title: element!(Text(content: "Hi", weight: Weight::Bold))) {
+    ///         Text(content: "Hello, world!")
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Keys only need to be unique among the children of the same parent, but they must be unique
+    /// there. If multiple siblings are given the same key, a warning is printed in debug builds,
+    /// or given to the hook set by [`set_warning_hook`](crate::set_warning_hook).
+    ///
+    /// Children can also be declared once per item of an iterator via `#for` loops. Optionally, a
+    /// key can be derived from each item via a `keyed by` clause, so that each item keeps its
+    /// component state when the items are reordered. If no key is given, the elements declared in
+    /// the loop are keyed by their position.
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// # struct User { id: i32, name: String }
+    /// # fn my_element(users: Vec<User>) -> Element<'static, Box> {
+    /// element! {
+    ///     Box(flex_direction: FlexDirection::Column) {
+    ///         #for user in users.iter() keyed by user.id {
+    ///             Text(content: format!("Hello, {}!", user.name))
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Any child, including `#()` blocks and `#for` loops, can be preceded by `#[cfg(...)]`
+    /// attributes to include it only when compiling with the given configuration. The attributes
+    /// apply only to the child which immediately follows them:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// # fn my_element() -> Element<'static, Box> {
+    /// element! {
+    ///     Box(flex_direction: FlexDirection::Column) {
+    ///         Text(content: "Hello, world!")
+    ///         #[cfg(debug_assertions)]
+    ///         Text(content: "This is a debug build.")
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Property values are converted to the property's type via [`Into`], which allows things like
+    /// `&str` values for `String` properties. When that conversion gets in the way of type
+    /// inference, such as an integer literal for a `u64` property, the property can be marked with
+    /// `#[raw]` to assign the value as-is:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// #[derive(Default, Props)]
+    /// struct ProgressProps {
+    ///     total_bytes: u64,
+    /// }
+    ///
+    /// #[component]
+    /// fn Progress(props: &ProgressProps) -> impl Into<AnyElement<'static>> {
+    ///     element!(Text(content: format!("{} bytes", props.total_bytes)))
+    /// }
+    ///
+    /// # fn my_element() -> Element<'static, Progress> {
+    /// element!(Progress(#[raw] total_bytes: 4096))
+    /// # }
+    /// ```
     pub use iocraft_macros::element;
 
+    /// Used to build a style value from a list of fields, with the same conveniences as
+    /// [`element!`] properties, such as `50pct`.
+    ///
+    /// By default, this builds a [`LayoutStyle`], which can be given to any element with layout
+    /// properties via its `style` property. This allows a style to be computed once, possibly
+    /// conditionally, and shared by multiple elements. Properties given individually take
+    /// precedence over the style, unless they're left at their default values:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// # fn my_element(compact: bool) -> Element<'static, Box> {
+    /// let card = style!(
+    ///     width: 50pct,
+    ///     padding: if compact { 0 } else { 1 },
+    ///     flex_direction: FlexDirection::Column,
+    /// );
+    ///
+    /// element! {
+    ///     Box {
+    ///         Box(style: card) {
+    ///             Text(content: "Hello")
+    ///         }
+    ///         Box(style: card, width: 30pct) {
+    ///             Text(content: "World")
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Other style types can be built by giving the type name before the fields in braces. Any
+    /// type with public fields which implements [`Default`] will do:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// let highlight = style!(CanvasTextStyle { color: Color::Yellow, weight: Weight::Bold });
+    /// assert_eq!(highlight.color, Some(Color::Yellow));
+    /// ```
+    pub use iocraft_macros::style;
+
     pub use iocraft_macros::*;
 }
 
@@ -174,6 +316,14 @@ pub mod components;
 
 pub mod hooks;
 
+pub mod animation;
+
+pub mod color;
+
+/// The layout engine, re-exported so that custom components can use the same version of it when
+/// calling [`ComponentUpdater::set_layout_style`] and [`ComponentUpdater::set_measure_func`].
+pub use taffy;
+
 /// By importing this module, you'll bring all of the crate's commonly used types into scope.
 pub mod prelude {
     pub use crate::components::*;
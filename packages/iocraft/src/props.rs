@@ -34,6 +34,18 @@ use std::marker::PhantomData;
 /// }
 /// ```
 ///
+/// The same goes for interior mutability, such as `Cell<&'a T>`. In either case, the error will
+/// point at the struct and explain which of its lifetime parameters is invariant:
+///
+/// ```compile_fail
+/// # use iocraft::prelude::*;
+/// # use std::cell::Cell;
+/// #[derive(Default, Props)]
+/// struct MyProps<'a> {
+///    foo: Cell<&'a str>,
+/// }
+/// ```
+///
 /// Properties can be used by custom components like so:
 ///
 /// ```
@@ -59,8 +71,25 @@ use std::marker::PhantomData;
 /// type is not actually covariant, the derive macro will give you an error at compile-time.
 pub unsafe trait Props {}
 
+/// Used by the [`element!`](crate::element) macro to fill in unspecified properties, producing a
+/// helpful error if the props type doesn't implement [`Default`].
 #[doc(hidden)]
-#[derive(Clone, Copy, iocraft_macros::Props, Default)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` must implement `Default` to be used as props in `element!`",
+    note = "derive `Default` for `{Self}`, or use `#[props(default = ...)]` on its fields to have `#[derive(Props)]` implement it"
+)]
+pub trait DefaultProps: Sized {
+    fn default_props() -> Self;
+}
+
+impl<T: Default> DefaultProps for T {
+    fn default_props() -> Self {
+        T::default()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone, Copy, iocraft_macros::Props, Default, PartialEq, Eq)]
 pub struct NoProps;
 
 struct DropRawImpl<T> {
@@ -114,6 +143,17 @@ impl<'a> AnyProps<'a> {
         unsafe { &mut *(self.raw as *mut T) }
     }
 
+    /// Converts borrowed props into a reference with the lifetime of the borrow.
+    ///
+    /// # Safety
+    ///
+    /// The props must be of type `T`, and must have been created via
+    /// [`borrowed`](Self::borrowed) or [`borrow`](Self::borrow), so that they outlive `'a`.
+    pub(crate) unsafe fn into_mut_unchecked<T: Props>(self) -> &'a mut T {
+        debug_assert!(self.drop.is_none(), "owned props can't be converted");
+        unsafe { &mut *(self.raw as *mut T) }
+    }
+
     pub(crate) fn borrow(&mut self) -> Self {
         Self {
             raw: self.raw,
@@ -1,26 +1,37 @@
 use crate::{
     canvas::{Canvas, CanvasSubviewMut},
     component::{ComponentHelperExt, Components, InstantiatedComponent},
-    context::{Context, ContextStack, SystemContext},
-    element::{ElementExt, ElementKey},
+    components::{catch_panic, ComponentError},
+    context::{Context, ContextStack, SystemContext, SystemState},
+    element::{flatten_fragments, ElementExt, ElementKey},
     props::AnyProps,
+    style::{IntrinsicSize, IntrinsicSizes},
     terminal::{Terminal, TerminalEvents},
 };
-use crossterm::{execute, terminal};
+use crossterm::{execute, terminal, tty::IsTty};
 use futures::future::{select, FutureExt};
 use std::{
     any::Any,
-    cell::{Ref, RefMut},
-    collections::HashMap,
-    io, mem,
+    cell::{Ref, RefCell, RefMut},
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    io::{self, Write},
+    mem,
+    os::fd::AsRawFd,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+use taffy::{
+    AvailableSpace, Dimension, Display, FlexDirection, Layout, NodeId, Point, Size, Style,
+    TaffyTree,
 };
-use taffy::{AvailableSpace, Layout, NodeId, Point, Size, Style, TaffyTree};
 use uuid::Uuid;
 
 pub(crate) struct UpdateContext<'a> {
     terminal: Option<&'a mut Terminal>,
     layout_engine: &'a mut LayoutEngine,
     did_clear_terminal_output: bool,
+    updated_components: usize,
 }
 
 /// Provides information and operations that low level component implementations may need to
@@ -30,6 +41,7 @@ pub struct ComponentUpdater<'a, 'b: 'a, 'c: 'a> {
     children: &'a mut Components,
     context: &'a mut UpdateContext<'b>,
     component_context_stack: &'a mut ContextStack<'c>,
+    has_changes: bool,
 }
 
 impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
@@ -39,14 +51,28 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
         context: &'a mut UpdateContext<'b>,
         component_context_stack: &'a mut ContextStack<'c>,
     ) -> Self {
+        context.updated_components += 1;
         Self {
             node_id,
             children,
             context,
             component_context_stack,
+            has_changes: false,
         }
     }
 
+    /// Marks the component as changed, so that it's re-rendered even if it's memoized and its
+    /// properties are unchanged. This is intended for hooks which detect changes during
+    /// [`Hook::pre_component_update`](crate::Hook::pre_component_update), such as changes to a
+    /// context.
+    pub fn mark_changed(&mut self) {
+        self.has_changes = true;
+    }
+
+    pub(crate) fn has_changes(&self) -> bool {
+        self.has_changes
+    }
+
     /// Puts the terminal into raw mode if it isn't already, and returns a stream of terminal
     /// events.
     pub fn terminal_events(&mut self) -> Option<TerminalEvents> {
@@ -79,12 +105,12 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
     }
 
     /// Gets an immutable reference to context of the given type.
-    pub fn get_context<T: Any>(&self) -> Option<Ref<T>> {
+    pub fn get_context<T: Any>(&self) -> Option<Ref<'_, T>> {
         self.component_context_stack.get_context()
     }
 
     /// Gets a mutable reference to context of the given type.
-    pub fn get_context_mut<T: Any>(&self) -> Option<RefMut<T>> {
+    pub fn get_context_mut<T: Any>(&self) -> Option<RefMut<'_, T>> {
         self.component_context_stack.get_context_mut()
     }
 
@@ -96,8 +122,100 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
             .expect("we should be able to set the style");
     }
 
-    /// Sets the measure function of the current component, which is invoked to calculate the area
-    /// that the component's content should occupy.
+    /// Sets the intrinsic sizes of the current component, which are resolved to lengths before
+    /// each layout. This must be called after [`set_layout_style`](Self::set_layout_style).
+    pub(crate) fn set_intrinsic_sizes(&mut self, intrinsic_sizes: IntrinsicSizes) {
+        let unresolved_style = (!intrinsic_sizes.is_empty()).then(|| {
+            self.context
+                .layout_engine
+                .style(self.node_id)
+                .expect("we should be able to get the style")
+                .clone()
+        });
+        let cx = self
+            .context
+            .layout_engine
+            .get_node_context_mut(self.node_id)
+            .expect("we should be able to get the node");
+        cx.intrinsic_sizes = intrinsic_sizes;
+        cx.unresolved_style = unresolved_style;
+    }
+
+    /// Sets the measure function of the current component, which is invoked during layout to
+    /// calculate the area that the component's content should occupy. This is how components such
+    /// as [`Text`](crate::components::Text) size themselves to their content.
+    ///
+    /// The function may be called several times per layout, with different constraints. See
+    /// [`MeasureFunc`] for a description of its arguments.
+    ///
+    /// The function is kept until it's replaced, and setting it marks the component's layout as
+    /// dirty, so it should be set on every update to reflect the latest content.
+    ///
+    /// # Example
+    ///
+    /// A sparkline which is as wide as it has values, but can be squeezed down to the most
+    /// recent few:
+    ///
+    /// ```
+    /// # use iocraft::prelude::*;
+    /// use iocraft::taffy::{AvailableSpace, Size};
+    /// // `Box` is the component in the prelude, so the standard one is named in full.
+    /// use std::boxed::Box as StdBox;
+    ///
+    /// #[derive(Default, Props)]
+    /// struct SparklineProps {
+    ///     values: Vec<u8>,
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct Sparkline {
+    ///     values: Vec<u8>,
+    /// }
+    ///
+    /// impl Component for Sparkline {
+    ///     type Props<'a> = SparklineProps;
+    ///
+    ///     fn new(_props: &Self::Props<'_>) -> Self {
+    ///         Self::default()
+    ///     }
+    ///
+    ///     fn update(
+    ///         &mut self,
+    ///         props: &mut Self::Props<'_>,
+    ///         _hooks: Hooks,
+    ///         updater: &mut ComponentUpdater,
+    ///     ) {
+    ///         self.values = props.values.clone();
+    ///         let len = self.values.len() as f32;
+    ///         updater.set_measure_func(StdBox::new(move |known_size, available_space, _style| {
+    ///             let width = known_size.width.unwrap_or(match available_space.width {
+    ///                 AvailableSpace::Definite(width) => len.min(width).max(4.0),
+    ///                 AvailableSpace::MinContent => 4.0,
+    ///                 AvailableSpace::MaxContent => len,
+    ///             });
+    ///             Size { width, height: 1.0 }
+    ///         }));
+    ///     }
+    ///
+    ///     fn draw(&mut self, drawer: &mut ComponentDrawer<'_>) {
+    ///         const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    ///         let width = drawer.size().width as usize;
+    ///         let start = self.values.len().saturating_sub(width);
+    ///         let line: String = self.values[start..]
+    ///             .iter()
+    ///             .map(|&v| BARS[v as usize * BARS.len() / 256])
+    ///             .collect();
+    ///         drawer.canvas().set_text(0, 0, &line, CanvasTextStyle::default());
+    ///     }
+    /// }
+    ///
+    /// let values = vec![0, 64, 128, 255, 128, 64];
+    /// assert_eq!(element!(Sparkline(values: values.clone())).to_string(), "▁▃▅█▅▃\n");
+    /// assert_eq!(
+    ///     element!(Box(width: 4) { Sparkline(values) }).to_string(),
+    ///     "▅█▅▃\n",
+    /// );
+    /// ```
     pub fn set_measure_func(&mut self, measure_func: MeasureFunc) {
         self.context
             .layout_engine
@@ -110,17 +228,21 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
             .expect("we should be able to mark the node as dirty");
     }
 
-    /// Updates the children of the current component.
+    /// Updates the children of the current component. Any [`Fragment`](crate::components::Fragment)
+    /// children are replaced with their own children.
     pub fn update_children<I, T>(&mut self, children: I, context: Option<Context>)
     where
         I: IntoIterator<Item = T>,
         T: ElementExt,
     {
+        let mut children = children.into_iter().collect::<Vec<_>>();
+        let children = flatten_fragments(&mut children);
         self.component_context_stack
             .with_context(context, |component_context_stack| {
                 let mut used_components = HashMap::with_capacity(self.children.components.len());
 
                 let mut child_node_ids = Vec::new();
+                let mut order = Vec::with_capacity(children.len());
 
                 for mut child in children {
                     let mut component: InstantiatedComponent =
@@ -149,9 +271,15 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
                     component.update(self.context, component_context_stack, child.props_mut());
 
                     let mut child_key = child.key().clone();
+                    if used_components.contains_key(&child_key) {
+                        if let Some(key) = child_key.explicit_value() {
+                            report_duplicate_key(key);
+                        }
+                    }
                     while used_components.contains_key(&child_key) {
                         child_key = ElementKey::new(Uuid::new_v4().as_u128());
                     }
+                    order.push(child_key.clone());
                     used_components.insert(child_key, component);
                 }
 
@@ -167,21 +295,89 @@ impl<'a, 'b, 'c> ComponentUpdater<'a, 'b, 'c> {
                         .expect("we should be able to remove the node");
                 }
                 mem::swap(&mut self.children.components, &mut used_components);
+                self.children.order = order;
             });
     }
 }
 
+type WarningHook = std::boxed::Box<dyn Fn(&str) + Send + Sync>;
+
+static WARNING_HOOK: RwLock<Option<WarningHook>> = RwLock::new(None);
+
+/// Sets the function which is given iocraft's warnings about likely mistakes, such as multiple
+/// sibling elements with the same key. These are only checked for in debug builds.
+///
+/// By default, warnings are printed to stderr, which may garble the output of an interactive
+/// program. A hook can send them elsewhere instead, such as to a log file.
+///
+/// # Example
+///
+/// ```
+/// iocraft::set_warning_hook(|warning| {
+///     // Send the warning to a log file instead.
+/// });
+/// ```
+pub fn set_warning_hook<F>(hook: F)
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    *WARNING_HOOK.write().unwrap() = Some(std::boxed::Box::new(hook));
+}
+
+/// Reports that multiple siblings were given the same explicit key. This panics if the
+/// `panic-on-duplicate-keys` feature is enabled, and otherwise warns in debug builds.
+fn report_duplicate_key(key: &dyn Debug) {
+    let message = format!(
+        "multiple sibling elements were given the key {:?}, so their components may not keep their state across renders. keys must be unique among siblings.",
+        key
+    );
+    if cfg!(feature = "panic-on-duplicate-keys") {
+        panic!("{}", message);
+    } else if cfg!(debug_assertions) {
+        // Only report each duplicate once, since it will likely recur on every render. Programs
+        // with ever-changing keys could make for any number of duplicates though, so the record
+        // of them is reset once it grows large.
+        const MAX_REPORTED: usize = 256;
+        thread_local! {
+            static REPORTED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+        }
+        let is_new = REPORTED.with(|reported| {
+            let mut reported = reported.borrow_mut();
+            if reported.len() >= MAX_REPORTED {
+                reported.clear();
+            }
+            reported.insert(message.clone())
+        });
+        if is_new {
+            match &*WARNING_HOOK.read().unwrap() {
+                Some(hook) => hook(&message),
+                None => eprintln!("warning: {}", message),
+            }
+        }
+    }
+}
+
 struct DrawContext<'a> {
     layout_engine: &'a LayoutEngine,
     canvas: &'a mut Canvas,
+    // The position of the canvas's top left corner relative to the final canvas. This is only
+    // non-zero while drawing the children of a scrolled component.
+    canvas_origin: Point<i32>,
 }
 
 /// Provides information and operations that low level component implementations may need to
 /// utilize during the draw phase.
 pub struct ComponentDrawer<'a> {
     node_id: NodeId,
-    node_position: Point<u16>,
+    // The position of the node relative to the canvas. This may be negative, such as for nodes
+    // with negative margins, in which case the parts outside of the canvas are clipped.
+    node_position: Point<i32>,
     node_size: Size<u16>,
+    children_scroll_offset: Option<Point<u16>>,
+    children_detached_origin: Option<Point<i32>>,
+    detached_children: Option<Canvas>,
+    catch_children_panics: bool,
+    children_panic: Option<ComponentError>,
     context: DrawContext<'a>,
 }
 
@@ -201,15 +397,208 @@ impl<'a> ComponentDrawer<'a> {
     }
 
     /// Gets the position of the component relative to the top left of the canvas.
+    ///
+    /// If the component is inside of a scrolled component, this is where it's drawn after
+    /// scrolling. Coordinates which are scrolled out of the top left of the canvas are clamped to
+    /// zero.
     pub fn canvas_position(&self) -> Point<u16> {
-        self.node_position
+        let origin = self.context.canvas_origin;
+        Point {
+            x: (origin.x + self.node_position.x).max(0) as _,
+            y: (origin.y + self.node_position.y).max(0) as _,
+        }
+    }
+
+    /// Gets the size of the area spanned by the component's children, measured from the
+    /// component's top left corner and including its padding and border. If the children overflow
+    /// the component, this is larger than the component itself.
+    pub fn content_size(&self) -> Size<u16> {
+        let layout = self.layout();
+        let mut size = Size {
+            width: layout.border.left
+                + layout.padding.left
+                + layout.padding.right
+                + layout.border.right,
+            height: layout.border.top
+                + layout.padding.top
+                + layout.padding.bottom
+                + layout.border.bottom,
+        };
+        let children = self
+            .context
+            .layout_engine
+            .children(self.node_id)
+            .expect("we should be able to get the children");
+        for child in children {
+            let child_layout = self
+                .context
+                .layout_engine
+                .layout(child)
+                .expect("we should be able to get the child's layout");
+            size.width = size.width.max(
+                child_layout.location.x
+                    + child_layout.size.width
+                    + layout.padding.right
+                    + layout.border.right,
+            );
+            size.height = size.height.max(
+                child_layout.location.y
+                    + child_layout.size.height
+                    + layout.padding.bottom
+                    + layout.border.bottom,
+            );
+        }
+        Size {
+            width: size.width as _,
+            height: size.height as _,
+        }
+    }
+
+    /// Causes the component's children to be drawn shifted up and to the left by the given
+    /// offset, and clipped to the area inside of the component's border. This can be used to
+    /// implement scrolling.
+    pub fn scroll_children(&mut self, offset: Point<u16>) {
+        self.children_scroll_offset = Some(offset);
+    }
+
+    /// Causes the component's children to be drawn to a separate canvas the size of the component
+    /// rather than in place, as if the component were at the given position on the canvas. The
+    /// canvas can be retrieved with [`take_detached_children`](Self::take_detached_children) once
+    /// the children have been drawn.
+    pub(crate) fn detach_children(&mut self, origin: Point<i32>) {
+        self.children_detached_origin = Some(origin);
+    }
+
+    /// Takes the canvas that the children were drawn to, if they were detached via
+    /// [`detach_children`](Self::detach_children).
+    pub(crate) fn take_detached_children(&mut self) -> Option<Canvas> {
+        self.detached_children.take()
+    }
+
+    /// Causes any panic while drawing the component's children to be caught rather than unwinding
+    /// further. The error can be retrieved with
+    /// [`take_children_panic`](Self::take_children_panic) once the children have been drawn.
+    pub(crate) fn catch_children_panics(&mut self) {
+        self.catch_children_panics = true;
+    }
+
+    /// Takes the error for the panic which was caught while drawing the children, if they were
+    /// drawn via [`catch_children_panics`](Self::catch_children_panics) and panicked.
+    pub(crate) fn take_children_panic(&mut self) -> Option<ComponentError> {
+        self.children_panic.take()
+    }
+
+    /// Draws the given canvas over the component's region of the canvas. Cells which are empty in
+    /// the given canvas are left as they are.
+    pub(crate) fn draw_canvas(&mut self, src: &Canvas) {
+        self.context.canvas.copy_from(
+            src,
+            0,
+            0,
+            (self.node_size.width as usize).min(src.width()),
+            (self.node_size.height as usize).min(src.height()),
+            self.node_position.x as _,
+            self.node_position.y as _,
+        );
+    }
+
+    /// Draws the children of the current node, taking into account any scroll offset set by the
+    /// component.
+    pub(crate) fn draw_children(&mut self, children: &mut Components) {
+        if mem::take(&mut self.catch_children_panics) {
+            let node_id = self.node_id;
+            let node_position = self.node_position;
+            let node_size = self.node_size;
+            if let Err(error) = catch_panic(|| self.draw_children(children)) {
+                // The panic may have left the drawer in the middle of drawing a descendant.
+                self.node_id = node_id;
+                self.node_position = node_position;
+                self.node_size = node_size;
+                self.children_scroll_offset = None;
+                self.children_detached_origin = None;
+                self.detached_children = None;
+                self.children_panic = Some(error);
+            }
+            return;
+        }
+
+        if let Some(origin) = self.children_detached_origin.take() {
+            let mut layer = Canvas::new(self.node_size.width as _, self.node_size.height as _);
+            let mut layer_drawer = ComponentDrawer {
+                node_id: self.node_id,
+                node_position: Point { x: 0, y: 0 },
+                node_size: self.node_size,
+                children_scroll_offset: self.children_scroll_offset.take(),
+                children_detached_origin: None,
+                detached_children: None,
+                catch_children_panics: false,
+                children_panic: None,
+                context: DrawContext {
+                    layout_engine: self.context.layout_engine,
+                    canvas: &mut layer,
+                    canvas_origin: origin,
+                },
+            };
+            layer_drawer.draw_children(children);
+            self.detached_children = Some(layer);
+            return;
+        }
+
+        let Some(offset) = self.children_scroll_offset.take() else {
+            children.draw(self);
+            return;
+        };
+
+        // The children are drawn to a separate canvas, the visible portion of which is then
+        // copied into this one.
+        let layout = self.layout();
+        let content_size = self.content_size();
+        let mut layer = Canvas::new(
+            content_size.width.max(self.node_size.width) as _,
+            content_size.height.max(self.node_size.height) as _,
+        );
+        let mut layer_drawer = ComponentDrawer {
+            node_id: self.node_id,
+            node_position: Point { x: 0, y: 0 },
+            node_size: self.node_size,
+            children_scroll_offset: None,
+            children_detached_origin: None,
+            detached_children: None,
+            catch_children_panics: false,
+            children_panic: None,
+            context: DrawContext {
+                layout_engine: self.context.layout_engine,
+                canvas: &mut layer,
+                canvas_origin: Point {
+                    x: self.context.canvas_origin.x + self.node_position.x - offset.x as i32,
+                    y: self.context.canvas_origin.y + self.node_position.y - offset.y as i32,
+                },
+            },
+        };
+        children.draw(&mut layer_drawer);
+
+        let left = layout.border.left as usize;
+        let top = layout.border.top as usize;
+        let width =
+            (layout.size.width - layout.border.left - layout.border.right).max(0.0) as usize;
+        let height =
+            (layout.size.height - layout.border.top - layout.border.bottom).max(0.0) as usize;
+        self.context.canvas.copy_from(
+            &layer,
+            left + offset.x as usize,
+            top + offset.y as usize,
+            width,
+            height,
+            (self.node_position.x + left as i32) as _,
+            (self.node_position.y + top as i32) as _,
+        );
     }
 
     /// Gets the region of the canvas that the component should be drawn to.
-    pub fn canvas(&mut self) -> CanvasSubviewMut {
-        self.context.canvas.subview_mut(
-            self.node_position.x as usize,
-            self.node_position.y as usize,
+    pub fn canvas(&mut self) -> CanvasSubviewMut<'_> {
+        self.context.canvas.subview_mut_at(
+            self.node_position.x as _,
+            self.node_position.y as _,
             self.node_size.width as usize,
             self.node_size.height as usize,
             true,
@@ -217,19 +606,28 @@ impl<'a> ComponentDrawer<'a> {
     }
 
     /// Prepares to begin drawing a node by moving to the node's position and invoking the given
-    /// closure.
+    /// closure. Nodes which are hidden via [`Display::None`] are skipped, along with their
+    /// descendants.
     pub(crate) fn for_child_node<F>(&mut self, node_id: NodeId, f: F)
     where
         F: FnOnce(&mut Self),
     {
+        let style = self
+            .context
+            .layout_engine
+            .style(node_id)
+            .expect("we should be able to get the style");
+        if style.display == Display::None {
+            return;
+        }
         let old_node_id = self.node_id;
         let old_node_position = self.node_position;
         let old_node_size = self.node_size;
         self.node_id = node_id;
         let layout = self.layout();
         self.node_position = Point {
-            x: self.node_position.x + layout.location.x as u16,
-            y: self.node_position.y + layout.location.y as u16,
+            x: self.node_position.x + layout.location.x as i32,
+            y: self.node_position.y + layout.location.y as i32,
         };
         self.node_size = Size {
             width: layout.size.width as u16,
@@ -242,30 +640,255 @@ impl<'a> ComponentDrawer<'a> {
     }
 }
 
-type MeasureFunc = Box<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>, &Style) -> Size<f32>>;
+/// A function which measures the content of a component, set via
+/// [`ComponentUpdater::set_measure_func`]. It's given:
+///
+/// - The known size. When the width or height has already been determined, such as by the
+///   component's style or by its parent stretching it, it's given here, and the function should
+///   return it as-is.
+/// - The available space, which is the space that the component's parent can offer it along each
+///   axis. This may be a definite number of cells, or
+///   [`MinContent`](taffy::AvailableSpace::MinContent) or
+///   [`MaxContent`](taffy::AvailableSpace::MaxContent) when the parent is determining the
+///   smallest size that the content can take without overflowing, or the size that it would take
+///   if given unlimited space.
+/// - The component's layout style.
+///
+/// It returns the size of the content, in cells. Any padding and borders are added to this by the
+/// layout engine.
+pub type MeasureFunc = Box<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>, &Style) -> Size<f32>>;
 
 #[derive(Default)]
 pub(crate) struct LayoutEngineNodeContext {
     measure_func: Option<MeasureFunc>,
+    intrinsic_sizes: IntrinsicSizes,
+    // For nodes with intrinsic sizes, the style that the component set, before those sizes were
+    // resolved, and the min-content and max-content sizes of the node as of the last measurement.
+    unresolved_style: Option<Style>,
+    content_sizes: Option<(Size<f32>, Size<f32>)>,
 }
 
 pub(crate) type LayoutEngine = TaffyTree<LayoutEngineNodeContext>;
 
-struct Tree<'a> {
+fn compute_layout(
+    layout_engine: &mut LayoutEngine,
+    node_id: NodeId,
+    available_space: Size<AvailableSpace>,
+) {
+    layout_engine
+        .compute_layout_with_measure(
+            node_id,
+            available_space,
+            |known_dimensions, available_space, _node_id, node_context, style| match node_context
+                .and_then(|cx| cx.measure_func.as_ref())
+            {
+                Some(f) => f(known_dimensions, available_space, style),
+                None => Size::ZERO,
+            },
+        )
+        .expect("we should be able to compute the layout");
+}
+
+/// Replaces the intrinsic sizes of the node and its descendants, which taffy doesn't support,
+/// with lengths determined by laying out each node on its own. Descendants are resolved first, so
+/// that their sizes are accounted for when measuring their ancestors.
+///
+/// Nodes are only measured when they've changed since the last layout. Unchanged subtrees are
+/// skipped entirely, since changes to a node mark all of its ancestors as changed too.
+fn resolve_intrinsic_sizes(layout_engine: &mut LayoutEngine, node_id: NodeId) {
+    let is_dirty = layout_engine
+        .dirty(node_id)
+        .expect("we should be able to get the node");
+    if is_dirty {
+        for child_id in layout_engine
+            .children(node_id)
+            .expect("we should be able to get the children")
+        {
+            resolve_intrinsic_sizes(layout_engine, child_id);
+        }
+    }
+
+    // Even if the node itself is unchanged, it's resolved again in case its parent's flex direction
+    // has changed, but that doesn't need a new measurement.
+    let (intrinsic_sizes, mut style, content_sizes) = match layout_engine.get_node_context(node_id)
+    {
+        Some(LayoutEngineNodeContext {
+            intrinsic_sizes,
+            unresolved_style: Some(style),
+            content_sizes,
+            ..
+        }) if !intrinsic_sizes.is_empty() => (
+            *intrinsic_sizes,
+            style.clone(),
+            content_sizes.filter(|_| !is_dirty),
+        ),
+        _ => return,
+    };
+
+    let (min_content, max_content) = match content_sizes {
+        Some(content_sizes) => content_sizes,
+        None => {
+            layout_engine
+                .set_style(node_id, style.clone())
+                .expect("we should be able to set the style");
+            let mut measure = |available_space| {
+                compute_layout(
+                    layout_engine,
+                    node_id,
+                    Size {
+                        width: available_space,
+                        height: available_space,
+                    },
+                );
+                layout_engine
+                    .layout(node_id)
+                    .expect("we should be able to get the layout")
+                    .size
+            };
+            let content_sizes = (
+                measure(AvailableSpace::MinContent),
+                measure(AvailableSpace::MaxContent),
+            );
+            layout_engine
+                .get_node_context_mut(node_id)
+                .expect("we should be able to get the node")
+                .content_sizes = Some(content_sizes);
+            content_sizes
+        }
+    };
+    let resolve = |size: IntrinsicSize, min_content: f32, max_content: f32| match size {
+        IntrinsicSize::Min => Dimension::Length(min_content),
+        IntrinsicSize::Max | IntrinsicSize::Fit => Dimension::Length(max_content),
+    };
+
+    for (size, min_size, max_size, intrinsic_size, min_content, max_content) in [
+        (
+            &mut style.size.width,
+            &mut style.min_size.width,
+            &mut style.max_size.width,
+            &intrinsic_sizes.size.width,
+            min_content.width,
+            max_content.width,
+        ),
+        (
+            &mut style.size.height,
+            &mut style.min_size.height,
+            &mut style.max_size.height,
+            &intrinsic_sizes.size.height,
+            min_content.height,
+            max_content.height,
+        ),
+    ] {
+        match intrinsic_size {
+            // The size is left automatic, but restricted to the range of the content's sizes.
+            Some(IntrinsicSize::Fit) => {
+                if *min_size == Dimension::Auto {
+                    *min_size = Dimension::Length(min_content);
+                }
+                if *max_size == Dimension::Auto {
+                    *max_size = Dimension::Length(max_content);
+                }
+            }
+            Some(intrinsic_size) => *size = resolve(*intrinsic_size, min_content, max_content),
+            None => {}
+        }
+    }
+    if let Some(s) = intrinsic_sizes.min_size.width {
+        style.min_size.width = resolve(s, min_content.width, max_content.width);
+    }
+    if let Some(s) = intrinsic_sizes.min_size.height {
+        style.min_size.height = resolve(s, min_content.height, max_content.height);
+    }
+    if let Some(s) = intrinsic_sizes.max_size.width {
+        style.max_size.width = resolve(s, min_content.width, max_content.width);
+    }
+    if let Some(s) = intrinsic_sizes.max_size.height {
+        style.max_size.height = resolve(s, min_content.height, max_content.height);
+    }
+    if let Some(s) = intrinsic_sizes.flex_basis {
+        let is_row = layout_engine
+            .parent(node_id)
+            .and_then(|parent_id| layout_engine.style(parent_id).ok())
+            .map_or(true, |parent_style| {
+                matches!(
+                    parent_style.flex_direction,
+                    FlexDirection::Row | FlexDirection::RowReverse
+                )
+            });
+        style.flex_basis = if is_row {
+            resolve(s, min_content.width, max_content.width)
+        } else {
+            resolve(s, min_content.height, max_content.height)
+        };
+    }
+    if *layout_engine
+        .style(node_id)
+        .expect("we should be able to get the style")
+        != style
+    {
+        layout_engine
+            .set_style(node_id, style)
+            .expect("we should be able to set the style");
+    }
+}
+
+pub(crate) struct Tree<'a> {
     layout_engine: LayoutEngine,
     wrapper_node_id: NodeId,
     root_component: InstantiatedComponent,
     root_component_props: AnyProps<'a>,
     system_context: SystemContext,
+    system: SystemState,
 }
 
-struct RenderOutput {
-    canvas: Canvas,
-    did_clear_terminal_output: bool,
+/// Statistics about a single render of the tree, useful for profiling.
+///
+/// These can be read from within components via
+/// [`UseFrameStats::use_frame_stats`](crate::hooks::UseFrameStats::use_frame_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// The number of frames rendered before this one.
+    pub frame: u64,
+    /// The time spent updating components.
+    pub update_duration: Duration,
+    /// The time spent computing the layout.
+    pub layout_duration: Duration,
+    /// The time spent drawing components to the canvas.
+    pub draw_duration: Duration,
+    /// The number of nodes in the layout tree.
+    pub node_count: usize,
+    /// The number of components which were updated.
+    pub updated_component_count: usize,
+}
+
+impl FrameStats {
+    /// Returns the total time spent rendering the frame.
+    pub fn total_duration(&self) -> Duration {
+        self.update_duration + self.layout_duration + self.draw_duration
+    }
+}
+
+/// Options for [`ElementExt::render_loop_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderLoopOptions {
+    /// Whether to render to the terminal's alternate screen, like
+    /// [`fullscreen`](ElementExt::fullscreen).
+    pub fullscreen: bool,
+
+    /// Whether the output is a TTY terminal, or `None` to detect it. Output which isn't a terminal
+    /// is given the element once as plain text instead of running the loop. Setting this is useful
+    /// for outputs which behave like terminals without being detected as such, or for always
+    /// getting either escape sequences or plain text.
+    pub tty: Option<bool>,
+}
+
+pub(crate) struct RenderOutput {
+    pub canvas: Canvas,
+    pub did_clear_terminal_output: bool,
 }
 
 impl<'a> Tree<'a> {
-    fn new(mut props: AnyProps<'a>, helper: Box<dyn ComponentHelperExt>) -> Self {
+    pub fn new(mut props: AnyProps<'a>, helper: Box<dyn ComponentHelperExt>) -> Self {
         let mut layout_engine = TaffyTree::new();
         let root_node_id = layout_engine
             .new_leaf_with_context(Style::default(), LayoutEngineNodeContext::default())
@@ -279,46 +902,84 @@ impl<'a> Tree<'a> {
             root_component: InstantiatedComponent::new(root_node_id, props.borrow(), helper),
             root_component_props: props,
             system_context: SystemContext::new(),
+            system: SystemState::default(),
         }
     }
 
-    fn render(
+    #[cfg(any(test, feature = "testing"))]
+    pub fn system_context_mut(&mut self) -> &mut SystemContext {
+        &mut self.system_context
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn system_mut(&mut self) -> &mut SystemState {
+        &mut self.system
+    }
+
+    /// Waits for any component in the tree to change.
+    #[cfg(any(test, feature = "testing"))]
+    pub async fn wait(&mut self) {
+        self.root_component.wait().await
+    }
+
+    pub fn render(
         &mut self,
         max_width: Option<usize>,
         terminal: Option<&mut Terminal>,
     ) -> RenderOutput {
-        let did_clear_terminal_output = {
+        let update_start = Instant::now();
+        let (did_clear_terminal_output, updated_component_count) = {
             let mut context = UpdateContext {
                 terminal,
                 layout_engine: &mut self.layout_engine,
                 did_clear_terminal_output: false,
+                updated_components: 0,
             };
-            let mut component_context_stack = ContextStack::root(&mut self.system_context);
+            let mut component_context_stack =
+                ContextStack::root(&mut self.system_context, &self.system);
             self.root_component.update(
                 &mut context,
                 &mut component_context_stack,
                 self.root_component_props.borrow(),
             );
-            context.did_clear_terminal_output
+            (
+                context.did_clear_terminal_output,
+                context.updated_components,
+            )
         };
+        let update_duration = update_start.elapsed();
 
+        // Constrain the root to the maximum width so that content such as text wraps to fit.
         self.layout_engine
-            .compute_layout_with_measure(
+            .set_style(
                 self.wrapper_node_id,
-                Size {
-                    width: max_width
-                        .map(|w| AvailableSpace::Definite(w as _))
-                        .unwrap_or(AvailableSpace::MaxContent),
-                    height: AvailableSpace::MaxContent,
-                },
-                |known_dimensions, available_space, _node_id, node_context, style| {
-                    match node_context.and_then(|cx| cx.measure_func.as_ref()) {
-                        Some(f) => f(known_dimensions, available_space, style),
-                        None => Size::ZERO,
-                    }
+                Style {
+                    max_size: Size {
+                        width: max_width
+                            .map(|w| Dimension::Length(w as _))
+                            .unwrap_or(Dimension::Auto),
+                        height: Dimension::Auto,
+                    },
+                    ..Default::default()
                 },
             )
-            .expect("we should be able to compute the layout");
+            .expect("we should be able to set the wrapper style");
+
+        let layout_start = Instant::now();
+        resolve_intrinsic_sizes(&mut self.layout_engine, self.wrapper_node_id);
+        compute_layout(
+            &mut self.layout_engine,
+            self.wrapper_node_id,
+            Size {
+                width: max_width
+                    .map(|w| AvailableSpace::Definite(w as _))
+                    .unwrap_or(AvailableSpace::MaxContent),
+                height: AvailableSpace::MaxContent,
+            },
+        );
+        let layout_duration = layout_start.elapsed();
+
+        let draw_start = Instant::now();
 
         let wrapper_layout = self
             .layout_engine
@@ -342,12 +1003,39 @@ impl<'a> Tree<'a> {
                 width: root_layout.size.width as _,
                 height: root_layout.size.height as _,
             },
+            children_scroll_offset: None,
+            children_detached_origin: None,
+            detached_children: None,
+            catch_children_panics: false,
+            children_panic: None,
             context: DrawContext {
                 layout_engine: &self.layout_engine,
                 canvas: &mut canvas,
+                canvas_origin: Point { x: 0, y: 0 },
             },
         };
         self.root_component.draw(&mut drawer);
+        if !self.system_context.is_blink_enabled() {
+            canvas.disable_blink();
+        }
+        let draw_duration = draw_start.elapsed();
+
+        // The stats are made available to components during the next render.
+        let frame = self
+            .system_context
+            .frame_stats()
+            .map(|stats| stats.frame + 1)
+            .unwrap_or(0);
+        self.system_context.frame_stats = Some(FrameStats {
+            frame,
+            update_duration,
+            layout_duration,
+            draw_duration,
+            node_count: self.layout_engine.total_node_count(),
+            updated_component_count,
+        });
+        self.system.frame_stats = self.system_context.frame_stats;
+
         RenderOutput {
             canvas,
             did_clear_terminal_output,
@@ -360,10 +1048,16 @@ impl<'a> Tree<'a> {
             let width = term.width().map(|w| w as usize);
             execute!(term, terminal::BeginSynchronizedUpdate,)?;
             let output = self.render(width, Some(&mut term));
-            if output.did_clear_terminal_output || prev_canvas.as_ref() != Some(&output.canvas) {
-                if !output.did_clear_terminal_output {
-                    term.clear_canvas()?;
-                }
+            // After a resize, the terminal may have re-wrapped or scrolled the previous output, so
+            // it's cleared and written again from scratch.
+            let resized = term.take_resized();
+            if resized {
+                term.clear_canvas()?;
+            }
+            if resized
+                || output.did_clear_terminal_output
+                || prev_canvas.as_ref() != Some(&output.canvas)
+            {
                 term.write_canvas(&output.canvas)?;
             }
             prev_canvas = Some(output.canvas);
@@ -399,6 +1093,28 @@ where
     tree.terminal_render_loop(term).await
 }
 
+/// Renders the element in a loop on the given output, or just once as plain text if it isn't a
+/// terminal, since there's no way to update the output or receive input.
+pub(crate) async fn render_loop<E, F>(
+    mut e: E,
+    options: RenderLoopOptions,
+    output: F,
+) -> io::Result<()>
+where
+    E: ElementExt,
+    F: Write + AsRawFd + Send + 'static,
+{
+    if !options.tty.unwrap_or_else(|| output.is_tty()) {
+        return e.write(output);
+    }
+    let term = if options.fullscreen {
+        Terminal::fullscreen(output)?
+    } else {
+        Terminal::new(output)?
+    };
+    terminal_render_loop(e, term).await
+}
+
 #[cfg(test)]
 pub(crate) async fn mock_terminal_render_loop<E>(e: E) -> io::Result<Vec<Canvas>>
 where
@@ -469,4 +1185,120 @@ mod tests {
         ];
         assert_eq!(actual, expected);
     }
+
+    #[component]
+    fn WaitForInput(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        hooks.use_terminal_events(|_| {});
+        element!(Text(content: "waiting"))
+    }
+
+    #[component]
+    fn ExitImmediately(hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        hooks.use_context_mut::<SystemContext>().exit();
+        element!(Text(content: "done"))
+    }
+
+    #[apply(test!)]
+    async fn test_render_loop_without_tty() {
+        let path = std::env::temp_dir().join(format!(
+            "iocraft-test-render-loop-without-tty-{}",
+            std::process::id()
+        ));
+
+        // Without a terminal, the element is rendered once rather than waiting for input forever.
+        let file = std::fs::File::create(&path).unwrap();
+        let options = RenderLoopOptions {
+            tty: Some(false),
+            ..Default::default()
+        };
+        element!(WaitForInput)
+            .render_loop_with(file, options)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "waiting\n");
+
+        // Unless the output is treated as a terminal regardless, in which case the loop runs and
+        // writes escape codes.
+        let file = std::fs::File::create(&path).unwrap();
+        let options = RenderLoopOptions {
+            tty: Some(true),
+            ..Default::default()
+        };
+        element!(ExitImmediately)
+            .render_loop_with(file, options)
+            .await
+            .unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(output.contains("done"));
+        assert!(output.starts_with('\x1b'));
+    }
+
+    #[apply(test!)]
+    async fn test_render_loop_to_file() {
+        // Files aren't terminals, so the element is written to them once as plain text.
+        let path = std::env::temp_dir().join(format!(
+            "iocraft-test-render-loop-to-{}",
+            std::process::id()
+        ));
+        let file = std::fs::File::create(&path).unwrap();
+        element!(WaitForInput)
+            .render_loop_to(file.try_clone().unwrap())
+            .await
+            .unwrap();
+        element!(WaitForInput).fullscreen_to(file).await.unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(output, "waiting\nwaiting\n");
+    }
+
+    #[derive(Default, Props)]
+    struct DuplicateKeysProps {
+        labels: Vec<&'static str>,
+    }
+
+    #[component]
+    fn DuplicateKeys(props: &DuplicateKeysProps) -> impl Into<AnyElement<'static>> {
+        element! {
+            Box(flex_direction: FlexDirection::Column) {
+                #(props.labels.iter().map(|label| element! {
+                    MyInnerComponent(key: "duplicate", label: *label)
+                }))
+            }
+        }
+    }
+
+    #[cfg(not(feature = "panic-on-duplicate-keys"))]
+    #[test]
+    fn test_duplicate_keys() {
+        use std::sync::Mutex;
+
+        static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        set_warning_hook(|warning| WARNINGS.lock().unwrap().push(warning.to_string()));
+
+        // Duplicate keys are reported, but all of the elements are still rendered.
+        assert_eq!(
+            element!(DuplicateKeys(labels: vec!["a", "b"])).to_string(),
+            "render count (a): 1\nrender count (b): 1\n"
+        );
+        let warnings = WARNINGS.lock().unwrap().clone();
+        if cfg!(debug_assertions) {
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0]
+                .starts_with("multiple sibling elements were given the key \"duplicate\""));
+        } else {
+            assert!(warnings.is_empty());
+        }
+
+        // Each duplicate is only reported once.
+        element!(DuplicateKeys(labels: vec!["a", "b"])).to_string();
+        assert_eq!(WARNINGS.lock().unwrap().len(), warnings.len());
+    }
+
+    #[cfg(feature = "panic-on-duplicate-keys")]
+    #[test]
+    #[should_panic(expected = "multiple sibling elements were given the key \"duplicate\"")]
+    fn test_duplicate_keys() {
+        element!(DuplicateKeys(labels: vec!["a", "b"])).to_string();
+    }
 }
@@ -1,23 +1,116 @@
 use bitflags::bitflags;
 use iocraft_macros::with_layout_style_props;
+use std::ops::{Add, Div, Mul, Sub};
 use taffy::{
     geometry,
-    style::{Dimension, LengthPercentage, LengthPercentageAuto},
-    Rect, Style,
+    style::{
+        Dimension, GridTrackRepetition, LengthPercentage, LengthPercentageAuto,
+        NonRepeatedTrackSizingFunction, TrackSizingFunction,
+    },
+    style_helpers, Line, Rect, Style,
 };
 
 // Re-export basic enum types.
-pub use crossterm::style::Color;
+pub use crossterm::style::{Attribute, Color, ContentStyle};
 pub use taffy::style::{
-    AlignContent, AlignItems, Display, FlexDirection, FlexWrap, JustifyContent, Overflow,
+    AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, JustifyContent,
+    JustifyItems, Overflow,
 };
 
 /// Defines a type that represents a percentage [0.0-100.0] and is convertible to any of the
 /// libary's other percent types. As a shorthand, you can express this in the
 /// [`element!`](crate::element!) macro using the `pct` suffix, e.g. `50pct`.
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 pub struct Percent(pub f32);
 
+impl Add for Percent {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Percent(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Percent {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Percent(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f32> for Percent {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self {
+        Percent(self.0 * rhs)
+    }
+}
+
+impl Div<f32> for Percent {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self {
+        Percent(self.0 / rhs)
+    }
+}
+
+/// Defines a type that represents an automatically selected value and is convertible to any of
+/// the library's types which support it, such as [`Size`], [`Margin`], and [`FlexBasis`], so it
+/// can be used directly as a prop value, e.g. `margin: Auto`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Auto;
+
+/// Defines a type that represents the smallest size an element can take without its content
+/// overflowing, such as the width of the longest word of some text. It's convertible to
+/// [`Size`] and [`FlexBasis`], so it can be used directly as a prop value, e.g.
+/// `width: MinContent`.
+///
+/// See [the MDN documentation for min-content](https://developer.mozilla.org/en-US/docs/Web/CSS/min-content).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MinContent;
+
+/// Defines a type that represents the size an element would take if given unlimited space, such
+/// as the width of some text without any wrapping. It's convertible to [`Size`] and
+/// [`FlexBasis`], so it can be used directly as a prop value, e.g. `width: MaxContent`.
+///
+/// See [the MDN documentation for max-content](https://developer.mozilla.org/en-US/docs/Web/CSS/max-content).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MaxContent;
+
+/// Defines a type that represents the available space, but no less than the
+/// [`MinContent`] size and no more than the [`MaxContent`] size. It's convertible to [`Size`] and
+/// [`FlexBasis`], so it can be used directly as a prop value, e.g. `width: FitContent`.
+///
+/// See [the MDN documentation for fit-content](https://developer.mozilla.org/en-US/docs/Web/CSS/fit-content).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FitContent;
+
+/// A size which depends on an element's content. Taffy doesn't support these directly, so they're
+/// resolved to lengths by measuring the element before each layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum IntrinsicSize {
+    Min,
+    Max,
+    Fit,
+}
+
+/// The intrinsic sizes used by a [`LayoutStyle`], in place of which the converted taffy style has
+/// `auto` values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct IntrinsicSizes {
+    pub size: geometry::Size<Option<IntrinsicSize>>,
+    pub min_size: geometry::Size<Option<IntrinsicSize>>,
+    pub max_size: geometry::Size<Option<IntrinsicSize>>,
+    pub flex_basis: Option<IntrinsicSize>,
+}
+
+impl IntrinsicSizes {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Defines the area to reserve around the element's content, but inside the border.
 ///
 /// See [the MDN documentation for padding](https://developer.mozilla.org/en-US/docs/Web/CSS/padding).
@@ -33,6 +126,17 @@ pub enum Padding {
 }
 
 impl Padding {
+    /// Constructs an absolute padding, in cells.
+    pub const fn length(length: u32) -> Self {
+        Padding::Length(length)
+    }
+
+    /// Constructs a padding which is the given percentage of the width of the parent, such as
+    /// `50.0` for half.
+    pub const fn percent(percent: f32) -> Self {
+        Padding::Percent(percent)
+    }
+
     fn or(self, other: Self) -> Self {
         match self {
             Padding::Unset => other,
@@ -70,7 +174,7 @@ macro_rules! impl_from_length {
         }
         impl From<u32> for $name {
             fn from(l: u32) -> Self {
-                $name::Length(l)
+                $name::Length(l as _)
             }
         }
     };
@@ -90,7 +194,7 @@ impl_from_length!(Padding);
 impl_from_percent!(Padding);
 
 macro_rules! new_size_type {
-    ($(#[$m:meta])* $name:ident, $def:expr) => {
+    ($(#[$m:meta])* $name:ident, $length:ty, $def:expr $(, $(#[$vm:meta])* $variant:ident)* $(,)?) => {
         $(#[$m])*
         #[derive(Clone, Copy, Debug, Default, PartialEq)]
         pub enum $name {
@@ -100,12 +204,29 @@ macro_rules! new_size_type {
             /// Automatically selects a suitable size.
             Auto,
             /// Sets an absolute value.
-            Length(u32),
+            Length($length),
             /// Sets a percentage of the width or height of the parent.
             Percent(f32),
+            $($(#[$vm])* $variant,)*
         }
 
         impl $name {
+            /// Constructs an absolute value, in cells.
+            pub const fn length(length: $length) -> Self {
+                $name::Length(length)
+            }
+
+            /// Constructs a value which is the given percentage of the width or height of the
+            /// parent, such as `50.0` for half.
+            pub const fn percent(percent: f32) -> Self {
+                $name::Percent(percent)
+            }
+
+            /// Constructs a value which is selected automatically.
+            pub const fn auto() -> Self {
+                $name::Auto
+            }
+
             #[allow(dead_code)]
             fn or<T: Into<Self>>(self, other: T) -> Self {
                 match self {
@@ -113,6 +234,54 @@ macro_rules! new_size_type {
                     _ => self,
                 }
             }
+
+            /// Returns the absolute value, if this is a length.
+            pub fn as_length(self) -> Option<$length> {
+                match self {
+                    $name::Length(l) => Some(l),
+                    _ => None,
+                }
+            }
+
+            /// Returns the percentage, if this is a percentage.
+            pub fn as_percent(self) -> Option<Percent> {
+                match self {
+                    $name::Percent(p) => Some(Percent(p)),
+                    _ => None,
+                }
+            }
+
+            /// Restricts the value to the given bounds. As in CSS, if the minimum is greater than
+            /// the maximum, the minimum takes precedence.
+            ///
+            /// Lengths are only restricted by bounds which are also lengths, and percentages are
+            /// only restricted by bounds which are also percentages, since other kinds of values
+            /// can't be compared without knowing the size of the parent. Any other bounds are
+            /// ignored.
+            pub fn clamp<Min: Into<Self>, Max: Into<Self>>(self, min: Min, max: Max) -> Self {
+                let (min, max) = (min.into(), max.into());
+                match self {
+                    $name::Length(mut l) => {
+                        if let Some(max) = max.as_length() {
+                            l = l.min(max);
+                        }
+                        if let Some(min) = min.as_length() {
+                            l = l.max(min);
+                        }
+                        $name::Length(l)
+                    }
+                    $name::Percent(mut p) => {
+                        if let Some(max) = max.as_percent() {
+                            p = p.min(max.0);
+                        }
+                        if let Some(min) = min.as_percent() {
+                            p = p.max(min.0);
+                        }
+                        $name::Percent(p)
+                    }
+                    _ => self,
+                }
+            }
         }
 
         impl From<$name> for LengthPercentageAuto {
@@ -122,6 +291,7 @@ macro_rules! new_size_type {
                     $name::Auto => LengthPercentageAuto::Auto,
                     $name::Length(l) => LengthPercentageAuto::Length(l as _),
                     $name::Percent(p) => LengthPercentageAuto::Percent(p / 100.0),
+                    $($name::$variant => LengthPercentageAuto::Auto,)*
                 }
             }
         }
@@ -133,10 +303,26 @@ macro_rules! new_size_type {
                     $name::Auto => Dimension::Auto,
                     $name::Length(l) => Dimension::Length(l as _),
                     $name::Percent(p) => Dimension::Percent(p / 100.0),
+                    // These are resolved to lengths during layout.
+                    $($name::$variant => Dimension::Auto,)*
                 }
             }
         }
 
+        impl From<Auto> for $name {
+            fn from(_: Auto) -> Self {
+                $name::Auto
+            }
+        }
+
+        $(
+            impl From<$variant> for $name {
+                fn from(_: $variant) -> Self {
+                    $name::$variant
+                }
+            }
+        )*
+
         impl_from_length!($name);
         impl_from_percent!($name);
     };
@@ -145,37 +331,355 @@ macro_rules! new_size_type {
 new_size_type!(
     /// Defines the area to reserve around the element's content, but outside the border.
     ///
+    /// Unlike other sizes, lengths and percentages may be negative, which pulls the element and
+    /// the elements after it towards that edge, overlapping whatever is there.
+    ///
     /// See [the MDN documentation for margin](https://developer.mozilla.org/en-US/docs/Web/CSS/margin).
     Margin,
+    i32,
     Margin::Length(0)
 );
 
 new_size_type!(
     /// Defines a width or height of an element.
     Size,
-    Size::Auto
+    u32,
+    Size::Auto,
+    /// Sets the [`MinContent`] size of the element.
+    MinContent,
+    /// Sets the [`MaxContent`] size of the element.
+    MaxContent,
+    /// Sets the [`FitContent`] size of the element. When used as a minimum or maximum, this is the
+    /// same as [`MaxContent`](Size::MaxContent).
+    FitContent,
 );
 
+impl Size {
+    fn intrinsic(self) -> Option<IntrinsicSize> {
+        match self {
+            Size::MinContent => Some(IntrinsicSize::Min),
+            Size::MaxContent => Some(IntrinsicSize::Max),
+            Size::FitContent => Some(IntrinsicSize::Fit),
+            _ => None,
+        }
+    }
+}
+
 /// Sets the initial main size of a flex item.
 ///
 /// See [the MDN documentation for flex-basis](https://developer.mozilla.org/en-US/docs/Web/CSS/flex-basis).
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum FlexBasis {
-    /// Uses the value of the `width` or `height` property, or the content size if not set.
+    /// The default behavior, which is the same as [`Auto`](FlexBasis::Auto).
     #[default]
+    Unset,
+    /// Uses the value of the `width` or `height` property, or the content size if not set.
     Auto,
     /// Sets an absolute value.
     Length(u32),
     /// Sets a percentage of the width or height of the parent.
     Percent(f32),
+    /// Uses the [`MinContent`] size of the element.
+    MinContent,
+    /// Uses the [`MaxContent`] size of the element.
+    MaxContent,
+    /// Uses the [`FitContent`] size of the element, which for a flex basis is the same as
+    /// [`MaxContent`](FlexBasis::MaxContent).
+    FitContent,
+}
+
+impl FlexBasis {
+    /// Constructs an absolute flex basis, in cells.
+    pub const fn length(length: u32) -> Self {
+        FlexBasis::Length(length)
+    }
+
+    /// Constructs a flex basis which is the given percentage of the width or height of the
+    /// parent, such as `50.0` for half.
+    pub const fn percent(percent: f32) -> Self {
+        FlexBasis::Percent(percent)
+    }
+
+    /// Constructs a flex basis which uses the value of the `width` or `height` property, or the
+    /// content size if not set.
+    pub const fn auto() -> Self {
+        FlexBasis::Auto
+    }
+
+    fn intrinsic(self) -> Option<IntrinsicSize> {
+        match self {
+            FlexBasis::MinContent => Some(IntrinsicSize::Min),
+            FlexBasis::MaxContent => Some(IntrinsicSize::Max),
+            FlexBasis::FitContent => Some(IntrinsicSize::Fit),
+            _ => None,
+        }
+    }
+}
+
+impl From<Auto> for FlexBasis {
+    fn from(_: Auto) -> Self {
+        FlexBasis::Auto
+    }
+}
+
+impl From<MinContent> for FlexBasis {
+    fn from(_: MinContent) -> Self {
+        FlexBasis::MinContent
+    }
+}
+
+impl From<MaxContent> for FlexBasis {
+    fn from(_: MaxContent) -> Self {
+        FlexBasis::MaxContent
+    }
+}
+
+impl From<FitContent> for FlexBasis {
+    fn from(_: FitContent) -> Self {
+        FlexBasis::FitContent
+    }
 }
 
 impl From<FlexBasis> for Dimension {
     fn from(b: FlexBasis) -> Self {
         match b {
-            FlexBasis::Auto => Dimension::Auto,
+            FlexBasis::Unset | FlexBasis::Auto => Dimension::Auto,
             FlexBasis::Length(l) => Dimension::Length(l as _),
             FlexBasis::Percent(p) => Dimension::Percent(p / 100.0),
+            // These are resolved to lengths during layout.
+            FlexBasis::MinContent | FlexBasis::MaxContent | FlexBasis::FitContent => {
+                Dimension::Auto
+            }
+        }
+    }
+}
+
+/// Determines whether the width and height of an element include its padding and border.
+///
+/// See [the MDN documentation for box-sizing](https://developer.mozilla.org/en-US/docs/Web/CSS/box-sizing).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoxSizing {
+    /// The width and height include the padding and border, so the content area is what's left
+    /// inside of them. This is the default.
+    #[default]
+    BorderBox,
+    /// The width and height only include the content area, and the padding and border are added
+    /// around it.
+    ///
+    /// Only sizes which are lengths are affected, and only by padding which is a length. Sizes
+    /// and padding which are percentages are applied as if the element were border-box.
+    ContentBox,
+}
+
+impl BoxSizing {
+    /// Grows the style's lengths by the lengths of the given edges, such as the padding or the
+    /// border, if they're meant to exclude them. Taffy always treats sizes as border-box sizes.
+    pub(crate) fn adjust_sizes(self, style: &mut Style, edges: Rect<LengthPercentage>) {
+        if self == BoxSizing::BorderBox {
+            return;
+        }
+        let length = |edge: LengthPercentage| match edge {
+            LengthPercentage::Length(l) => l,
+            LengthPercentage::Percent(_) => 0.0,
+        };
+        let width = length(edges.left) + length(edges.right);
+        let height = length(edges.top) + length(edges.bottom);
+        for (dimension, extra) in [
+            (&mut style.size.width, width),
+            (&mut style.size.height, height),
+            (&mut style.min_size.width, width),
+            (&mut style.min_size.height, height),
+            (&mut style.max_size.width, width),
+            (&mut style.max_size.height, height),
+        ] {
+            if let Dimension::Length(l) = dimension {
+                *l += extra;
+            }
+        }
+    }
+}
+
+/// Defines the size of a track, i.e. a column or a row, of a grid.
+///
+/// See [the MDN documentation for grid-template-columns](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns).
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridTrack {
+    /// Sizes the track to fit its items, stretching it into any space left over.
+    Auto,
+    /// Sets an absolute size.
+    Length(u32),
+    /// Sets a percentage of the width or height of the grid.
+    Percent(f32),
+    /// Sets a share of the space left over once the other tracks are sized, like the `fr` unit in
+    /// CSS. The space is divided between such tracks in proportion to their fractions.
+    Fraction(f32),
+    /// Sizes the track to the largest [`MinContent`] size of its items.
+    MinContent,
+    /// Sizes the track to the largest [`MaxContent`] size of its items.
+    MaxContent,
+    /// Repeats the given tracks the given number of times.
+    Repeat(u16, Vec<GridTrack>),
+}
+
+impl GridTrack {
+    /// Constructs an absolute track size, in cells.
+    pub const fn length(length: u32) -> Self {
+        GridTrack::Length(length)
+    }
+
+    /// Constructs a track size which is the given percentage of the width or height of the grid,
+    /// such as `50.0` for half.
+    pub const fn percent(percent: f32) -> Self {
+        GridTrack::Percent(percent)
+    }
+
+    /// Constructs a track size which is the given share of the space left over, such as `1.0`
+    /// for `1fr`.
+    pub const fn fr(fraction: f32) -> Self {
+        GridTrack::Fraction(fraction)
+    }
+
+    /// Constructs a track size which fits the track's items.
+    pub const fn auto() -> Self {
+        GridTrack::Auto
+    }
+
+    /// Constructs a sequence of tracks, repeated `count` times.
+    pub fn repeat<T: Into<GridTrack>>(count: u16, tracks: impl IntoIterator<Item = T>) -> Self {
+        GridTrack::Repeat(count, tracks.into_iter().map(Into::into).collect())
+    }
+
+    /// Converts the tracks to taffy's representation. Taffy doesn't support nested repetitions,
+    /// so those are expanded.
+    pub(crate) fn to_taffy(tracks: &[GridTrack]) -> Vec<TrackSizingFunction> {
+        tracks
+            .iter()
+            .map(|track| match track {
+                GridTrack::Repeat(count, tracks) => TrackSizingFunction::Repeat(
+                    GridTrackRepetition::Count(*count),
+                    Self::to_taffy_non_repeated(tracks),
+                ),
+                track => TrackSizingFunction::Single(track.to_taffy_single()),
+            })
+            .collect()
+    }
+
+    fn to_taffy_non_repeated(tracks: &[GridTrack]) -> Vec<NonRepeatedTrackSizingFunction> {
+        let mut result = Vec::new();
+        for track in tracks {
+            match track {
+                GridTrack::Repeat(count, tracks) => {
+                    let tracks = Self::to_taffy_non_repeated(tracks);
+                    for _ in 0..*count {
+                        result.extend(tracks.iter().copied());
+                    }
+                }
+                track => result.push(track.to_taffy_single()),
+            }
+        }
+        result
+    }
+
+    fn to_taffy_single(&self) -> NonRepeatedTrackSizingFunction {
+        match self {
+            GridTrack::Auto => style_helpers::auto(),
+            GridTrack::Length(l) => style_helpers::length(*l as f32),
+            GridTrack::Percent(p) => style_helpers::percent(*p / 100.0),
+            GridTrack::Fraction(f) => style_helpers::fr(*f),
+            GridTrack::MinContent => style_helpers::min_content(),
+            GridTrack::MaxContent => style_helpers::max_content(),
+            GridTrack::Repeat(..) => unreachable!("repetitions are expanded by the caller"),
+        }
+    }
+}
+
+impl_from_length!(GridTrack);
+impl_from_percent!(GridTrack);
+
+impl From<Auto> for GridTrack {
+    fn from(_: Auto) -> Self {
+        GridTrack::Auto
+    }
+}
+
+impl From<MinContent> for GridTrack {
+    fn from(_: MinContent) -> Self {
+        GridTrack::MinContent
+    }
+}
+
+impl From<MaxContent> for GridTrack {
+    fn from(_: MaxContent) -> Self {
+        GridTrack::MaxContent
+    }
+}
+
+/// Places an item within the columns or rows of its grid.
+///
+/// Grid lines are numbered from 1, so the first column or row lies between lines 1 and 2.
+/// Negative line numbers count back from the end of the grid, so -1 is the last line.
+///
+/// See [the MDN documentation for grid-column](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-column).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GridPlacement {
+    /// The default behavior, which is to place the item automatically.
+    #[default]
+    Unset,
+    /// Places the item automatically.
+    Auto,
+    /// Places the item in the single track following the given line.
+    Line(i16),
+    /// Places the item in the tracks between the given start and end lines.
+    Lines(i16, i16),
+    /// Places the item automatically, spanning the given number of tracks.
+    Span(u16),
+}
+
+impl GridPlacement {
+    /// Constructs a placement in the single track following the given line.
+    pub const fn line(line: i16) -> Self {
+        GridPlacement::Line(line)
+    }
+
+    /// Constructs a placement in the tracks between the given start and end lines.
+    pub const fn lines(start: i16, end: i16) -> Self {
+        GridPlacement::Lines(start, end)
+    }
+
+    /// Constructs an automatic placement, spanning the given number of tracks.
+    pub const fn span(span: u16) -> Self {
+        GridPlacement::Span(span)
+    }
+}
+
+impl From<i16> for GridPlacement {
+    fn from(line: i16) -> Self {
+        GridPlacement::Line(line)
+    }
+}
+
+impl From<i32> for GridPlacement {
+    fn from(line: i32) -> Self {
+        GridPlacement::Line(line as _)
+    }
+}
+
+impl From<Auto> for GridPlacement {
+    fn from(_: Auto) -> Self {
+        GridPlacement::Auto
+    }
+}
+
+impl From<GridPlacement> for Line<taffy::GridPlacement> {
+    fn from(p: GridPlacement) -> Self {
+        match p {
+            GridPlacement::Unset | GridPlacement::Auto => style_helpers::auto(),
+            GridPlacement::Line(line) => style_helpers::line(line),
+            GridPlacement::Lines(start, end) => Line {
+                start: style_helpers::line(start),
+                end: style_helpers::line(end),
+            },
+            GridPlacement::Span(span) => style_helpers::span(span),
         }
     }
 }
@@ -188,7 +692,8 @@ pub enum Weight {
     Normal,
     /// The bold weight.
     Bold,
-    /// The light weight.
+    /// The light weight, also known as dim or faint. Not all terminals support this, in which
+    /// case the text is displayed with the normal weight.
     Light,
 }
 
@@ -207,16 +712,51 @@ bitflags! {
     }
 }
 
-#[doc(hidden)]
-#[with_layout_style_props]
+/// The layout-related properties of an element, such as its size, padding, and flexbox or grid
+/// behavior.
+///
+/// Components which accept layout properties typically convert them to a `LayoutStyle` via their
+/// `layout_style` method. Low level components can also build one programmatically:
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// let style = LayoutStyle::default()
+///     .flex_direction(FlexDirection::Column)
+///     .width(40)
+///     .padding(1)
+///     .align_items(AlignItems::Center);
+/// assert_eq!(style.width, Size::Length(40));
+/// ```
+#[with_layout_style_props(builder)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct LayoutStyle {
     // fields added by proc macro, defined in ../macros/src/lib.rs
 }
 
+impl LayoutStyle {
+    pub(crate) fn intrinsic_sizes(&self) -> IntrinsicSizes {
+        IntrinsicSizes {
+            size: geometry::Size {
+                width: self.width.intrinsic(),
+                height: self.height.intrinsic(),
+            },
+            min_size: geometry::Size {
+                width: self.min_width.intrinsic(),
+                height: self.min_height.intrinsic(),
+            },
+            max_size: geometry::Size {
+                width: self.max_width.intrinsic(),
+                height: self.max_height.intrinsic(),
+            },
+            flex_basis: self.flex_basis.intrinsic(),
+        }
+    }
+}
+
 impl From<LayoutStyle> for Style {
     fn from(s: LayoutStyle) -> Self {
-        Self {
-            display: s.display,
+        let mut style = Self {
+            display: s.display.unwrap_or_default(),
             size: geometry::Size {
                 width: s.width.into(),
                 height: s.height.into(),
@@ -241,15 +781,119 @@ impl From<LayoutStyle> for Style {
                 top: s.margin_top.or(s.margin).into(),
                 bottom: s.margin_bottom.or(s.margin).into(),
             },
-            flex_direction: s.flex_direction,
-            flex_wrap: s.flex_wrap,
+            flex_direction: s.flex_direction.unwrap_or_default(),
+            flex_wrap: s.flex_wrap.unwrap_or_default(),
             flex_basis: s.flex_basis.into(),
-            flex_grow: s.flex_grow,
+            flex_grow: s.flex_grow.unwrap_or_default(),
             flex_shrink: s.flex_shrink.unwrap_or(1.0),
             align_items: s.align_items,
             align_content: s.align_content,
+            align_self: s.align_self,
+            justify_items: s.justify_items,
             justify_content: s.justify_content,
+            grid_column: s.grid_column.into(),
+            grid_row: s.grid_row.into(),
             ..Default::default()
-        }
+        };
+        let padding = style.padding;
+        s.box_sizing
+            .unwrap_or_default()
+            .adjust_sizes(&mut style, padding);
+        style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_arithmetic() {
+        assert_eq!(Percent(50.0) * 0.5, Percent(25.0));
+        assert_eq!(Percent(50.0) / 4.0, Percent(12.5));
+        assert_eq!(Percent(50.0) + Percent(25.0), Percent(75.0));
+        assert_eq!(Percent(50.0) - Percent(75.0), Percent(-25.0));
+        assert!(Percent(10.0) < Percent(20.0));
+    }
+
+    #[test]
+    fn test_size_conversions() {
+        assert_eq!(Size::Length(10).as_length(), Some(10));
+        assert_eq!(Size::Percent(10.0).as_length(), None);
+        assert_eq!(Size::Percent(10.0).as_percent(), Some(Percent(10.0)));
+        assert_eq!(Size::Auto.as_percent(), None);
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Size::length(10), Size::Length(10));
+        assert_eq!(Size::percent(50.0), Size::from(Percent(50.0)));
+        assert_eq!(Size::auto(), Size::from(Auto));
+        assert_eq!(Margin::length(2), Margin::from(2));
+        assert_eq!(Margin::length(-2), Margin::from(-2));
+        assert_eq!(Margin::percent(-10.0), Margin::from(Percent(-10.0)));
+        assert_eq!(Margin::auto(), Margin::Auto);
+        assert_eq!(Padding::length(1), Padding::from(1));
+        assert_eq!(Padding::percent(5.0), Padding::Percent(5.0));
+        assert_eq!(FlexBasis::length(3), FlexBasis::Length(3));
+        assert_eq!(FlexBasis::percent(25.0), FlexBasis::Percent(25.0));
+        assert_eq!(FlexBasis::auto(), FlexBasis::from(Auto));
+        assert_eq!(GridTrack::length(4), GridTrack::from(4));
+        assert_eq!(GridTrack::percent(50.0), GridTrack::from(Percent(50.0)));
+        assert_eq!(GridTrack::auto(), GridTrack::from(Auto));
+        assert_eq!(
+            GridTrack::repeat(2, [1, 2]),
+            GridTrack::Repeat(2, vec![GridTrack::Length(1), GridTrack::Length(2)])
+        );
+        assert_eq!(GridPlacement::line(2), GridPlacement::from(2));
+        assert_eq!(GridPlacement::span(2), GridPlacement::Span(2));
+    }
+
+    #[test]
+    fn test_size_clamp() {
+        assert_eq!(Size::Length(5).clamp(10, 20), Size::Length(10));
+        assert_eq!(Size::Length(15).clamp(10, 20), Size::Length(15));
+        assert_eq!(Size::Length(25).clamp(10, 20), Size::Length(20));
+        assert_eq!(Size::Length(25).clamp(30, 20), Size::Length(30));
+        assert_eq!(Size::Length(25).clamp(Size::Unset, 20), Size::Length(20));
+        assert_eq!(
+            Size::Length(25).clamp(Percent(10.0), Auto),
+            Size::Length(25)
+        );
+
+        assert_eq!(
+            Size::Percent(5.0).clamp(Percent(10.0), Percent(20.0)),
+            Size::Percent(10.0)
+        );
+        assert_eq!(
+            Size::Percent(25.0).clamp(10, Percent(20.0)),
+            Size::Percent(20.0)
+        );
+
+        assert_eq!(Size::Auto.clamp(10, 20), Size::Auto);
+        assert_eq!(Margin::Length(25).clamp(10, 20), Margin::Length(20));
+    }
+
+    #[test]
+    fn test_grid_track_repetitions() {
+        // Repetitions within repetitions are expanded, since taffy doesn't support them.
+        let tracks = GridTrack::to_taffy(&[
+            GridTrack::fr(1.0),
+            GridTrack::repeat(2, [GridTrack::length(1), GridTrack::repeat(2, [3])]),
+        ]);
+        assert_eq!(
+            tracks,
+            vec![
+                style_helpers::fr(1.0),
+                TrackSizingFunction::Repeat(
+                    GridTrackRepetition::Count(2),
+                    vec![
+                        style_helpers::length(1.0),
+                        style_helpers::length(3.0),
+                        style_helpers::length(3.0),
+                    ],
+                ),
+            ],
+        );
     }
 }
@@ -1,9 +1,9 @@
-use crate::canvas::Canvas;
+use crate::{canvas::Canvas, color, components::intercept_caught_panic};
+use async_io::Timer;
 use crossterm::{
     cursor,
     event::{self, Event, EventStream},
     execute, queue, terminal,
-    tty::IsTty,
 };
 use futures::{
     future::pending,
@@ -11,14 +11,24 @@ use futures::{
 };
 use std::{
     collections::VecDeque,
-    io::{self, stdout, Write},
+    future::Future,
+    io::{self, stdout, IsTerminal, Write},
+    mem, panic,
     pin::Pin,
-    sync::{Arc, Mutex, Weak},
+    ptr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Once, Weak,
+    },
     task::{Context, Poll, Waker},
+    thread::{self, ThreadId},
+    time::Duration,
 };
 
 // Re-exports for basic types.
-pub use crossterm::event::{KeyCode, KeyEventKind, KeyEventState, KeyModifiers};
+pub use crossterm::event::{
+    KeyCode, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEventKind,
+};
 
 /// An event fired when a key is pressed.
 #[derive(Clone, Debug)]
@@ -33,16 +43,110 @@ pub struct KeyEvent {
     pub kind: KeyEventKind,
 }
 
+/// An event fired when the mouse is moved, clicked, scrolled, etc.
+///
+/// Mouse events are only reported when rendering in fullscreen mode.
+#[derive(Clone, Debug)]
+pub struct MouseEvent {
+    /// The column that the event occurred on, relative to the left of the canvas.
+    pub column: u16,
+
+    /// The row that the event occurred on, relative to the top of the canvas.
+    pub row: u16,
+
+    /// The kind of mouse event, including the button involved if any.
+    pub kind: MouseEventKind,
+
+    /// The modifiers that were active when the event occurred.
+    pub modifiers: KeyModifiers,
+}
+
 /// An event fired by the terminal.
 #[non_exhaustive]
 #[derive(Clone, Debug)]
 pub enum TerminalEvent {
     /// A key event, fired when a key is pressed.
     Key(KeyEvent),
-    /// A resize event, fired when the terminal is resized.
+    /// A mouse event, fired when the mouse is moved, clicked, or scrolled in fullscreen mode.
+    Mouse(MouseEvent),
+    /// A resize event, fired when the terminal is resized. Contains the new width and height.
+    ///
+    /// When a resize is in progress, e.g. while the user drags the edge of the window, only the
+    /// final size is reported.
     Resize(u16, u16),
 }
 
+/// How long the terminal must go without being resized again before a resize is reported.
+const RESIZE_DEBOUNCE_PERIOD: Duration = Duration::from_millis(50);
+
+/// Wraps a stream of terminal events so that bursts of resize events are coalesced into one
+/// event for the final size, which is reported once the size has been stable for
+/// [`RESIZE_DEBOUNCE_PERIOD`]. Other events are passed through in order.
+struct DebouncedResizes<S> {
+    inner: S,
+    pending_resize: Option<(u16, u16)>,
+    timer: Option<Timer>,
+    queued: Option<TerminalEvent>,
+}
+
+impl<S> DebouncedResizes<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            inner,
+            pending_resize: None,
+            timer: None,
+            queued: None,
+        }
+    }
+
+    fn take_pending_resize(&mut self) -> Option<TerminalEvent> {
+        self.timer = None;
+        self.pending_resize
+            .take()
+            .map(|(width, height)| TerminalEvent::Resize(width, height))
+    }
+}
+
+impl<S: Stream<Item = TerminalEvent> + Unpin> Stream for DebouncedResizes<S> {
+    type Item = TerminalEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(event) = this.queued.take() {
+            return Poll::Ready(Some(event));
+        }
+        loop {
+            match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(TerminalEvent::Resize(width, height))) => {
+                    this.pending_resize = Some((width, height));
+                    this.timer = Some(Timer::after(RESIZE_DEBOUNCE_PERIOD));
+                }
+                Poll::Ready(Some(event)) => {
+                    // Anything that happened after the resize needs to be reported after it.
+                    return Poll::Ready(Some(match this.take_pending_resize() {
+                        Some(resize) => {
+                            this.queued = Some(event);
+                            resize
+                        }
+                        None => event,
+                    }));
+                }
+                Poll::Ready(None) => return Poll::Ready(this.take_pending_resize()),
+                Poll::Pending => break,
+            }
+        }
+        let timer_fired = this
+            .timer
+            .as_mut()
+            .is_some_and(|timer| Pin::new(timer).poll(cx).is_ready());
+        if timer_fired {
+            Poll::Ready(this.take_pending_resize())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
 struct TerminalEventsInner {
     pending: VecDeque<TerminalEvent>,
     waker: Option<Waker>,
@@ -71,15 +175,159 @@ trait TerminalImpl: Write {
     fn width(&self) -> Option<u16>;
     fn is_raw_mode_enabled(&self) -> bool;
     fn clear_canvas(&mut self) -> io::Result<()>;
+    // Replaces the previously written canvas, if it hasn't been cleared.
     fn write_canvas(&mut self, canvas: &Canvas) -> io::Result<()>;
     fn event_stream(&mut self) -> io::Result<BoxStream<'static, TerminalEvent>>;
 }
 
+/// Returns whether every row of the canvas fits within the terminal without wrapping.
+fn fits_width(canvas: &Canvas, width: Option<u16>) -> bool {
+    width.is_some_and(|width| (0..canvas.height()).all(|y| canvas.row_width(y) <= width as usize))
+}
+
+/// The terminal modes which need to be reverted when a [`StdTerminal`] goes away.
+#[derive(Clone, Copy)]
+struct TerminalModes {
+    fullscreen: bool,
+    raw_mode_enabled: bool,
+}
+
+impl TerminalModes {
+    fn restore(self, dest: &mut impl Write) -> io::Result<()> {
+        if self.raw_mode_enabled {
+            terminal::disable_raw_mode()?;
+            if self.fullscreen {
+                execute!(dest, event::DisableMouseCapture)?;
+            }
+            execute!(dest, event::PopKeyboardEnhancementFlags)?;
+        }
+        if self.fullscreen {
+            queue!(dest, terminal::LeaveAlternateScreen)?;
+        }
+        execute!(dest, cursor::Show)
+    }
+}
+
+/// The output that a [`StdTerminal`] writes to. It's shared so that the panic hook can restore the
+/// terminal through the same output.
+#[derive(Clone)]
+struct TerminalOutput(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl TerminalOutput {
+    fn new<W: Write + Send + 'static>(dest: W) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(dest))))
+    }
+}
+
+impl Write for TerminalOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).flush()
+    }
+}
+
+// The modes of the active terminals and their outputs, keyed by terminal id, along with the thread
+// which last updated them. Whichever of a terminal's drop or the panic hook gets to its modes first
+// restores them, so cleanup happens exactly once.
+//
+// Each terminal's own modes are tracked, but raw mode is a property of the process's controlling
+// terminal, so running multiple terminals at once isn't supported: the first one to go away
+// disables raw mode for all of them.
+static ACTIVE_TERMINAL_MODES: Mutex<Vec<(u64, ThreadId, TerminalModes, TerminalOutput)>> =
+    Mutex::new(Vec::new());
+
+fn set_active_terminal_modes(id: u64, modes: TerminalModes, dest: TerminalOutput) {
+    let owner = thread::current().id();
+    let mut active = ACTIVE_TERMINAL_MODES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match active
+        .iter_mut()
+        .find(|(active_id, _, _, _)| *active_id == id)
+    {
+        Some(entry) => *entry = (id, owner, modes, dest),
+        None => active.push((id, owner, modes, dest)),
+    }
+}
+
+fn restore_active_terminal_modes(id: u64) {
+    let modes = {
+        let mut active = ACTIVE_TERMINAL_MODES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        active
+            .iter()
+            .position(|(active_id, _, _, _)| *active_id == id)
+            .map(|i| active.remove(i))
+    };
+    if let Some((_, _, modes, mut dest)) = modes {
+        let _ = modes.restore(&mut dest);
+    }
+}
+
+fn restore_owned_active_terminal_modes(owner: ThreadId) {
+    let owned = {
+        let mut active = ACTIVE_TERMINAL_MODES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (owned, others) = mem::take(&mut *active)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, active_owner, _, _)| *active_owner == owner);
+        *active = others;
+        owned
+    };
+    // The most recently created terminal is restored first, in case it was layered on top of the
+    // others.
+    for (_, _, modes, mut dest) in owned.into_iter().rev() {
+        let _ = modes.restore(&mut dest);
+    }
+}
+
+// Installs a panic hook which restores the terminal before the panic is reported. Without it, the
+// message would be printed in raw mode or to the alternate screen, and would be garbled or lost
+// when the terminal is restored during unwinding. This also covers `panic = "abort"`, where the
+// terminal is never dropped.
+//
+// Only the terminals owned by the panicking thread are restored. A panic on any other thread, e.g.
+// a background worker whose panic is handled by whoever joins it, leaves them as they are. Panics
+// which are caught by an `ErrorBoundary` are left for the boundary to report, and the terminal is
+// left as it is, since the application keeps running. They're still passed on to the previous
+// hook if the application installed its own, e.g. to log them, but not to the default hook, which
+// would print them over the canvas.
+pub(crate) fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let prev_hook = panic::take_hook();
+        // Taking the hook again returns the default one, which the previous hook is only the same
+        // object as if the application didn't install its own.
+        let default_hook = panic::take_hook();
+        #[allow(ambiguous_wide_pointer_comparisons)]
+        let prev_hook_is_default = ptr::eq(&*prev_hook, &*default_hook);
+        panic::set_hook(Box::new(move |info| {
+            if intercept_caught_panic(info.location()) {
+                if !prev_hook_is_default {
+                    prev_hook(info);
+                }
+                return;
+            }
+            restore_owned_active_terminal_modes(thread::current().id());
+            prev_hook(info);
+        }));
+    });
+}
+
 struct StdTerminal {
-    dest: io::Stdout,
+    // Identifies the terminal's modes among those of the active terminals.
+    id: u64,
+    dest: TerminalOutput,
     fullscreen: bool,
+    supports_truecolor: bool,
     raw_mode_enabled: bool,
-    prev_canvas_height: u16,
+    // The canvas currently displayed, as it was written, and the terminal width at the time.
+    prev_canvas: Option<(Canvas, Option<u16>)>,
 }
 
 impl Write for StdTerminal {
@@ -102,64 +350,134 @@ impl TerminalImpl for StdTerminal {
     }
 
     fn clear_canvas(&mut self) -> io::Result<()> {
-        if self.prev_canvas_height == 0 {
+        let Some((prev_canvas, _)) = self.prev_canvas.take() else {
             return Ok(());
-        }
-        let lines_to_rewind = self.prev_canvas_height - if self.fullscreen { 1 } else { 0 };
+        };
+        self.rewind(&prev_canvas)?;
         queue!(
             self.dest,
-            cursor::MoveToPreviousLine(lines_to_rewind as _),
             terminal::Clear(terminal::ClearType::FromCursorDown)
         )
     }
 
     fn write_canvas(&mut self, canvas: &Canvas) -> io::Result<()> {
-        self.prev_canvas_height = canvas.height() as _;
-        if self.fullscreen {
-            canvas.write_ansi_without_final_newline(self)?;
-        } else {
-            canvas.write_ansi(self)?;
+        let mut canvas = canvas.clone();
+        if !self.supports_truecolor {
+            canvas.map_colors(color::to_ansi256);
+        }
+        let width = self.width();
+        match self.prev_canvas.take() {
+            Some((prev_canvas, prev_width))
+                if prev_width == width
+                    && fits_width(&prev_canvas, width)
+                    && fits_width(&canvas, width) =>
+            {
+                canvas.write_ansi_diff(&mut self.dest, &prev_canvas, self.fullscreen)?;
+            }
+            prev_canvas => {
+                if let Some((prev_canvas, _)) = prev_canvas {
+                    self.rewind(&prev_canvas)?;
+                    queue!(
+                        self.dest,
+                        terminal::Clear(terminal::ClearType::FromCursorDown)
+                    )?;
+                }
+                if self.fullscreen {
+                    canvas.write_ansi_without_final_newline(&mut self.dest)?;
+                } else {
+                    canvas.write_ansi(&mut self.dest)?;
+                }
+            }
         }
+        self.prev_canvas = Some((canvas, width));
         Ok(())
     }
 
     fn event_stream(&mut self) -> io::Result<BoxStream<'static, TerminalEvent>> {
         self.set_raw_mode_enabled(true)?;
 
-        Ok(EventStream::new()
-            .filter_map(|event| async move {
-                match event {
-                    Ok(Event::Key(event)) => Some(TerminalEvent::Key(KeyEvent {
-                        code: event.code,
-                        modifiers: event.modifiers,
-                        kind: event.kind,
-                    })),
-                    Ok(Event::Resize(width, height)) => Some(TerminalEvent::Resize(width, height)),
-                    _ => None,
-                }
-            })
-            .boxed())
+        Ok(DebouncedResizes::new(
+            EventStream::new()
+                .filter_map(|event| async move {
+                    match event {
+                        Ok(Event::Key(event)) => Some(TerminalEvent::Key(KeyEvent {
+                            code: event.code,
+                            modifiers: event.modifiers,
+                            kind: event.kind,
+                        })),
+                        Ok(Event::Mouse(event)) => Some(TerminalEvent::Mouse(MouseEvent {
+                            column: event.column,
+                            row: event.row,
+                            kind: event.kind,
+                            modifiers: event.modifiers,
+                        })),
+                        Ok(Event::Resize(width, height)) => {
+                            Some(TerminalEvent::Resize(width, height))
+                        }
+                        _ => None,
+                    }
+                })
+                .boxed(),
+        )
+        .boxed())
     }
 }
 
 impl StdTerminal {
-    fn new(fullscreen: bool) -> io::Result<Self>
+    fn new<W: Write + Send + 'static>(dest: W, fullscreen: bool) -> io::Result<Self>
     where
         Self: Sized,
     {
-        let mut dest = stdout();
+        let mut dest = TerminalOutput::new(dest);
         queue!(dest, cursor::Hide)?;
         if fullscreen {
-            queue!(dest, terminal::EnterAlternateScreen)?;
+            // Mouse coordinates are reported relative to the screen, so the canvas needs to start
+            // at its top left corner.
+            queue!(dest, terminal::EnterAlternateScreen, cursor::MoveTo(0, 0))?;
         }
+        install_panic_hook();
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        set_active_terminal_modes(
+            id,
+            TerminalModes {
+                fullscreen,
+                raw_mode_enabled: false,
+            },
+            dest.clone(),
+        );
         Ok(Self {
+            id,
             dest,
             fullscreen,
+            supports_truecolor: color::supports_truecolor(),
             raw_mode_enabled: false,
-            prev_canvas_height: 0,
+            prev_canvas: None,
         })
     }
 
+    /// Moves the cursor to the start of the first line of the previously written canvas.
+    fn rewind(&mut self, prev_canvas: &Canvas) -> io::Result<()> {
+        // If the terminal is narrower than a line, the line wraps. Most terminals re-wrap lines
+        // when they're resized, so this is measured against the current width rather than the
+        // width at the time the canvas was written.
+        let width = self.width();
+        let mut lines = (0..prev_canvas.height())
+            .map(|y| match width {
+                Some(width) if width > 0 => prev_canvas.row_width(y).div_ceil(width as _).max(1),
+                _ => 1,
+            })
+            .sum::<usize>();
+        if self.fullscreen {
+            lines = lines.saturating_sub(1);
+        }
+        if lines > 0 {
+            queue!(self.dest, cursor::MoveToPreviousLine(lines as _))
+        } else {
+            queue!(self.dest, cursor::MoveToColumn(0))
+        }
+    }
+
     fn set_raw_mode_enabled(&mut self, raw_mode_enabled: bool) -> io::Result<()> {
         if raw_mode_enabled != self.raw_mode_enabled {
             if raw_mode_enabled {
@@ -181,6 +499,14 @@ impl StdTerminal {
                 execute!(self.dest, event::PopKeyboardEnhancementFlags)?;
             }
             self.raw_mode_enabled = raw_mode_enabled;
+            set_active_terminal_modes(
+                self.id,
+                TerminalModes {
+                    fullscreen: self.fullscreen,
+                    raw_mode_enabled,
+                },
+                self.dest.clone(),
+            );
         }
         Ok(())
     }
@@ -188,14 +514,11 @@ impl StdTerminal {
 
 impl Drop for StdTerminal {
     fn drop(&mut self) {
-        let _ = self.set_raw_mode_enabled(false);
-        if self.fullscreen {
-            let _ = queue!(self.dest, terminal::LeaveAlternateScreen);
-        }
-        let _ = execute!(self.dest, cursor::Show);
+        restore_active_terminal_modes(self.id);
     }
 }
 
+/// Provides access to the output written to a mock terminal.
 #[cfg(test)]
 pub struct MockTerminalOutput {
     state: Arc<Mutex<MockTerminalState>>,
@@ -203,6 +526,7 @@ pub struct MockTerminalOutput {
 
 #[cfg(test)]
 impl MockTerminalOutput {
+    /// Returns all of the canvases that have been written to the terminal.
     pub fn canvases(&self) -> Vec<Canvas> {
         self.state.lock().unwrap().canvases.clone()
     }
@@ -297,20 +621,66 @@ impl TerminalImpl for MockTerminal {
     }
 }
 
+/// A terminal which receives its events from a channel and discards its output, used by
+/// `TestRenderer`.
+#[cfg(any(test, feature = "testing"))]
+struct ScriptedTerminal {
+    width: Option<u16>,
+    events: Option<futures::channel::mpsc::UnboundedReceiver<TerminalEvent>>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Write for ScriptedTerminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl TerminalImpl for ScriptedTerminal {
+    fn width(&self) -> Option<u16> {
+        self.width
+    }
+
+    fn is_raw_mode_enabled(&self) -> bool {
+        false
+    }
+
+    fn clear_canvas(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_canvas(&mut self, _canvas: &Canvas) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn event_stream(&mut self) -> io::Result<BoxStream<'static, TerminalEvent>> {
+        Ok(match self.events.take() {
+            Some(events) => events.chain(futures::stream::pending()).boxed(),
+            None => futures::stream::pending().boxed(),
+        })
+    }
+}
+
 pub(crate) struct Terminal {
     inner: Box<dyn TerminalImpl>,
     event_stream: Option<BoxStream<'static, TerminalEvent>>,
     subscribers: Vec<Weak<Mutex<TerminalEventsInner>>>,
     received_ctrl_c: bool,
+    resized: bool,
 }
 
 impl Terminal {
-    pub fn new() -> io::Result<Self> {
-        Ok(Self::new_with_impl(StdTerminal::new(false)?))
+    pub fn new<W: Write + Send + 'static>(dest: W) -> io::Result<Self> {
+        Ok(Self::new_with_impl(StdTerminal::new(dest, false)?))
     }
 
-    pub fn fullscreen() -> io::Result<Self> {
-        Ok(Self::new_with_impl(StdTerminal::new(true)?))
+    pub fn fullscreen<W: Write + Send + 'static>(dest: W) -> io::Result<Self> {
+        Ok(Self::new_with_impl(StdTerminal::new(dest, true)?))
     }
 
     #[cfg(test)]
@@ -319,12 +689,26 @@ impl Terminal {
         (Self::new_with_impl(term), output)
     }
 
+    /// Creates a terminal of the given width whose events are supplied by the returned sender.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn scripted(
+        width: Option<u16>,
+    ) -> (Self, futures::channel::mpsc::UnboundedSender<TerminalEvent>) {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let term = ScriptedTerminal {
+            width,
+            events: Some(rx),
+        };
+        (Self::new_with_impl(term), tx)
+    }
+
     fn new_with_impl<T: TerminalImpl + 'static>(inner: T) -> Self {
         Self {
             inner: Box::new(inner),
             event_stream: None,
             subscribers: Vec::new(),
             received_ctrl_c: false,
+            resized: false,
         }
     }
 
@@ -348,6 +732,12 @@ impl Terminal {
         self.received_ctrl_c
     }
 
+    /// Returns whether the terminal has been resized since the last call, in which case the
+    /// canvas needs to be laid out again and fully repainted.
+    pub fn take_resized(&mut self) -> bool {
+        std::mem::take(&mut self.resized)
+    }
+
     pub async fn wait(&mut self) {
         match &mut self.event_stream {
             Some(event_stream) => {
@@ -363,6 +753,7 @@ impl Terminal {
                     if self.received_ctrl_c {
                         return;
                     }
+                    let is_resize = matches!(event, TerminalEvent::Resize(..));
                     self.subscribers.retain(|subscriber| {
                         if let Some(subscriber) = subscriber.upgrade() {
                             let mut subscriber = subscriber.lock().unwrap();
@@ -375,6 +766,10 @@ impl Terminal {
                             false
                         }
                     });
+                    if is_resize {
+                        self.resized = true;
+                        return;
+                    }
                 }
             }
             None => pending().await,
@@ -405,19 +800,24 @@ impl Write for Terminal {
 }
 
 /// Returns whether the standard output is a TTY terminal.
+///
+/// If it isn't, for example because the output is piped to another program or redirected to a
+/// file, [`render_loop`](crate::ElementExt::render_loop) and
+/// [`fullscreen`](crate::ElementExt::fullscreen) render the element once as plain text instead.
 pub fn stdout_is_tty() -> bool {
-    stdout().is_tty()
+    stdout().is_terminal()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
+    use futures::stream::StreamExt;
 
     #[test]
     fn test_std_terminal() {
         // There's unfortunately not much here we can really test, but we'll do our best.
         // TODO: Is there a library we can use to emulate terminal input/output?
-        let mut terminal = Terminal::new().unwrap();
+        let mut terminal = Terminal::new(std::io::stdout()).unwrap();
         assert!(!terminal.is_raw_mode_enabled());
         assert!(!terminal.received_ctrl_c());
         assert!(!terminal.is_raw_mode_enabled());
@@ -425,6 +825,95 @@ mod tests {
         terminal.write_canvas(&canvas).unwrap();
     }
 
+    #[test]
+    fn test_terminal_modes_restore() {
+        let mut output = Vec::new();
+        let modes = super::TerminalModes {
+            fullscreen: true,
+            raw_mode_enabled: false,
+        };
+        modes.restore(&mut output).unwrap();
+        assert_eq!(output, b"\x1b[?1049l\x1b[?25h");
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedOutput(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_multiple_terminals_restore() {
+        // Each terminal restores its own modes, through its own output.
+        let (a, b) = (SharedOutput::default(), SharedOutput::default());
+        let term_a = Terminal::new(a.clone()).unwrap();
+        let term_b = Terminal::fullscreen(b.clone()).unwrap();
+        drop(term_a);
+        assert!(a.0.lock().unwrap().ends_with(b"\x1b[?25h"));
+        assert!(!b.0.lock().unwrap().ends_with(b"\x1b[?25h"));
+        drop(term_b);
+        assert!(b.0.lock().unwrap().ends_with(b"\x1b[?1049l\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_panic_on_other_thread_leaves_terminal() {
+        let output = SharedOutput::default();
+        let terminal = Terminal::fullscreen(output.clone()).unwrap();
+        let written = output.0.lock().unwrap().len();
+        std::thread::spawn(|| panic!("background panic"))
+            .join()
+            .unwrap_err();
+        assert_eq!(output.0.lock().unwrap().len(), written);
+        drop(terminal);
+        assert!(output.0.lock().unwrap().ends_with(b"\x1b[?1049l\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_debounced_resizes() {
+        let key = |c| {
+            TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::empty(),
+                kind: KeyEventKind::Press,
+            })
+        };
+        let events = futures::stream::iter(vec![
+            TerminalEvent::Resize(10, 10),
+            TerminalEvent::Resize(20, 20),
+            key('a'),
+            key('b'),
+            TerminalEvent::Resize(30, 30),
+        ]);
+        let events = smol::block_on(super::DebouncedResizes::new(events).collect::<Vec<_>>());
+        assert_eq!(
+            format!("{:?}", events),
+            format!(
+                "{:?}",
+                vec![
+                    TerminalEvent::Resize(20, 20),
+                    key('a'),
+                    key('b'),
+                    TerminalEvent::Resize(30, 30),
+                ]
+            )
+        );
+
+        // Resizes which are followed by nothing else are reported once they stop.
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut events = super::DebouncedResizes::new(rx);
+        tx.unbounded_send(TerminalEvent::Resize(10, 10)).unwrap();
+        tx.unbounded_send(TerminalEvent::Resize(20, 20)).unwrap();
+        let event = smol::block_on(events.next());
+        assert!(matches!(event, Some(TerminalEvent::Resize(20, 20))));
+    }
+
     #[test]
     fn test_stdout_is_tty() {
         let _ = stdout_is_tty();
@@ -0,0 +1,318 @@
+use crate::{
+    canvas::Canvas,
+    clock::Clock,
+    element::ElementExt,
+    render::Tree,
+    terminal::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, Terminal, TerminalEvent},
+};
+use futures::{
+    channel::mpsc,
+    task::{waker, ArcWake},
+};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::Context,
+    time::Duration,
+};
+
+/// The maximum number of consecutive renders a [`TestRenderer`] will perform while waiting for the
+/// tree to settle before concluding that it never will.
+const MAX_SETTLE_RENDERS: usize = 1000;
+
+/// A virtual clock which replaces real time for timers such as
+/// [`use_interval`](crate::hooks::UseInterval::use_interval) when rendering with a
+/// [`TestRenderer`].
+#[derive(Clone, Default)]
+pub(crate) struct MockClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl MockClock {
+    /// Returns the amount of virtual time that has passed since the clock was created.
+    pub fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+/// `TestRenderer` renders an element in a simulated terminal, allowing interactive components to
+/// be tested without a TTY.
+///
+/// Events are delivered to the element with [`send`](TestRenderer::send), and timers created via
+/// [`use_interval`](crate::hooks::UseInterval::use_interval) are driven by a virtual clock which
+/// only advances when [`advance_time`](TestRenderer::advance_time) is called, so animations can be
+/// tested deterministically. After each step, the tree is re-rendered until it settles, and the
+/// resulting frame is captured.
+///
+/// Futures which wait on real I/O or real timers are polled, but never waited on, so they won't
+/// make progress unless they're already able to.
+///
+/// This is only available with the `testing` feature, which is meant to be enabled for
+/// dev-dependencies:
+///
+/// ```toml
+/// [dev-dependencies]
+/// iocraft = { version = "*", features = ["testing"] }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// # use std::time::Duration;
+/// #[component]
+/// fn Counter(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+///     let count = hooks.use_state(|| 0);
+///     hooks.use_terminal_events(move |event| match event {
+///         TerminalEvent::Key(KeyEvent { code: KeyCode::Enter, kind, .. })
+///             if kind != KeyEventKind::Release =>
+///         {
+///             count.set(count.get() + 1);
+///         }
+///         _ => {}
+///     });
+///     hooks.use_interval(Duration::from_secs(1), move || count.set(0));
+///     element!(Text(content: format!("count: {}", count)))
+/// }
+///
+/// let mut element = element!(Counter);
+/// let mut renderer = TestRenderer::new(&mut element);
+/// assert_eq!(renderer.frame(), "count: 0\n");
+///
+/// renderer.send_key(KeyCode::Enter);
+/// renderer.send_key(KeyCode::Enter);
+/// assert_eq!(renderer.frame(), "count: 2\n");
+///
+/// renderer.advance_time(Duration::from_secs(1));
+/// assert_eq!(renderer.frame(), "count: 0\n");
+/// ```
+pub struct TestRenderer<'a> {
+    tree: Tree<'a>,
+    terminal: Terminal,
+    events: mpsc::UnboundedSender<TerminalEvent>,
+    clock: MockClock,
+    width: Option<usize>,
+    frames: Vec<Canvas>,
+}
+
+impl<'a> TestRenderer<'a> {
+    /// Creates a new renderer for the given element and renders the first frame. The element
+    /// will be laid out as if the terminal were infinitely wide.
+    pub fn new<E: ElementExt>(element: &'a mut E) -> Self {
+        Self::with_width(element, None)
+    }
+
+    /// Creates a new renderer for the given element and renders the first frame. If `width` is
+    /// given, the element will be laid out as if the terminal were that wide.
+    pub fn with_width<E: ElementExt>(element: &'a mut E, width: Option<usize>) -> Self {
+        let helper = element.helper();
+        let mut tree = Tree::new(element.props_mut(), helper);
+        let clock = MockClock::default();
+        tree.system_mut().clock = Clock::Mock(clock.clone());
+        let (terminal, events) = Terminal::scripted(width.map(|w| w as _));
+        let mut renderer = Self {
+            tree,
+            terminal,
+            events,
+            clock,
+            width,
+            frames: Vec::new(),
+        };
+        renderer.settle();
+        renderer
+    }
+
+    /// Delivers an event to the element, then renders until the tree settles.
+    pub fn send(&mut self, event: TerminalEvent) {
+        self.events
+            .unbounded_send(event)
+            .expect("the terminal should still be receiving events");
+        self.settle();
+    }
+
+    /// Delivers a key press with no modifiers to the element, then renders until the tree
+    /// settles.
+    pub fn send_key(&mut self, code: KeyCode) {
+        self.send(TerminalEvent::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+        }));
+    }
+
+    /// Delivers a key press for each character of the given text, as if it were typed.
+    pub fn send_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.send_key(KeyCode::Char(c));
+        }
+    }
+
+    /// Advances the virtual clock, firing any timers that come due, then renders until the tree
+    /// settles.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.settle();
+    }
+
+    /// Returns the most recently rendered frame as plain text.
+    pub fn frame(&self) -> String {
+        self.canvas().to_string()
+    }
+
+    /// Returns the most recently rendered frame.
+    pub fn canvas(&self) -> &Canvas {
+        self.frames
+            .last()
+            .expect("the first frame is rendered on creation")
+    }
+
+    /// Returns every distinct frame that has been rendered so far, as plain text.
+    pub fn frames(&self) -> Vec<String> {
+        self.frames.iter().map(|c| c.to_string()).collect()
+    }
+
+    /// Returns whether a component has requested that the render loop exit via
+    /// [`SystemContext::exit`](crate::SystemContext::exit).
+    pub fn has_exited(&mut self) -> bool {
+        self.tree.system_context_mut().should_exit()
+    }
+
+    fn settle(&mut self) {
+        let woken = Arc::new(WakeFlag::default());
+        let waker = waker(woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut needs_render = true;
+        for _ in 0..MAX_SETTLE_RENDERS {
+            if needs_render {
+                let output = self.tree.render(self.width, Some(&mut self.terminal));
+                if self.frames.last() != Some(&output.canvas) {
+                    self.frames.push(output.canvas);
+                }
+            }
+
+            // Deliver any pending events to their subscribers, then check for changes. Handlers
+            // invoked while polling may cause changes in components which were already polled,
+            // so we keep polling until nothing is woken.
+            woken.0.store(false, Ordering::SeqCst);
+            let _ = pin!(self.terminal.wait()).poll(&mut cx);
+            needs_render = pin!(self.tree.wait()).poll(&mut cx).is_ready();
+            if !needs_render && !woken.0.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+        panic!(
+            "the element was still changing after {} renders",
+            MAX_SETTLE_RENDERS
+        );
+    }
+}
+
+#[derive(Default)]
+struct WakeFlag(AtomicBool);
+
+impl ArcWake for WakeFlag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::time::Duration;
+
+    #[component]
+    fn Form(mut hooks: Hooks) -> impl Into<AnyElement<'static>> {
+        let value = hooks.use_state(String::new);
+        let submitted = hooks.use_state(|| false);
+        let mut system = hooks.use_context_mut::<SystemContext>();
+
+        hooks.use_terminal_events(move |event| {
+            if let TerminalEvent::Key(KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            }) = event
+            {
+                submitted.set(true);
+            }
+        });
+
+        if submitted.get() {
+            system.exit();
+        }
+
+        element! {
+            Box(flex_direction: FlexDirection::Column, width: 20) {
+                Box(height: 1) {
+                    TextInput(
+                        has_focus: !submitted.get(),
+                        value: value.to_string(),
+                        on_change: move |new_value| value.set(new_value),
+                    )
+                }
+                #(if submitted.get() {
+                    Some(element!(Text(content: format!("submitted: {}", value))))
+                } else {
+                    None
+                })
+            }
+        }
+    }
+
+    #[test]
+    fn test_test_renderer_events() {
+        let mut element = element!(Form);
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), " \n");
+
+        renderer.send_text("hi");
+        assert_eq!(renderer.frame(), "hi \n");
+        assert!(!renderer.has_exited());
+
+        renderer.send_key(KeyCode::Enter);
+        assert!(renderer.frame().contains("submitted: hi"));
+        assert!(renderer.has_exited());
+
+        assert_eq!(
+            renderer.frames(),
+            vec![" \n", "h \n", "hi \n", "hi\nsubmitted: hi\n"]
+        );
+    }
+
+    #[test]
+    fn test_test_renderer_timers() {
+        let mut element = element! {
+            Spinner(frames: vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        };
+        let mut renderer = TestRenderer::new(&mut element);
+        assert_eq!(renderer.frame(), "a\n");
+
+        renderer.advance_time(Duration::from_millis(79));
+        assert_eq!(renderer.frame(), "a\n");
+        renderer.advance_time(Duration::from_millis(1));
+        assert_eq!(renderer.frame(), "b\n");
+        renderer.advance_time(Duration::from_millis(80));
+        assert_eq!(renderer.frame(), "c\n");
+
+        // Ticks which are missed are coalesced, so the animation only advances one frame.
+        renderer.advance_time(Duration::from_millis(240));
+        assert_eq!(renderer.frame(), "a\n");
+        renderer.advance_time(Duration::from_millis(80));
+        assert_eq!(renderer.frame(), "b\n");
+    }
+
+    #[test]
+    fn test_test_renderer_width() {
+        let mut element = element!(Text(content: "foo bar"));
+        let renderer = TestRenderer::with_width(&mut element, Some(4));
+        assert_eq!(renderer.frame(), "foo\nbar\n");
+    }
+}
@@ -0,0 +1,94 @@
+use crate::Color;
+
+/// A role that a color plays in an application, which is resolved to an actual color by the
+/// current [`Theme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SemanticColor {
+    /// The color for the most important or interactive elements, such as headings and links.
+    Primary,
+    /// The color for accents which complement the primary color.
+    Secondary,
+    /// The color for indicating that something succeeded.
+    Success,
+    /// The color for indicating that something needs attention.
+    Warning,
+    /// The color for errors and destructive actions.
+    Danger,
+    /// The color for less important content, such as hints and timestamps.
+    Muted,
+}
+
+/// A set of colors for an application, which components can refer to via [`SemanticColor`]
+/// rather than hard-coding colors.
+///
+/// A theme is provided to a subtree via a [`ContextProvider`](crate::components::ContextProvider),
+/// and read by components using the [`use_theme`](crate::hooks::UseTheme::use_theme) hook.
+/// Built-in components which accept a semantic color, such as [`Text`](crate::components::Text),
+/// resolve it against the nearest theme. If no theme is provided, the default theme is used, which
+/// is made up of the terminal's palette colors.
+///
+/// # Example
+///
+/// ```
+/// # use iocraft::prelude::*;
+/// #[component]
+/// fn Status() -> impl Into<AnyElement<'static>> {
+///     element! {
+///         Text(content: "All systems go", semantic_color: SemanticColor::Success)
+///     }
+/// }
+///
+/// # fn my_element(is_dark: bool) -> impl Into<AnyElement<'static>> {
+/// let theme = Theme {
+///     success: if is_dark { Color::Green } else { Color::DarkGreen },
+///     ..Default::default()
+/// };
+/// element! {
+///     ContextProvider(value: Context::owned(theme)) {
+///         Status
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// The color for [`SemanticColor::Primary`]. Defaults to [`Color::Blue`].
+    pub primary: Color,
+    /// The color for [`SemanticColor::Secondary`]. Defaults to [`Color::Magenta`].
+    pub secondary: Color,
+    /// The color for [`SemanticColor::Success`]. Defaults to [`Color::Green`].
+    pub success: Color,
+    /// The color for [`SemanticColor::Warning`]. Defaults to [`Color::Yellow`].
+    pub warning: Color,
+    /// The color for [`SemanticColor::Danger`]. Defaults to [`Color::Red`].
+    pub danger: Color,
+    /// The color for [`SemanticColor::Muted`]. Defaults to [`Color::DarkGrey`].
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::Blue,
+            secondary: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Yellow,
+            danger: Color::Red,
+            muted: Color::DarkGrey,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the theme's color for the given role.
+    pub fn color(&self, color: SemanticColor) -> Color {
+        match color {
+            SemanticColor::Primary => self.primary,
+            SemanticColor::Secondary => self.secondary,
+            SemanticColor::Success => self.success,
+            SemanticColor::Warning => self.warning,
+            SemanticColor::Danger => self.danger,
+            SemanticColor::Muted => self.muted,
+        }
+    }
+}